@@ -36,6 +36,15 @@ if [ -f "target/wasm32-unknown-unknown/release/stellar_htlc.wasm" ]; then
     # Show file size
     size=$(stat -f%z "target/wasm32-unknown-unknown/release/stellar_htlc.wasm" 2>/dev/null || stat -c%s "target/wasm32-unknown-unknown/release/stellar_htlc.wasm" 2>/dev/null || echo "unknown")
     echo "📏 File size: $size bytes"
+
+    # Fail the build if the contract grows past the size budget. The release
+    # build measures about 152 KiB before wasm-opt, well past 64 KiB, so the
+    # default budget leaves ~5% headroom over that.
+    max_size=${MAX_WASM_SIZE:-163840}
+    if [ "$size" != "unknown" ] && [ "$size" -gt "$max_size" ]; then
+        echo "❌ WASM size $size bytes exceeds the $max_size byte limit"
+        exit 1
+    fi
 else
     echo "❌ Build failed - WASM file not found"
     exit 1
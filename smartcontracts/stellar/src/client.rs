@@ -0,0 +1,65 @@
+//! Host-side helpers for relayers, resolvers and other clients
+//!
+//! Built only with the `client-utils` feature, so none of this ends up in
+//! the contract wasm. Derivations reuse the contract's own helpers and
+//! constants, which keeps both sides in lockstep.
+
+extern crate std;
+
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use std::string::String;
+
+pub use crate::shared::{
+    claim_cutoff, is_claimable_at, is_past_claim_deadline, is_refundable_at, is_valid_timelock,
+};
+pub use crate::types::{
+    DEFAULT_CLAIM_CUTOFF_BUFFER, MAX_PROTOCOL_FEE_BPS, MAX_TIMELOCK_DURATION, MIN_TIMELOCK_DURATION,
+};
+
+/// Derive the ID the contract assigns to a new swap
+///
+/// # Arguments
+/// * `swap_counter` - Contract swap counter after the swap is opened (1 for the first swap)
+/// * `ledger_sequence` - Ledger sequence the swap is opened in
+/// * `timestamp` - Ledger timestamp the swap is opened at
+/// * `amount` - Swap amount
+/// * `hashlock` - Swap hashlock
+///
+/// # Returns
+/// The swap ID as a lowercase hex string
+pub fn derive_swap_id(
+    swap_counter: u64,
+    ledger_sequence: u32,
+    timestamp: u64,
+    amount: i128,
+    hashlock: &[u8; 32],
+) -> String {
+    let data = crate::shared::swap_id_data(swap_counter, ledger_sequence, timestamp, amount, hashlock);
+    let hash: [u8; 32] = Sha256::digest(data).into();
+    let hex = crate::shared::hex_encode(&hash);
+    // hex_encode only emits ASCII hex digits
+    String::from_utf8(hex.to_vec()).unwrap()
+}
+
+/// Calculate the basis-point fee the contract takes from an amount
+///
+/// Ignores fee policy contracts and per-token minimum fees.
+pub fn compute_fee(amount: i128, fee_bps: u32) -> i128 {
+    crate::shared::calculate_protocol_fee(amount, fee_bps)
+}
+
+/// Compute a SHA-256 hashlock from a secret
+///
+/// For salted swaps pass the secret followed by the salt.
+pub fn hash_preimage_sha256(secret: &[u8]) -> [u8; 32] {
+    Sha256::digest(secret).into()
+}
+
+/// Compute a Keccak-256 commitment from a secret
+///
+/// Matches the Ethereum leg when it commits with `keccak256` rather than
+/// SHA-256; the Stellar contract itself only checks SHA-256 hashlocks.
+pub fn hash_preimage_keccak(secret: &[u8]) -> [u8; 32] {
+    Keccak256::digest(secret).into()
+}
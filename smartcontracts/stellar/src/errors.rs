@@ -10,20 +10,43 @@ pub enum HTLCError {
     InvalidFee = 1002,
     InvalidPreimage = 1003,
     InvalidRecipient = 1004,
+    BatchTooLarge = 1005,
+    UnknownEthContract = 1006,
+    MemoTooLong = 1007,
+    InvalidHashlockLength = 1008,
+    PriceDeviation = 1009,
+    PayoutBelowMinimum = 1010,
+    InvalidAdmin = 1011,
     
     // Swap state errors
     SwapNotFound = 2000,
     SwapAlreadyExists = 2001,
     AlreadyClaimed = 2002,
     AlreadyRefunded = 2003,
+    NonceAlreadyUsed = 2004,
+    SwapNotConfirmed = 2005,
+    SwapNotClaimable = 2006,
+    SwapNotPending = 2007,
+    TooManyWatchers = 2008,
+    CounterpartAlreadyLinked = 2009,
+    TemplateNotFound = 2010,
     
     // Timing errors
     TimelockExpired = 3000,
     TimelockNotExpired = 3001,
+    OrderExpired = 3002,
+    ClaimWindowClosed = 3003,
+    EthFinalityExpired = 3004,
+    FinalityDelayActive = 3005,
     
     // Authorization errors
     Unauthorized = 4000,
     NotInitiated = 4001,
+    AddressBlocked = 4002,
+    CreatorNotAllowed = 4003,
+    InvalidAttestation = 4004,
+    AdminRenounced = 4005,
+    RecipientNotRegistered = 4006,
     
     // External contract errors
     TokenTransferFailed = 5000,
@@ -33,9 +56,16 @@ pub enum HTLCError {
     // Resolver errors
     ResolverNotFound = 6000,
     ResolverNotActive = 6001,
+    SwapExceedsResolverLimit = 6002,
+    InvalidResolverNonce = 6003,
+    ResolverLimitReached = 6004,
+    ResolverInUse = 6005,
     
     // Contract state errors
     AlreadyInitialized = 7000,
     NotInitialized = 7001,
+    InvariantViolated = 7002,
+    ReentrantCall = 7003,
+    InitializationUnconfirmed = 7004,
 }
 
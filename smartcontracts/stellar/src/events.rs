@@ -1,5 +1,5 @@
-use soroban_sdk::{Env, Address, String, BytesN, symbol_short, contracttype};
-use crate::types::SwapStatus;
+use soroban_sdk::{Env, Address, String, BytesN, IntoVal, Val, Vec, symbol_short, contracttype};
+use crate::types::{FailureCode, SwapStatus, EVENT_SCHEMA_VERSION};
 
 /// Event structures for cross-chain monitoring compatibility
 
@@ -37,9 +37,11 @@ pub struct FundsClaimedEvent {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FundsRefundedEvent {
-    pub swap_id: BytesN<32>,
+    pub swap_id: String,
     pub sender: Address,
     pub amount: i128,
+    pub resolver: Option<Address>,
+    pub elapsed: u64,
 }
 
 #[contracttype]
@@ -47,13 +49,60 @@ pub struct FundsRefundedEvent {
 pub struct SwapFailedEvent {
     pub swap_id: String,
     pub sender: Address,
+    pub code: FailureCode,
     pub reason: String,
+    pub resolver: Option<Address>,
+    pub elapsed: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolverDefaultedEvent {
+    pub swap_id: String,
+    pub resolver: Address,
+    pub outcome: SwapStatus,
+    pub elapsed: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapStatusChangedEvent {
+    pub swap_id: String,
+    pub from: SwapStatus,
+    pub to: SwapStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeCollectedEvent {
+    pub swap_id: String,
+    pub token: Address,
+    pub fee_amount: i128,
+    pub fee_recipient: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CounterpartLinkedEvent {
+    pub swap_id: String,
+    pub counterpart_id: BytesN<32>,
+    pub proof_source: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimFallbackEvent {
+    pub swap_id: String,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub error_code: u32,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SwapStatusUpdatedEvent {
-    pub swap_id: BytesN<32>,
+    pub swap_id: String,
     pub old_status: SwapStatus,
     pub new_status: SwapStatus,
 }
@@ -65,6 +114,14 @@ pub struct ResolverRegisteredEvent {
     pub collateral: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralIncreasedEvent {
+    pub resolver: Address,
+    pub amount: i128,
+    pub total_collateral: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ResolverDeactivatedEvent {
@@ -85,7 +142,21 @@ pub struct FeeRecipientUpdatedEvent {
     pub new_recipient: Address,
 }
 
-/// Event emission functions
+// Event emission functions
+
+/// Publish an event with the schema version appended to its topics
+/// 
+/// Every event goes through here so consumers can rely on the last topic
+/// being `EVENT_SCHEMA_VERSION`.
+pub(crate) fn publish_event<T, D>(env: &Env, topics: T, data: D)
+where
+    T: IntoVal<Env, Vec<Val>>,
+    D: IntoVal<Env, Val>,
+{
+    let mut topics: Vec<Val> = topics.into_val(env);
+    topics.push_back(EVENT_SCHEMA_VERSION.into_val(env));
+    env.events().publish(topics, data);
+}
 
 pub fn emit_contract_initialized(
     env: &Env,
@@ -99,7 +170,8 @@ pub fn emit_contract_initialized(
         protocol_fee_bps,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("init"),),
         event
     );
@@ -129,7 +201,8 @@ pub fn emit_swap_initialized(
         eth_tx_hash,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("swap_init"), swap_id),
         event
     );
@@ -149,7 +222,8 @@ pub fn emit_funds_claimed(
         preimage,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("claimed"), swap_id),
         event
     );
@@ -157,17 +231,22 @@ pub fn emit_funds_claimed(
 
 pub fn emit_funds_refunded(
     env: &Env,
-    swap_id: BytesN<32>,
+    swap_id: String,
     sender: Address,
     amount: i128,
+    resolver: Option<Address>,
+    elapsed: u64,
 ) {
     let event = FundsRefundedEvent {
         swap_id: swap_id.clone(),
         sender,
         amount,
+        resolver,
+        elapsed,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("refunded"), swap_id),
         event
     );
@@ -177,23 +256,136 @@ pub fn emit_swap_failed(
     env: &Env,
     swap_id: String,
     sender: Address,
+    code: FailureCode,
     reason: String,
+    resolver: Option<Address>,
+    elapsed: u64,
 ) {
     let event = SwapFailedEvent {
         swap_id: swap_id.clone(),
         sender,
+        code,
         reason,
+        resolver,
+        elapsed,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("failed"), swap_id.clone()),
         event
     );
 }
 
+pub fn emit_resolver_defaulted(
+    env: &Env,
+    swap_id: String,
+    resolver: Address,
+    outcome: SwapStatus,
+    elapsed: u64,
+) {
+    let event = ResolverDefaultedEvent {
+        swap_id,
+        resolver: resolver.clone(),
+        outcome,
+        elapsed,
+    };
+    
+    publish_event(
+        env,
+        (symbol_short!("res_dflt"), resolver),
+        event
+    );
+}
+
+pub fn emit_swap_status_changed(
+    env: &Env,
+    swap_id: String,
+    watchers: Vec<Address>,
+    from: SwapStatus,
+    to: SwapStatus,
+) {
+    let event = SwapStatusChangedEvent {
+        swap_id,
+        from,
+        to,
+    };
+    
+    for watcher in watchers.iter() {
+        publish_event(
+            env,
+            (symbol_short!("swap_stat"), watcher),
+            event.clone()
+        );
+    }
+}
+
+pub fn emit_fee_collected(
+    env: &Env,
+    swap_id: String,
+    token: Address,
+    fee_amount: i128,
+    fee_recipient: Address,
+) {
+    let event = FeeCollectedEvent {
+        swap_id: swap_id.clone(),
+        token,
+        fee_amount,
+        fee_recipient,
+    };
+    
+    publish_event(
+        env,
+        (symbol_short!("fee_coll"), swap_id),
+        event
+    );
+}
+
+pub fn emit_claim_fallback(
+    env: &Env,
+    swap_id: String,
+    recipient: Address,
+    token: Address,
+    amount: i128,
+    error_code: u32,
+) {
+    let event = ClaimFallbackEvent {
+        swap_id: swap_id.clone(),
+        recipient,
+        token,
+        amount,
+        error_code,
+    };
+    
+    publish_event(
+        env,
+        (symbol_short!("claim_fb"), swap_id),
+        event
+    );
+}
+
+pub fn emit_counterpart_linked(
+    env: &Env,
+    swap_id: String,
+    counterpart_id: BytesN<32>,
+    proof_source: Address,
+) {
+    let event = CounterpartLinkedEvent {
+        swap_id: swap_id.clone(),
+        counterpart_id,
+        proof_source,
+    };
+    
+    publish_event(
+        env,
+        (symbol_short!("linked"), swap_id),
+        event
+    );
+}
+
 pub fn emit_swap_status_updated(
     env: &Env,
-    swap_id: BytesN<32>,
+    swap_id: String,
     old_status: SwapStatus,
     new_status: SwapStatus,
 ) {
@@ -203,7 +395,8 @@ pub fn emit_swap_status_updated(
         new_status,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("status"), swap_id),
         event
     );
@@ -219,12 +412,32 @@ pub fn emit_resolver_registered(
         collateral,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("res_reg"), resolver),
         event
     );
 }
 
+pub fn emit_collateral_increased(
+    env: &Env,
+    resolver: Address,
+    amount: i128,
+    total_collateral: i128,
+) {
+    let event = CollateralIncreasedEvent {
+        resolver: resolver.clone(),
+        amount,
+        total_collateral,
+    };
+    
+    publish_event(
+        env,
+        (symbol_short!("coll_inc"), resolver),
+        event
+    );
+}
+
 pub fn emit_resolver_deactivated(
     env: &Env,
     resolver: Address,
@@ -233,7 +446,8 @@ pub fn emit_resolver_deactivated(
         resolver: resolver.clone(),
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("res_deact"), resolver),
         event
     );
@@ -249,7 +463,8 @@ pub fn emit_protocol_fee_updated(
         new_fee_bps,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("fee_upd"),),
         event
     );
@@ -265,7 +480,8 @@ pub fn emit_fee_recipient_updated(
         new_recipient,
     };
     
-    env.events().publish(
+    publish_event(
+        env,
         (symbol_short!("fee_rec"),),
         event
     );
@@ -0,0 +1,65 @@
+use soroban_sdk::{contractclient, Address, Env, String};
+
+/// Interface for contracts notified after a swap is claimed
+/// 
+/// A swap created with a `claim_hook` invokes `on_htlc_claimed` on that
+/// contract once the funds have been released to the recipient. Hook
+/// failures are reported via an event and never revert the claim.
+#[contractclient(name = "ClaimHookClient")]
+pub trait ClaimHook {
+    /// Called after a successful claim and transfer
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Identifier of the claimed swap
+    /// * `recipient` - Address that received the funds
+    /// * `amount` - Amount released to the recipient, net of fees
+    fn on_htlc_claimed(env: Env, swap_id: String, recipient: Address, amount: i128);
+}
+
+/// Interface for contracts notified after a swap is refunded
+/// 
+/// A swap created with a `refund_hook` invokes `on_swap_refunded` on that
+/// contract once the funds have been returned. Hook failures are reported
+/// via an event and never revert the refund.
+#[contractclient(name = "RefundHookClient")]
+pub trait RefundHook {
+    /// Called after a successful refund and transfer
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Identifier of the refunded swap
+    /// * `amount` - Amount returned to the refund address
+    fn on_swap_refunded(env: Env, swap_id: String, amount: i128);
+}
+
+/// Interface for external fee policy contracts
+/// 
+/// When a fee policy is configured, the fee taken on claim is quoted by
+/// `get_fee` instead of the internal basis-point calculation. Quotes are
+/// capped at `MAX_PROTOCOL_FEE_BPS` of the amount.
+#[contractclient(name = "FeePolicyClient")]
+pub trait FeePolicy {
+    /// Quote the fee for a swap
+    /// 
+    /// # Arguments
+    /// * `token` - Token of the swap
+    /// * `amount` - Swap amount the fee is taken from
+    /// * `sender` - Address that created the swap
+    fn get_fee(env: Env, token: Address, amount: i128, sender: Address) -> i128;
+}
+
+/// Interface for price oracles guarding against mispriced swaps
+/// 
+/// When an oracle is configured, swaps recording a counter amount are
+/// rejected if their rate strays too far from the quoted price.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Quote the expected rate of a token
+    /// 
+    /// The rate is the token amount per unit of the EVM counter asset,
+    /// normalized by decimals and scaled by `RATE_SCALE`, as returned by
+    /// `get_swap_rate`.
+    /// 
+    /// # Arguments
+    /// * `token` - Stellar token being priced
+    fn price(env: Env, token: Address) -> i128;
+}
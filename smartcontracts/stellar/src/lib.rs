@@ -1,21 +1,69 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Env, String, BytesN, Bytes, Vec, panic_with_error};
+// Contract entry points mirror their on-chain argument lists
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contractimpl, token, vec, xdr::{FromXdr, ToXdr}, Address, Env, IntoVal, InvokeError, String, BytesN, Bytes, Val, Vec, panic_with_error};
 
 mod types;
 mod storage;
 mod events;
 mod errors;
+mod interfaces;
+mod shared;
+
+#[cfg(feature = "client-utils")]
+pub mod client;
 
 #[cfg(test)]
 mod test;
 
+#[cfg(test)]
+mod test_fixtures;
+
 #[cfg(test)]
 mod test_comprehensive;
 
+#[cfg(test)]
+mod test_custom_account;
+
+#[cfg(test)]
+mod test_claim_hook;
+
+#[cfg(test)]
+mod test_fee_policy;
+
+#[cfg(test)]
+mod test_price_oracle;
+
+#[cfg(test)]
+mod test_signed_order;
+
+#[cfg(test)]
+mod test_eth_attestation;
+
+#[cfg(test)]
+mod test_budget;
+
+#[cfg(test)]
+mod test_properties;
+
+#[cfg(test)]
+mod test_stellar_asset;
+
+#[cfg(test)]
+mod test_reentrancy;
+
+#[cfg(test)]
+mod test_client;
+
+#[cfg(test)]
+mod test_storage_keys;
+
 pub use types::*;
-pub use storage::*;
+use storage::*;
+use shared::*;
 pub use events::*;
 pub use errors::*;
+pub use interfaces::*;
 
 /// Stellar HTLC Contract for Cross-Chain Atomic Swaps
 /// 
@@ -33,22 +81,43 @@ impl StellarHTLC {
     /// * `admin` - Contract administrator address
     /// * `fee_recipient` - Address to receive protocol fees
     /// * `protocol_fee_bps` - Protocol fee in basis points (default: 30 = 0.3%)
+    /// * `confirm_within` - Optional number of ledgers within which the admin
+    ///   must call `confirm_initialization`; swaps are refused until it does,
+    ///   so a mistyped admin is caught before any funds are locked
     pub fn initialize(
         env: Env,
         admin: Address,
         fee_recipient: Address,
         protocol_fee_bps: u32,
+        confirm_within: Option<u32>,
     ) {
         admin.require_auth();
         
+        // A renounced contract must never get an admin back
+        if has_admin(&env) || is_admin_renounced(&env) {
+            panic_with_error!(&env, HTLCError::AlreadyInitialized);
+        }
+        
+        // The contract can neither administer itself nor collect its own fees
+        let contract = env.current_contract_address();
+        if admin == contract {
+            panic_with_error!(&env, HTLCError::InvalidAdmin);
+        }
+        if fee_recipient == contract {
+            panic_with_error!(&env, HTLCError::InvalidRecipient);
+        }
+        
         // Validate fee is reasonable (max 5%)
-        if protocol_fee_bps > 500 {
+        if protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
             panic_with_error!(&env, HTLCError::InvalidFee);
         }
         
         // Store configuration
         set_admin(&env, &admin);
-        set_fee_recipient(&env, &fee_recipient);
+        set_fee_splits(&env, &Vec::from_array(&env, [FeeSplit {
+            recipient: fee_recipient.clone(),
+            share_bps: 10_000,
+        }]));
         set_protocol_fee_bps(&env, protocol_fee_bps);
         
         // Initialize counters
@@ -56,11 +125,60 @@ impl StellarHTLC {
         set_total_swaps_created(&env, 0);
         set_total_swaps_completed(&env, 0);
         
+        if !invariant_violations(&env).is_empty() {
+            panic_with_error!(&env, HTLCError::InvariantViolated);
+        }
+        
         // Emit initialization event
-        env.events().publish(
+        publish_event(
+            &env,
             ("initialize",),
             (admin.clone(), fee_recipient.clone(), protocol_fee_bps)
         );
+        
+        // Hold swaps until the admin proves it controls its address
+        if let Some(confirm_within) = confirm_within {
+            let deadline = env.ledger().sequence().saturating_add(confirm_within);
+            set_initialization_deadline(&env, Some(deadline));
+            publish_event(
+                &env,
+                ("initialization_pending",),
+                (admin, deadline)
+            );
+        }
+    }
+
+    /// Confirm an initialization made with `confirm_within` (admin only)
+    /// 
+    /// Must be called by the admin by the deadline ledger set at
+    /// initialization. Swaps are accepted from then on; a contract left
+    /// unconfirmed past the deadline never accepts swaps and has to be
+    /// redeployed.
+    pub fn confirm_initialization(env: Env) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        let deadline = get_initialization_deadline(&env)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::AlreadyInitialized));
+        if env.ledger().sequence() > deadline {
+            panic_with_error!(&env, HTLCError::InitializationUnconfirmed);
+        }
+
+        set_initialization_deadline(&env, None);
+
+        publish_event(
+            &env,
+            ("initialization_confirmed",),
+            admin
+        );
+    }
+
+    /// Get the ledger by which a pending initialization must be confirmed
+    /// 
+    /// # Returns
+    /// Deadline ledger, or None once confirmed or when no confirmation was asked
+    pub fn get_initialization_deadline(env: Env) -> Option<u32> {
+        get_initialization_deadline(&env)
     }
 
     /// Create a new HTLC swap
@@ -74,7 +192,7 @@ impl StellarHTLC {
     /// * `amount` - Amount to lock in the swap
     /// * `eth_contract` - Ethereum contract address for cross-chain coordination
     /// * `eth_chain_id` - Ethereum chain ID (1 for mainnet, 11155111 for sepolia)
-    /// * `resolver_address` - Optional 1inch Fusion+ resolver address
+    /// * `options` - Optional swap terms; `SwapOptions::default()` for none
     pub fn create_swap(
         env: Env,
         sender: Address,
@@ -85,317 +203,3845 @@ impl StellarHTLC {
         amount: i128,
         eth_contract: Address,
         eth_chain_id: u64,
-        resolver_address: Option<Address>,
+        options: SwapOptions,
     ) -> String {
-        // Require authorization from sender
-        sender.require_auth();
-        
-        // Validate inputs
-        if amount <= 0 {
-            panic_with_error!(&env, HTLCError::InvalidAmount);
-        }
-        
-        let current_time = env.ledger().timestamp();
-        if timelock <= current_time + 3600 { // Minimum 1 hour
-            panic_with_error!(&env, HTLCError::InvalidTimelock);
-        }
-        
-        if timelock > current_time + 604800 { // Maximum 7 days
-            panic_with_error!(&env, HTLCError::InvalidTimelock);
-        }
-        
-        // Check resolver if provided
-        if let Some(resolver) = &resolver_address {
-            let resolver_info = get_resolver(&env, resolver);
-            if resolver_info.is_none() {
-                panic_with_error!(&env, HTLCError::ResolverNotActive);
-            }
-        }
-        
-        // Generate unique swap ID
-        let mut swap_counter = get_swap_counter(&env);
-        swap_counter += 1;
-        set_swap_counter(&env, swap_counter);
-        
-        let swap_id = generate_swap_id(&env, &sender, &recipient, &token, amount, &hashlock);
+        // Require authorization from sender, bound to every swap term so a
+        // relayer can't reroute refunds or change the claim conditions
+        sender.require_auth_for_args(
+            (
+                recipient.clone(),
+                token.clone(),
+                amount,
+                hashlock.clone(),
+                timelock,
+                eth_contract.clone(),
+                eth_chain_id,
+                options.clone(),
+            ).into_val(&env)
+        );
         
-        // Check if swap already exists
-        if get_swap(&env, &swap_id).is_some() {
-            panic_with_error!(&env, HTLCError::SwapAlreadyExists);
-        }
+        open_swap(&env, NewSwap {
+            sender,
+            recipient,
+            hashlock,
+            timelock,
+            token,
+            amount,
+            eth_contract,
+            eth_chain_id,
+            options,
+        })
+    }
+
+    /// Create a new HTLC swap, or return the existing one on an exact retry
+    /// 
+    /// Swaps created here are keyed by sender and hashlock. Repeating a call
+    /// with identical terms while the swap is still pending returns the
+    /// existing swap ID without moving funds again; any other reuse of the
+    /// key fails with `SwapAlreadyExists`.
+    /// 
+    /// # Arguments
+    /// Same as `create_swap`
+    pub fn create_swap_idempotent(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        hashlock: BytesN<32>,
+        timelock: u64,
+        token: Address,
+        amount: i128,
+        eth_contract: Address,
+        eth_chain_id: u64,
+        options: SwapOptions,
+    ) -> String {
+        // Require authorization from sender, bound to every swap term so a
+        // relayer can't reroute refunds or change the claim conditions
+        sender.require_auth_for_args(
+            (
+                recipient.clone(),
+                token.clone(),
+                amount,
+                hashlock.clone(),
+                timelock,
+                eth_contract.clone(),
+                eth_chain_id,
+                options.clone(),
+            ).into_val(&env)
+        );
 
-        // Create swap object
-        let swap = Swap {
-            id: swap_id.clone(),
+        let new_swap = NewSwap {
             sender: sender.clone(),
-            recipient: recipient.clone(),
-            token: token.clone(),
-            amount,
+            recipient,
             hashlock: hashlock.clone(),
             timelock,
-            status: SwapStatus::Pending,
-            created_at: current_time,
-            claimed_at: None,
-            refunded_at: None,
-            preimage: None,
+            token,
+            amount,
             eth_contract,
             eth_chain_id,
-            resolver: resolver_address.clone(),
+            options,
         };
 
-        // Store the swap
-        set_swap(&env, &swap_id, &swap);
-        
-        // Track user swaps
-        add_user_swap(&env, &sender, &swap_id);
-        
-        // Update statistics
-        let total_swaps = get_total_swaps_created(&env) + 1;
-        set_total_swaps_created(&env, total_swaps);
+        if let Some(swap_id) = get_idempotent_swap(&env, &sender, &hashlock) {
+            let swap = get_swap(&env, &swap_id)
+                .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+            if swap.status == SwapStatus::Pending && is_same_swap(&swap, &new_swap) {
+                return swap_id;
+            }
+            panic_with_error!(&env, HTLCError::SwapAlreadyExists);
+        }
 
-        // Emit event
-        env.events().publish(
-            ("swap_created",),
+        let swap_id = open_swap(&env, new_swap);
+        set_idempotent_swap(&env, &sender, &hashlock, &swap_id);
+        swap_id
+    }
+
+    /// Create a new HTLC swap whose timelock is a duration from now
+    /// 
+    /// The contract resolves the timelock as the current ledger time plus
+    /// `duration_secs`, so the swap can't fall outside the valid window
+    /// because of client clock skew. The resolved timelock is emitted in
+    /// `swap_created`. Use `create_swap` when both chains must agree on an
+    /// exact timestamp.
+    /// 
+    /// # Arguments
+    /// Same as `create_swap`, except:
+    /// * `duration_secs` - Seconds from now until the sender can refund,
+    ///   above `MIN_TIMELOCK_DURATION` and at most `MAX_TIMELOCK_DURATION`
+    pub fn create_swap_with_duration(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        hashlock: BytesN<32>,
+        duration_secs: u64,
+        token: Address,
+        amount: i128,
+        eth_contract: Address,
+        eth_chain_id: u64,
+        options: SwapOptions,
+    ) -> String {
+        // Require authorization from sender, bound to every swap term so a
+        // relayer can't reroute refunds or change the claim conditions
+        sender.require_auth_for_args(
             (
-                swap_id.clone(),
-                sender,
-                recipient,
+                recipient.clone(),
+                token.clone(),
                 amount,
-                timelock,
-            )
+                hashlock.clone(),
+                duration_secs,
+                eth_contract.clone(),
+                eth_chain_id,
+                options.clone(),
+            ).into_val(&env)
         );
 
-        swap_id
+        open_swap(&env, NewSwap {
+            sender,
+            recipient,
+            hashlock,
+            timelock: env.ledger().timestamp().saturating_add(duration_secs),
+            token,
+            amount,
+            eth_contract,
+            eth_chain_id,
+            options,
+        })
     }
 
-    /// Claim a swap by providing the correct preimage
+    /// Register the ed25519 public key used to sign orders for a maker
     /// 
     /// # Arguments
-    /// * `swap_id` - Unique identifier of the swap to claim
-    /// * `preimage` - Secret that hashes to the swap's hashlock
-    pub fn claim_swap(env: Env, swap_id: String, preimage: BytesN<32>) {
-        let mut swap = get_swap(&env, &swap_id)
-            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
-
-        // Check swap status
-        if swap.status == SwapStatus::Claimed {
-            panic_with_error!(&env, HTLCError::AlreadyClaimed);
-        }
-        
-        if swap.status == SwapStatus::Refunded {
-            panic_with_error!(&env, HTLCError::AlreadyRefunded);
-        }
-
-        // Check timelock hasn't expired
-        let current_time = env.ledger().timestamp();
-        if current_time >= swap.timelock {
-            panic_with_error!(&env, HTLCError::TimelockExpired);
-        }
-
-        // Verify preimage matches hashlock
-        let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
-        let hash = env.crypto().sha256(&preimage_bytes);
-        if hash.to_array() != swap.hashlock.to_array() {
-            panic_with_error!(&env, HTLCError::InvalidPreimage);
-        }
-
-        // Only recipient can claim
-        swap.recipient.require_auth();
-
-        // TODO: Implement token transfer
-        // This would typically involve calling the token contract's transfer method
-        // For now, we'll mark the swap as claimed
-        
-        // Update swap
-        swap.status = SwapStatus::Claimed;
-        swap.claimed_at = Some(current_time);
-        swap.preimage = Some(preimage.clone());
-        
-        set_swap(&env, &swap_id, &swap);
+    /// * `maker` - Address whose funds signed orders may pull
+    /// * `public_key` - Ed25519 public key that signs the maker's orders
+    pub fn register_maker_key(env: Env, maker: Address, public_key: BytesN<32>) {
+        maker.require_auth();
 
-        // Update statistics
-        let total_completed = get_total_swaps_completed(&env) + 1;
-        set_total_swaps_completed(&env, total_completed);
+        set_maker_key(&env, &maker, &public_key);
 
-        // Emit event
-        env.events().publish(
-            ("swap_claimed",),
-            (swap_id, swap.recipient.clone(), preimage)
+        publish_event(
+            &env,
+            ("maker_key_registered",),
+            (maker, public_key)
         );
     }
 
-    /// Refund a swap after timelock expiration
+    /// Create a swap from an order signed off-chain by the maker
+    /// 
+    /// The maker's registered ed25519 key must have signed the canonical XDR
+    /// of the order terms, which name this contract so the order can't be
+    /// filled on another deployment. Funds are pulled with `transfer_from` against the
+    /// allowance the maker granted this contract, so the maker doesn't need
+    /// to submit a transaction per order. Each nonce can be used only once.
     /// 
     /// # Arguments
-    /// * `swap_id` - Unique identifier of the swap to refund
-    pub fn refund_swap(env: Env, swap_id: String) {
-        let mut swap = get_swap(&env, &swap_id)
-            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+    /// * `order` - Order terms and the maker's signature over them
+    pub fn fill_signed_order(env: Env, order: SignedOrder) -> String {
+        let terms = order.terms;
 
-        // Check swap status
-        if swap.status == SwapStatus::Claimed {
-            panic_with_error!(&env, HTLCError::AlreadyClaimed);
-        }
-        
-        if swap.status == SwapStatus::Refunded {
-            panic_with_error!(&env, HTLCError::AlreadyRefunded);
+        if terms.contract != env.current_contract_address() {
+            panic_with_error!(&env, HTLCError::Unauthorized);
         }
 
-        // Check timelock has expired
-        let current_time = env.ledger().timestamp();
-        if current_time < swap.timelock {
-            panic_with_error!(&env, HTLCError::TimelockNotExpired);
+        // Signing key must belong to the maker
+        match get_maker_key(&env, &terms.maker) {
+            Some(key) if key == terms.maker_public_key => {}
+            _ => panic_with_error!(&env, HTLCError::Unauthorized),
         }
 
-        // Only sender can refund
-        swap.sender.require_auth();
+        if env.ledger().timestamp() > terms.expiry {
+            panic_with_error!(&env, HTLCError::OrderExpired);
+        }
 
-        // TODO: Implement token transfer back to sender
-        
-        // Update swap
-        swap.status = SwapStatus::Refunded;
-        swap.refunded_at = Some(current_time);
-        
-        set_swap(&env, &swap_id, &swap);
+        if is_nonce_used(&env, &terms.maker, terms.nonce) {
+            panic_with_error!(&env, HTLCError::NonceAlreadyUsed);
+        }
 
-        // Emit event
-        env.events().publish(
-            ("swap_refunded",),
-            (swap_id, swap.sender.clone())
+        // Panics if the signature doesn't match the terms
+        env.crypto().ed25519_verify(
+            &terms.maker_public_key,
+            &terms.clone().to_xdr(&env),
+            &order.signature,
         );
+
+        set_nonce_used(&env, &terms.maker, terms.nonce);
+
+        open_swap(&env, NewSwap {
+            sender: terms.maker,
+            recipient: terms.recipient,
+            hashlock: terms.hashlock,
+            timelock: terms.timelock,
+            token: terms.token,
+            amount: terms.amount,
+            eth_contract: terms.eth_contract,
+            eth_chain_id: terms.eth_chain_id,
+            options: SwapOptions {
+                use_allowance: true,
+                ..SwapOptions::default()
+            },
+        })
     }
 
-    /// Register a new resolver for 1inch Fusion+ integration
+    /// Save swap terms shared by the swaps of a repeated corridor
+    /// 
+    /// The template is owned by its creator; unless it is public, only the
+    /// owner can create swaps from it.
     /// 
     /// # Arguments
-    /// * `resolver` - Resolver address
-    /// * `collateral_token` - Token used for collateral
-    /// * `min_collateral` - Minimum collateral amount required
-    pub fn register_resolver(
-        env: Env,
-        resolver: Address,
-        collateral_token: Address,
-        min_collateral: i128,
-    ) {
-        let admin = get_admin(&env);
-        admin.require_auth();
+    /// * `owner` - Address creating the template
+    /// * `template` - Shared swap terms
+    /// 
+    /// # Returns
+    /// ID of the new template
+    pub fn create_template(env: Env, owner: Address, template: SwapTemplate) -> u64 {
+        owner.require_auth();
 
-        if min_collateral <= 0 {
-            panic_with_error!(&env, HTLCError::InsufficientCollateral);
+        // The offset must yield a valid timelock whenever it is applied
+        if !is_valid_timelock(0, template.timelock_offset) {
+            panic_with_error!(&env, HTLCError::InvalidTimelock);
         }
 
-        let resolver_info = ResolverInfo {
-            resolver: resolver.clone(),
-            collateral_token,
-            min_collateral,
-            is_active: true,
-            total_resolved: 0,
-            created_at: env.ledger().timestamp(),
-        };
-
-        set_resolver(&env, &resolver, &resolver_info);
+        let template_id = get_template_counter(&env) + 1;
+        set_template_counter(&env, template_id);
+        set_template(&env, template_id, &TemplateInfo { owner: owner.clone(), template });
 
-        env.events().publish(
-            ("resolver_registered",),
-            (resolver, min_collateral)
+        publish_event(
+            &env,
+            ("template_created",),
+            (template_id, owner)
         );
+
+        template_id
     }
 
-    /// Update protocol fee (admin only)
+    /// Create a swap from a saved template
+    /// 
+    /// The token, chain, resolver and fee payer come from the template, and
+    /// the timelock is the current time plus the template's offset. Every
+    /// other option takes its default.
     /// 
     /// # Arguments
-    /// * `new_fee_bps` - New protocol fee in basis points
-    pub fn update_protocol_fee(env: Env, new_fee_bps: u32) {
-        let admin = get_admin(&env);
-        admin.require_auth();
+    /// * `sender` - Address creating the swap (must have auth)
+    /// * `template_id` - Template to fill the swap terms from
+    /// * `recipient` - Address that can claim the swap with correct preimage
+    /// * `hashlock` - Hash of the secret required to claim
+    /// * `amount` - Amount to lock in the swap
+    pub fn create_swap_from_template(
+        env: Env,
+        sender: Address,
+        template_id: u64,
+        recipient: Address,
+        hashlock: BytesN<32>,
+        amount: i128,
+    ) -> String {
+        sender.require_auth();
 
-        if new_fee_bps > 500 { // Max 5%
-            panic_with_error!(&env, HTLCError::InvalidFee);
+        let TemplateInfo { owner, template } = get_template(&env, template_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::TemplateNotFound));
+        if !template.is_public && sender != owner {
+            panic_with_error!(&env, HTLCError::Unauthorized);
         }
 
-        let old_fee = get_protocol_fee_bps(&env);
-        set_protocol_fee_bps(&env, new_fee_bps);
+        open_swap(&env, NewSwap {
+            sender,
+            recipient,
+            hashlock,
+            timelock: env.ledger().timestamp().saturating_add(template.timelock_offset),
+            token: template.token,
+            amount,
+            eth_contract: template.eth_contract,
+            eth_chain_id: template.eth_chain_id,
+            options: SwapOptions {
+                resolver: template.resolver,
+                fee_payer: template.fee_payer,
+                ..SwapOptions::default()
+            },
+        })
+    }
 
-        env.events().publish(
-            ("fee_updated",),
-            (old_fee, new_fee_bps)
-        );
+    /// Get a swap template and its owner
+    pub fn get_template(env: Env, template_id: u64) -> Option<TemplateInfo> {
+        get_template(&env, template_id)
     }
 
-    /// Mark a swap as failed (admin only)
+    /// Claim a swap by providing the correct preimage
+    /// 
+    /// Claims close `claim_cutoff_buffer` seconds before the timelock, or at
+    /// the swap's claim deadline if that comes first. The recipient must
+    /// authorize the claim until the swap's public window opens, after which
+    /// any caller may submit it.
     /// 
     /// # Arguments
-    /// * `swap_id` - Unique identifier of the swap to mark as failed
-    /// * `reason` - Reason for failure
-    pub fn mark_swap_failed(env: Env, swap_id: String, reason: String) {
-        let admin = get_admin(&env);
-        admin.require_auth();
+    /// * `swap_id` - Unique identifier of the swap to claim
+    /// * `preimage` - Secret that hashes to the swap's hashlock
+    /// * `salt` - Salt for swaps committed to as `sha256(preimage || salt)`
+    pub fn claim_swap(env: Env, swap_id: String, preimage: BytesN<32>, salt: Option<BytesN<32>>) {
+        execute_claim(&env, swap_id, preimage, salt, None);
+    }
 
-        let mut swap = get_swap(&env, &swap_id)
+    /// Consent to claims of a swap submitted by any caller
+    /// 
+    /// Lets a recipient whose key is kept offline, e.g. on a hardware wallet,
+    /// sign once up front so a relayer can claim as soon as the secret is
+    /// known. The preimage is still checked and the funds still go to the
+    /// recipient.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    pub fn preauthorize_claim(env: Env, swap_id: String) {
+        let swap = get_swap_core(&env, &swap_id)
             .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
-
-        // Only allow marking as failed if not already claimed or refunded
         if swap.status == SwapStatus::Claimed {
             panic_with_error!(&env, HTLCError::AlreadyClaimed);
         }
-        
         if swap.status == SwapStatus::Refunded {
             panic_with_error!(&env, HTLCError::AlreadyRefunded);
         }
+        swap.recipient.require_auth_for_args((swap_id.clone(),).into_val(&env));
 
-        // Update swap status
-        swap.status = SwapStatus::Failed;
-        set_swap(&env, &swap_id, &swap);
+        set_claim_preauthorized(&env, &swap_id);
 
-        // Emit event
-        emit_swap_failed(&env, swap_id, swap.sender.clone(), reason);
+        publish_event(
+            &env,
+            ("claim_preauthorized",),
+            (swap_id, swap.recipient)
+        );
     }
 
-    /// Check if a swap exists
+    /// Check whether a swap's recipient preauthorized claims by any caller
+    pub fn is_claim_preauthorized(env: Env, swap_id: String) -> bool {
+        is_claim_preauthorized(&env, &swap_id)
+    }
+
+    /// Record a swap's preimage without claiming it
+    /// 
+    /// Anyone may publish a preimage once it is known, e.g. after a claim
+    /// that reverted for an unrelated reason, so it stays on record for
+    /// cross-chain dispute resolution. The swap's status and funds are
+    /// untouched.
     /// 
     /// # Arguments
-    /// * `swap_id` - Unique identifier of the swap to check
-    pub fn swap_exists(env: Env, swap_id: String) -> bool {
-        get_swap(&env, &swap_id).is_some()
-    }
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `preimage` - Secret that hashes to the swap's hashlock
+    /// * `salt` - Salt for swaps committed to as `sha256(preimage || salt)`
+    pub fn record_preimage(env: Env, swap_id: String, preimage: BytesN<32>, salt: Option<BytesN<32>>) {
+        let swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+        if !matches_hashlock(&env, &swap, &preimage, &salt) {
+            panic_with_error!(&env, HTLCError::InvalidPreimage);
+        }
 
-    // View functions
+        set_revealed_preimage(&env, &swap_id, &preimage);
 
-    /// Get swap details by ID
-    pub fn get_swap_details(env: Env, swap_id: String) -> Option<Swap> {
-        get_swap(&env, &swap_id)
+        publish_event(
+            &env,
+            ("preimage_recorded",),
+            (swap_id, preimage)
+        );
     }
 
-    /// Get contract statistics
-    pub fn get_contract_stats(env: Env) -> ContractStats {
-        ContractStats {
-            total_swaps_created: get_total_swaps_created(&env),
-            total_swaps_completed: get_total_swaps_completed(&env),
-            protocol_fee_bps: get_protocol_fee_bps(&env),
-            admin: get_admin(&env),
-            fee_recipient: get_fee_recipient(&env),
-        }
+    /// Claim a swap for its recipient as the swap's resolver
+    /// 
+    /// Funds go to the recipient as with `claim_swap`. Each resolver claim
+    /// must carry the resolver's current nonce, which is then incremented.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap to claim
+    /// * `preimage` - Secret that hashes to the swap's hashlock
+    /// * `salt` - Salt for swaps committed to as `sha256(preimage || salt)`
+    /// * `nonce` - Resolver's current nonce (see `get_resolver_nonce`)
+    pub fn claim_swap_as_resolver(
+        env: Env,
+        swap_id: String,
+        preimage: BytesN<32>,
+        salt: Option<BytesN<32>>,
+        nonce: u64,
+    ) {
+        execute_claim(&env, swap_id, preimage, salt, Some(nonce));
+    }
+
+    /// Replace the resolver of a pending swap (sender only)
+    /// 
+    /// Lets the sender move a swap away from a resolver that went offline
+    /// before confirming it. The new resolver must be live and allowed to
+    /// take the swap's amount.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `new_resolver` - Resolver to assign instead
+    pub fn reassign_resolver(env: Env, swap_id: String, new_resolver: Address) {
+        let mut swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+
+        swap.sender.require_auth_for_args((swap_id.clone(), new_resolver.clone()).into_val(&env));
+
+        // Only swaps no resolver has confirmed yet can be reassigned
+        if swap.status != SwapStatus::Pending {
+            panic_with_error!(&env, HTLCError::SwapNotPending);
+        }
+
+        // Move the swap from the old resolver's open work to the new one's
+        let old_resolver = swap.resolver.clone();
+        settle_resolver_swap(&env, &swap_id, &old_resolver, swap.amount, false);
+        let mut resolver_info = eligible_resolver(&env, &new_resolver, swap.amount);
+        resolver_info.open_swaps += 1;
+        resolver_info.open_value += swap.amount;
+        set_resolver(&env, &new_resolver, &resolver_info);
+        add_resolver_swap(&env, &new_resolver, &swap_id);
+
+        swap.resolver = Some(new_resolver.clone());
+        set_swap_core(&env, &swap_id, &swap);
+
+        publish_event(
+            &env,
+            ("resolver_reassigned",),
+            (swap_id, old_resolver, new_resolver)
+        );
+    }
+
+    /// Link a swap to its Ethereum escrow
+    /// 
+    /// Called by the swap's resolver or the counterpart oracle. A swap can be
+    /// linked once and an escrow belongs to at most one swap; only the admin
+    /// can change a link afterwards (see `relink_counterpart`).
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `counterpart_id` - ID of the Ethereum escrow
+    /// * `proof_source` - Resolver or oracle vouching for the link
+    pub fn link_counterpart(env: Env, swap_id: String, counterpart_id: BytesN<32>, proof_source: Address) {
+        let swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+
+        if swap.resolver.as_ref() != Some(&proof_source)
+            && get_counterpart_oracle(&env).as_ref() != Some(&proof_source)
+        {
+            panic_with_error!(&env, HTLCError::Unauthorized);
+        }
+        proof_source.require_auth_for_args((swap_id.clone(), counterpart_id.clone()).into_val(&env));
+
+        if get_swap_counterpart(&env, &swap_id).is_some() {
+            panic_with_error!(&env, HTLCError::CounterpartAlreadyLinked);
+        }
+
+        link_swap_counterpart(&env, &swap_id, &counterpart_id, proof_source);
+    }
+
+    /// Watch a swap for status changes
+    /// 
+    /// Each status transition of the swap emits a `swap_stat` event with the
+    /// watcher as a topic, so wallets can follow their swaps without tailing
+    /// every event. Watching a swap twice has no effect.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `watcher` - Address to notify (at most `MAX_SWAP_WATCHERS` per swap)
+    pub fn watch_swap(env: Env, swap_id: String, watcher: Address) {
+        watcher.require_auth();
+
+        if get_swap_core(&env, &swap_id).is_none() {
+            panic_with_error!(&env, HTLCError::SwapNotFound);
+        }
+
+        let mut watchers = get_swap_watchers(&env, &swap_id);
+        if watchers.contains(&watcher) {
+            return;
+        }
+        if watchers.len() >= MAX_SWAP_WATCHERS {
+            panic_with_error!(&env, HTLCError::TooManyWatchers);
+        }
+        watchers.push_back(watcher.clone());
+        set_swap_watchers(&env, &swap_id, &watchers);
+
+        publish_event(
+            &env,
+            ("swap_watched",),
+            (swap_id, watcher)
+        );
+    }
+
+    /// Stop watching a swap
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `watcher` - Address to stop notifying
+    pub fn unwatch_swap(env: Env, swap_id: String, watcher: Address) {
+        watcher.require_auth();
+
+        let mut watchers = get_swap_watchers(&env, &swap_id);
+        let Some(index) = watchers.first_index_of(&watcher) else {
+            return;
+        };
+        watchers.remove(index);
+        set_swap_watchers(&env, &swap_id, &watchers);
+
+        publish_event(
+            &env,
+            ("swap_unwatched",),
+            (swap_id, watcher)
+        );
+    }
+
+    /// Refund a swap after timelock expiration
+    /// 
+    /// Swaps with a claim deadline can also be refunded once it passes
+    /// unclaimed, and swaps the admin marked failed or whose counterpart was
+    /// attested refunded can be refunded right away. Funds always return to
+    /// the swap's refund address, whoever triggers the refund.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap to refund
+    /// * `caller` - Sender, refund address or refund authority triggering the
+    ///   refund (defaults to the sender)
+    pub fn refund_swap(env: Env, swap_id: String, caller: Option<Address>) {
+        let mut swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+
+        // Check swap status
+        if swap.status == SwapStatus::Claimed {
+            panic_with_error!(&env, HTLCError::AlreadyClaimed);
+        }
+        
+        if swap.status == SwapStatus::Refunded {
+            panic_with_error!(&env, HTLCError::AlreadyRefunded);
+        }
+
+        // Check timelock has expired, or the claim deadline passed unclaimed
+        let current_time = env.ledger().timestamp();
+        if !is_swap_refundable(&env, &swap_id, &swap, current_time) {
+            panic_with_error!(&env, HTLCError::TimelockNotExpired);
+        }
+
+        // Only the sender, the refund address or the refund authority can
+        // refund; the authorization is scoped to this swap
+        let caller = caller.unwrap_or_else(|| swap.sender.clone());
+        if caller != swap.sender
+            && caller != swap.refund_to
+            && Some(&caller) != swap.refund_authority.as_ref()
+        {
+            panic_with_error!(&env, HTLCError::Unauthorized);
+        }
+        caller.require_auth_for_args((swap_id.clone(),).into_val(&env));
+        enter_guard(&env);
+
+        // Update swap before any token call
+        transition(&env, &swap_id, &mut swap, SwapStatus::Refunded);
+        swap.refunded_at = Some(current_time);
+        
+        set_swap_core(&env, &swap_id, &swap);
+        settle_resolver_swap(&env, &swap_id, &swap.resolver, swap.amount, false);
+        release_total_locked(&env, &swap.token, swap.amount);
+
+        // Return locked funds, including any prepaid fee, to the refund address
+        let locked = swap.amount + swap.prepaid_fee;
+        transfer_or_fail(
+            &env,
+            &token::Client::new(&env, &swap.token),
+            &env.current_contract_address(),
+            &swap.refund_to,
+            locked,
+        );
+        release_locked_balance(&env, &swap.token, locked);
+        exit_guard(&env);
+
+        // Emit events
+        let elapsed = swap_age(&env, &swap_id, current_time);
+        emit_funds_refunded(&env, swap_id.clone(), swap.sender.clone(), locked, swap.resolver.clone(), elapsed);
+        if let Some(resolver) = &swap.resolver {
+            emit_resolver_defaulted(&env, swap_id.clone(), resolver.clone(), SwapStatus::Refunded, elapsed);
+        }
+        publish_event(
+            &env,
+            ("swap_refunded",),
+            (swap_id.clone(), swap.sender.clone(), swap.refund_to.clone())
+        );
+        notify_refund_hook(&env, &swap_id, &swap, locked);
+    }
+
+    /// Cancel several of the sender's pending swaps at once
+    /// 
+    /// A swap is cancelled only while it is still pending and nothing has
+    /// happened on the counterpart side: no linked counterpart escrow and no
+    /// Ethereum attestation. The sender holds the secret, so each cancel must
+    /// also be authorized by the swap's counterparty, its resolver or else
+    /// its recipient, who may already have funded the Ethereum leg. Swaps
+    /// younger than the cancel cooldown can't be cancelled. Cancelled swaps
+    /// are refunded in full to their refund address. Ineligible swaps are
+    /// skipped rather than failing the batch.
+    /// 
+    /// # Arguments
+    /// * `sender` - Sender of the swaps, authorizing the whole batch once
+    /// * `swap_ids` - Swaps to cancel (at most `MAX_CANCEL_BATCH_SIZE`)
+    /// 
+    /// # Returns
+    /// Outcome for each swap, in the order given
+    pub fn cancel_swaps_batch(env: Env, sender: Address, swap_ids: Vec<String>) -> Vec<CancelOutcome> {
+        sender.require_auth();
+
+        if swap_ids.len() > MAX_CANCEL_BATCH_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut outcomes = Vec::new(&env);
+        let mut cancelled = 0u32;
+        for swap_id in swap_ids.iter() {
+            let Some(mut swap) = get_swap_core(&env, &swap_id) else {
+                outcomes.push_back(CancelOutcome::NotFound);
+                continue;
+            };
+            if swap.sender != sender {
+                outcomes.push_back(CancelOutcome::NotSender);
+                continue;
+            }
+            if swap.status != SwapStatus::Pending
+                || get_swap_counterpart(&env, &swap_id).is_some()
+                || get_eth_attestation(&env, &swap_id).is_some()
+            {
+                outcomes.push_back(CancelOutcome::NotCancellable);
+                continue;
+            }
+            if swap_age(&env, &swap_id, current_time) < get_cancel_cooldown(&env) {
+                outcomes.push_back(CancelOutcome::OnCooldown);
+                continue;
+            }
+
+            // The counterparty agrees to release the swap
+            let counterparty = swap.resolver.clone().unwrap_or_else(|| swap.recipient.clone());
+            counterparty.require_auth_for_args((swap_id.clone(),).into_val(&env));
+
+            enter_guard(&env);
+
+            // Update swap before any token call
+            transition(&env, &swap_id, &mut swap, SwapStatus::Refunded);
+            swap.refunded_at = Some(current_time);
+
+            set_swap_core(&env, &swap_id, &swap);
+            settle_resolver_swap(&env, &swap_id, &swap.resolver, swap.amount, false);
+            release_total_locked(&env, &swap.token, swap.amount);
+
+            let locked = swap.amount + swap.prepaid_fee;
+            transfer_or_fail(
+                &env,
+                &token::Client::new(&env, &swap.token),
+                &env.current_contract_address(),
+                &swap.refund_to,
+                locked,
+            );
+            release_locked_balance(&env, &swap.token, locked);
+            exit_guard(&env);
+
+            let elapsed = swap_age(&env, &swap_id, current_time);
+            emit_funds_refunded(&env, swap_id.clone(), sender.clone(), locked, swap.resolver.clone(), elapsed);
+            publish_event(
+                &env,
+                ("swap_cancelled",),
+                (swap_id.clone(), sender.clone(), swap.refund_to.clone())
+            );
+            notify_refund_hook(&env, &swap_id, &swap, locked);
+            outcomes.push_back(CancelOutcome::Cancelled);
+            cancelled += 1;
+        }
+
+        publish_event(
+            &env,
+            ("swaps_cancelled",),
+            (sender, cancelled)
+        );
+
+        outcomes
+    }
+
+    /// Refund a swap its sender has abandoned, paying the caller a bounty
+    /// 
+    /// Anyone may call this once the abandonment period has passed after the
+    /// timelock. The bounty is taken from the refunded amount; the rest is
+    /// returned to the swap's refund address.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap to refund
+    /// * `caller` - Address triggering the refund and receiving the bounty
+    /// 
+    /// # Returns
+    /// Bounty paid to the caller
+    pub fn refund_abandoned(env: Env, swap_id: String, caller: Address) -> i128 {
+        caller.require_auth();
+
+        let mut swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+
+        // Check swap status
+        if swap.status == SwapStatus::Claimed {
+            panic_with_error!(&env, HTLCError::AlreadyClaimed);
+        }
+        
+        if swap.status == SwapStatus::Refunded {
+            panic_with_error!(&env, HTLCError::AlreadyRefunded);
+        }
+
+        // Check the abandonment period has elapsed
+        let current_time = env.ledger().timestamp();
+        if current_time < swap.timelock.saturating_add(get_abandonment_period(&env)) {
+            panic_with_error!(&env, HTLCError::TimelockNotExpired);
+        }
+
+        let bounty = refund_with_bounty(&env, &swap_id, &mut swap, &caller, get_abandonment_bounty_bps(&env));
+
+        // Emit event
+        publish_event(
+            &env,
+            ("swap_abandoned",),
+            (swap_id, swap.sender.clone(), caller, bounty, swap.refund_to.clone())
+        );
+
+        bounty
+    }
+
+    /// Refund an expired swap on the sender's behalf, paying the keeper a bounty
+    /// 
+    /// Anyone may call this as soon as the swap becomes refundable. The
+    /// bounty, `refund_bounty_bps` of the amount (none by default), is taken
+    /// from the refunded amount; the rest is returned to the swap's refund
+    /// address.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap to refund
+    /// * `keeper` - Address triggering the refund and receiving the bounty
+    /// 
+    /// # Returns
+    /// Bounty paid to the keeper
+    pub fn keeper_refund(env: Env, swap_id: String, keeper: Address) -> i128 {
+        keeper.require_auth();
+
+        let mut swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+
+        // Check swap status
+        if swap.status == SwapStatus::Claimed {
+            panic_with_error!(&env, HTLCError::AlreadyClaimed);
+        }
+        
+        if swap.status == SwapStatus::Refunded {
+            panic_with_error!(&env, HTLCError::AlreadyRefunded);
+        }
+
+        // Check timelock has expired, or the claim deadline passed unclaimed
+        if !is_swap_refundable(&env, &swap_id, &swap, env.ledger().timestamp()) {
+            panic_with_error!(&env, HTLCError::TimelockNotExpired);
+        }
+
+        let bounty = refund_with_bounty(&env, &swap_id, &mut swap, &keeper, get_refund_bounty_bps(&env));
+
+        // Emit event
+        publish_event(
+            &env,
+            ("swap_keeper_refunded",),
+            (swap_id, swap.sender.clone(), keeper, bounty, swap.refund_to.clone())
+        );
+
+        bounty
+    }
+
+    /// Withdraw funds credited to a claimable balance after a failed payout
+    /// 
+    /// # Arguments
+    /// * `recipient` - Address owning the claimable balance
+    /// * `token` - Token of the claimable balance
+    /// 
+    /// # Returns
+    /// Amount withdrawn
+    pub fn withdraw_claimable(env: Env, recipient: Address, token: Address) -> i128 {
+        recipient.require_auth();
+
+        let amount = get_claimable_balance(&env, &recipient, &token);
+        if amount <= 0 {
+            panic_with_error!(&env, HTLCError::InsufficientBalance);
+        }
+
+        enter_guard(&env);
+        set_claimable_balance(&env, &recipient, &token, 0);
+        release_locked_balance(&env, &token, amount);
+        transfer_or_fail(
+            &env,
+            &token::Client::new(&env, &token),
+            &env.current_contract_address(),
+            &recipient,
+            amount,
+        );
+        exit_guard(&env);
+
+        publish_event(
+            &env,
+            ("claimable_withdrawn",),
+            (recipient, token, amount)
+        );
+
+        amount
+    }
+
+    /// Pay out a resolver's rewards in a token
+    /// 
+    /// Pays the rewards accrued from the resolver's claimed swaps in the
+    /// token, each reserved from that swap's protocol fee when it was
+    /// claimed.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver claiming its rewards
+    /// * `token` - Token the rewarded swaps were made in
+    /// 
+    /// # Returns
+    /// Reward paid
+    pub fn claim_resolver_rewards(env: Env, resolver: Address, token: Address) -> i128 {
+        resolver.require_auth();
+
+        let reward = get_resolver_reward(&env, &resolver, &token);
+        if reward <= 0 {
+            panic_with_error!(&env, HTLCError::InsufficientBalance);
+        }
+
+        enter_guard(&env);
+        set_resolver_reward(&env, &resolver, &token, 0);
+        set_reward_pool(&env, &token, get_reward_pool(&env, &token) - reward);
+        release_locked_balance(&env, &token, reward);
+        transfer_or_fail(
+            &env,
+            &token::Client::new(&env, &token),
+            &env.current_contract_address(),
+            &resolver,
+            reward,
+        );
+        exit_guard(&env);
+
+        publish_event(
+            &env,
+            ("resolver_rewards_claimed",),
+            (resolver, token, reward)
+        );
+
+        reward
+    }
+
+    /// Submit a signed observation of the swap's Ethereum counterpart
+    /// 
+    /// The signer is recovered from a secp256k1 signature over the keccak256
+    /// digest of `payload` and must match the registered attester address.
+    /// The payload names this contract, so it can't be replayed against
+    /// another deployment.
+    /// 
+    /// A `Funded` attestation activates a pending swap; a `Claimed`
+    /// attestation must carry the preimage matching the hashlock; a
+    /// `Refunded` attestation lets the swap be refunded before its
+    /// timelock. Claimed and refunded are final on Ethereum, so no
+    /// attestation is accepted after them, nor a repeat of the stored
+    /// event or transaction, nor any on a settled swap. The latest
+    /// attestation is stored for audit.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Swap the attestation applies to
+    /// * `payload` - XDR-encoded `EthAttestationPayload`
+    /// * `signature` - Compact secp256k1 signature (r || s)
+    /// * `recovery_id` - Signature recovery ID
+    pub fn submit_eth_attestation(
+        env: Env,
+        swap_id: String,
+        payload: Bytes,
+        signature: BytesN<64>,
+        recovery_id: u32,
+    ) {
+        let attester = get_eth_attester(&env)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::InvalidAttestation));
+
+        // Recover the signer's Ethereum address
+        let digest = env.crypto().keccak256(&payload);
+        let public_key = env.crypto().secp256k1_recover(&digest, &signature, recovery_id);
+        if eth_address_from_public_key(&env, &public_key) != attester {
+            panic_with_error!(&env, HTLCError::InvalidAttestation);
+        }
+
+        let attestation = EthAttestationPayload::from_xdr(&env, &payload)
+            .unwrap_or_else(|_| panic_with_error!(&env, HTLCError::InvalidAttestation));
+        if attestation.contract != env.current_contract_address() || attestation.swap_id != swap_id {
+            panic_with_error!(&env, HTLCError::InvalidAttestation);
+        }
+
+        let mut swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+        match swap.status {
+            SwapStatus::Claimed => panic_with_error!(&env, HTLCError::AlreadyClaimed),
+            SwapStatus::Refunded => panic_with_error!(&env, HTLCError::AlreadyRefunded),
+            _ => {}
+        }
+
+        // Reject replays of the stored observation or of an earlier one
+        if let Some(stored) = get_eth_attestation(&env, &swap_id) {
+            if stored.event != CounterpartEvent::Funded
+                || stored.event == attestation.event
+                || stored.eth_tx_hash == attestation.eth_tx_hash
+            {
+                panic_with_error!(&env, HTLCError::InvalidAttestation);
+            }
+        }
+
+        // Apply the attested state change
+        match attestation.event {
+            CounterpartEvent::Funded => {
+                if swap.status == SwapStatus::Pending {
+                    transition(&env, &swap_id, &mut swap, SwapStatus::Active);
+                }
+            }
+            CounterpartEvent::Claimed => {
+                let preimage = attestation.preimage.clone()
+                    .unwrap_or_else(|| panic_with_error!(&env, HTLCError::InvalidPreimage));
+                if !matches_hashlock(&env, &swap, &preimage, &None) {
+                    panic_with_error!(&env, HTLCError::InvalidPreimage);
+                }
+            }
+            // Checked by `is_swap_refundable` from the stored attestation
+            CounterpartEvent::Refunded => {}
+        }
+
+        set_swap_core(&env, &swap_id, &swap);
+        set_eth_attestation(&env, &swap_id, &EthAttestation {
+            event: attestation.event.clone(),
+            preimage: attestation.preimage,
+            eth_tx_hash: attestation.eth_tx_hash.clone(),
+            attester,
+            attested_at: env.ledger().timestamp(),
+        });
+
+        publish_event(
+            &env,
+            ("eth_attestation",),
+            (swap_id, attestation.event, attestation.eth_tx_hash)
+        );
+    }
+
+    /// Register a new resolver for 1inch Fusion+ integration
+    /// 
+    /// Registering an existing resolver updates its collateral terms and
+    /// reactivates it; its deposited collateral and open swaps carry over.
+    /// The collateral token can't change while collateral is deposited.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver address
+    /// * `collateral_token` - Token used for collateral
+    /// * `min_collateral` - Minimum collateral amount required
+    pub fn register_resolver(
+        env: Env,
+        resolver: Address,
+        collateral_token: Address,
+        min_collateral: i128,
+    ) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        add_resolver(&env, resolver, collateral_token, min_collateral);
+    }
+
+    /// Register several resolvers at once (admin only)
+    /// 
+    /// Every entry is validated as in `register_resolver`; one invalid entry
+    /// fails the whole batch.
+    /// 
+    /// # Arguments
+    /// * `entries` - Resolvers to register (at most `MAX_RESOLVER_BATCH_SIZE`)
+    pub fn register_resolvers_batch(env: Env, entries: Vec<ResolverRegistration>) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if entries.len() > MAX_RESOLVER_BATCH_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        for entry in entries.iter() {
+            add_resolver(&env, entry.resolver, entry.collateral_token, entry.min_collateral);
+        }
+    }
+
+    /// Remove a resolver from the registry (admin only)
+    /// 
+    /// The resolver must have no open swaps and no deposited collateral.
+    /// Its slot counts towards `max_resolvers` again.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver address
+    pub fn unregister_resolver(env: Env, resolver: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        let resolver_info = get_resolver(&env, &resolver)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::ResolverNotFound));
+        if resolver_info.open_swaps > 0 || resolver_info.deposited_collateral > 0 {
+            panic_with_error!(&env, HTLCError::ResolverInUse);
+        }
+
+        remove_resolver(&env, &resolver);
+        remove_from_resolver_leaderboard(&env, &resolver);
+        set_resolver_count(&env, get_resolver_count(&env).saturating_sub(1));
+
+        publish_event(
+            &env,
+            ("resolver_unregistered",),
+            resolver
+        );
+    }
+
+    /// Cap the number of registered resolvers (admin only)
+    /// 
+    /// Lowering the cap below the current count keeps existing resolvers
+    /// but blocks new registrations until enough are unregistered.
+    /// 
+    /// # Arguments
+    /// * `max_resolvers` - Largest number of registered resolvers (0 = unlimited)
+    pub fn set_max_resolvers(env: Env, max_resolvers: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_max_resolvers(&env, max_resolvers);
+
+        publish_event(
+            &env,
+            ("max_resolvers_updated",),
+            max_resolvers
+        );
+    }
+
+    /// Get the cap on registered resolvers (0 = unlimited)
+    pub fn get_max_resolvers(env: Env) -> u32 {
+        get_max_resolvers(&env)
+    }
+
+    /// Set the collateral resolvers must hold against their open swaps (admin only)
+    /// 
+    /// A resolver is only assigned a swap while its deposited collateral is
+    /// at least `ratio_bps` of the total amount of its open swaps, the new
+    /// one included. Swaps already assigned are unaffected.
+    /// 
+    /// # Arguments
+    /// * `ratio_bps` - Required collateral in basis points of open swap value
+    ///   (0 = disabled, 10000 = fully collateralized)
+    pub fn set_collateral_ratio(env: Env, ratio_bps: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_collateral_ratio_bps(&env, ratio_bps);
+
+        publish_event(
+            &env,
+            ("collateral_ratio_updated",),
+            ratio_bps
+        );
+    }
+
+    /// Get the required collateral ratio in basis points (0 = disabled)
+    pub fn get_collateral_ratio(env: Env) -> u32 {
+        get_collateral_ratio_bps(&env)
+    }
+
+    /// Get the number of registered resolvers
+    pub fn get_resolver_count(env: Env) -> u32 {
+        get_resolver_count(&env)
+    }
+
+    /// Record that a resolver is still online
+    /// 
+    /// Resolvers that miss heartbeats for longer than the staleness window
+    /// cannot be assigned new swaps until they send one again.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver address (must have auth)
+    pub fn resolver_heartbeat(env: Env, resolver: Address) {
+        resolver.require_auth();
+
+        let mut resolver_info = get_resolver(&env, &resolver)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::ResolverNotFound));
+        resolver_info.last_active = env.ledger().timestamp();
+        set_resolver(&env, &resolver, &resolver_info);
+
+        publish_event(
+            &env,
+            ("resolver_heartbeat",),
+            (resolver, resolver_info.last_active)
+        );
+    }
+
+    /// Set how long a resolver stays live without a heartbeat (admin only)
+    /// 
+    /// # Arguments
+    /// * `window` - Staleness window in seconds
+    pub fn set_staleness_window(env: Env, window: u64) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_staleness_window(&env, window);
+
+        publish_event(
+            &env,
+            ("staleness_window_updated",),
+            window
+        );
+    }
+
+    /// Set how long before the timelock claims stop being accepted (admin only)
+    /// 
+    /// Refunds still wait for the full timelock, leaving a window in which
+    /// neither side can settle.
+    /// 
+    /// # Arguments
+    /// * `buffer` - Claim cutoff buffer in seconds (below `MIN_TIMELOCK_DURATION`)
+    pub fn set_claim_cutoff_buffer(env: Env, buffer: u64) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if buffer >= MIN_TIMELOCK_DURATION {
+            panic_with_error!(&env, HTLCError::InvalidTimelock);
+        }
+
+        set_claim_cutoff_buffer(&env, buffer);
+
+        publish_event(
+            &env,
+            ("claim_cutoff_updated",),
+            buffer
+        );
+    }
+
+    /// Set how long after creation a sender must wait to cancel a swap (admin only)
+    /// 
+    /// Zero, the default, allows cancelling immediately.
+    /// 
+    /// # Arguments
+    /// * `cooldown` - Cancel cooldown in seconds
+    pub fn set_cancel_cooldown(env: Env, cooldown: u64) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_cancel_cooldown(&env, cooldown);
+
+        publish_event(
+            &env,
+            ("cancel_cooldown_updated",),
+            cooldown
+        );
+    }
+
+    /// Get the cancel cooldown in seconds
+    pub fn get_cancel_cooldown(env: Env) -> u64 {
+        get_cancel_cooldown(&env)
+    }
+
+    /// Give up the admin role for good (admin only)
+    /// 
+    /// The first call records a request; a second call within
+    /// `RENOUNCE_CONFIRMATION_WINDOW` clears the admin, after which every
+    /// admin-gated entrypoint fails with `AdminRenounced`. Views and the swap
+    /// lifecycle keep working. A request left unconfirmed past the window
+    /// has to be made again.
+    /// 
+    /// # Returns
+    /// True once the admin has been renounced
+    pub fn renounce_admin(env: Env) -> bool {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        let now = env.ledger().timestamp();
+        let confirmed = get_renounce_requested_at(&env)
+            .is_some_and(|requested_at| now <= requested_at.saturating_add(RENOUNCE_CONFIRMATION_WINDOW));
+        if !confirmed {
+            set_renounce_requested_at(&env, Some(now));
+            publish_event(
+                &env,
+                ("admin_renounce_requested",),
+                (admin, now)
+            );
+            return false;
+        }
+
+        set_renounce_requested_at(&env, None);
+        renounce_admin(&env);
+
+        publish_event(
+            &env,
+            ("admin_renounced",),
+            admin
+        );
+        true
+    }
+
+    /// Set when pending swaps confirm themselves (admin only)
+    /// 
+    /// Once set, a `Pending` swap can only be claimed after an Ethereum
+    /// attestation activates it or `delay` seconds have passed since it was
+    /// created, at which point the claim moves it to `Active` first. This
+    /// keeps the flow moving where no resolver confirms swaps.
+    /// 
+    /// # Arguments
+    /// * `delay` - Seconds after creation before a pending swap auto-confirms, or 0 to disable
+    pub fn set_auto_confirm_after(env: Env, delay: u64) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_auto_confirm_after(&env, delay);
+
+        publish_event(
+            &env,
+            ("auto_confirm_updated",),
+            delay
+        );
+    }
+
+    /// Configure refunds of abandoned swaps (admin only)
+    /// 
+    /// # Arguments
+    /// * `period` - Time after the timelock before anyone may refund, in seconds
+    /// * `bounty_bps` - Bounty paid to the caller in basis points
+    pub fn set_abandonment_policy(env: Env, period: u64, bounty_bps: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if bounty_bps > MAX_ABANDONMENT_BOUNTY_BPS {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+
+        set_abandonment_period(&env, period);
+        set_abandonment_bounty_bps(&env, bounty_bps);
+
+        publish_event(
+            &env,
+            ("abandonment_policy_updated",),
+            (period, bounty_bps)
+        );
+    }
+
+    /// Set the bounty paid to keepers refunding expired swaps (admin only)
+    /// 
+    /// # Arguments
+    /// * `bounty_bps` - Bounty in basis points of the swap amount (0 = none)
+    pub fn set_refund_bounty(env: Env, bounty_bps: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if bounty_bps > MAX_REFUND_BOUNTY_BPS {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+
+        set_refund_bounty_bps(&env, bounty_bps);
+
+        publish_event(
+            &env,
+            ("refund_bounty_updated",),
+            bounty_bps
+        );
+    }
+
+    /// Get the bounty paid to keepers refunding expired swaps, in basis points
+    pub fn get_refund_bounty(env: Env) -> u32 {
+        get_refund_bounty_bps(&env)
+    }
+
+    /// Set the reward paid to resolvers on their claimed volume (admin only)
+    /// 
+    /// The reward is set aside from the protocol fee of each claimed
+    /// resolver swap, so it never exceeds the fee collected; the rest of the
+    /// fee goes to the fee recipients.
+    /// 
+    /// # Arguments
+    /// * `reward_bps` - Reward in basis points of the claimed volume, at most
+    ///   the protocol fee (0 = none)
+    pub fn set_resolver_reward_rate(env: Env, reward_bps: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if reward_bps > get_protocol_fee_bps(&env) {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+
+        set_resolver_reward_bps(&env, reward_bps);
+
+        publish_event(
+            &env,
+            ("resolver_reward_rate_updated",),
+            reward_bps
+        );
+    }
+
+    /// Get the reward paid to resolvers on their claimed volume, in basis points
+    pub fn get_resolver_reward_rate(env: Env) -> u32 {
+        get_resolver_reward_bps(&env)
+    }
+
+    /// Cap the size of swaps a resolver can be assigned (admin only)
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver address
+    /// * `max_swap_amount` - Largest assignable swap amount (0 = unlimited)
+    pub fn set_resolver_limit(env: Env, resolver: Address, max_swap_amount: i128) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if max_swap_amount < 0 {
+            panic_with_error!(&env, HTLCError::InvalidAmount);
+        }
+
+        let mut resolver_info = get_resolver(&env, &resolver)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::ResolverNotFound));
+        resolver_info.max_swap_amount = max_swap_amount;
+        set_resolver(&env, &resolver, &resolver_info);
+
+        publish_event(
+            &env,
+            ("resolver_limit_updated",),
+            (resolver, max_swap_amount)
+        );
+    }
+
+    /// Deposit additional collateral for a resolver
+    /// 
+    /// Inactive resolvers may also top up, e.g. as part of reinstatement.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver address (must have auth)
+    /// * `amount` - Amount of the collateral token to deposit
+    pub fn add_collateral(env: Env, resolver: Address, amount: i128) {
+        resolver.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, HTLCError::InvalidAmount);
+        }
+
+        let mut resolver_info = get_resolver(&env, &resolver)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::ResolverNotFound));
+
+        enter_guard(&env);
+        resolver_info.deposited_collateral += amount;
+        set_resolver(&env, &resolver, &resolver_info);
+        add_locked_balance(&env, &resolver_info.collateral_token, amount);
+
+        transfer_or_fail(
+            &env,
+            &token::Client::new(&env, &resolver_info.collateral_token),
+            &resolver,
+            &env.current_contract_address(),
+            amount,
+        );
+        exit_guard(&env);
+
+        emit_collateral_increased(&env, resolver, amount, resolver_info.deposited_collateral);
+    }
+
+    /// Withdraw deposited collateral for a resolver
+    /// 
+    /// While the resolver has open swaps, the remaining deposit must stay at
+    /// or above `min_collateral` and the collateral ratio of their value.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver address (must have auth)
+    /// * `amount` - Amount of the collateral token to withdraw
+    pub fn request_collateral_withdrawal(env: Env, resolver: Address, amount: i128) {
+        resolver.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, HTLCError::InvalidAmount);
+        }
+
+        let mut resolver_info = get_resolver(&env, &resolver)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::ResolverNotFound));
+
+        if amount > resolver_info.deposited_collateral {
+            panic_with_error!(&env, HTLCError::InsufficientBalance);
+        }
+
+        let remaining = resolver_info.deposited_collateral - amount;
+        if resolver_info.open_swaps > 0 && remaining < resolver_info.min_collateral {
+            panic_with_error!(&env, HTLCError::InsufficientCollateral);
+        }
+        if remaining < required_collateral(&env, resolver_info.open_value) {
+            panic_with_error!(&env, HTLCError::InsufficientCollateral);
+        }
+
+        enter_guard(&env);
+        resolver_info.deposited_collateral = remaining;
+        set_resolver(&env, &resolver, &resolver_info);
+        release_locked_balance(&env, &resolver_info.collateral_token, amount);
+
+        transfer_or_fail(
+            &env,
+            &token::Client::new(&env, &resolver_info.collateral_token),
+            &env.current_contract_address(),
+            &resolver,
+            amount,
+        );
+        exit_guard(&env);
+
+        publish_event(
+            &env,
+            ("collateral_withdrawn",),
+            (resolver, amount, remaining)
+        );
+    }
+
+    /// Update protocol fee (admin only)
+    /// 
+    /// # Arguments
+    /// * `new_fee_bps` - New protocol fee in basis points
+    pub fn update_protocol_fee(env: Env, new_fee_bps: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if new_fee_bps > MAX_PROTOCOL_FEE_BPS {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+
+        let old_fee = get_protocol_fee_bps(&env);
+        set_protocol_fee_bps(&env, new_fee_bps);
+
+        publish_event(
+            &env,
+            ("fee_updated",),
+            (old_fee, new_fee_bps)
+        );
+    }
+
+    /// Split the protocol fee between several recipients (admin only)
+    /// 
+    /// Each claim pays every recipient its share of the fee, rounded down;
+    /// the rounding remainder goes to the first recipient.
+    /// 
+    /// # Arguments
+    /// * `splits` - Recipients and their shares (at most `MAX_FEE_SPLITS`, summing to 10000 bps)
+    pub fn set_fee_splits(env: Env, splits: Vec<FeeSplit>) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if splits.is_empty() || splits.len() > MAX_FEE_SPLITS {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+        let mut total_bps = 0u32;
+        for split in splits.iter() {
+            if split.share_bps == 0 {
+                panic_with_error!(&env, HTLCError::InvalidFee);
+            }
+            total_bps = total_bps.saturating_add(split.share_bps);
+        }
+        if total_bps != 10_000 {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+
+        set_fee_splits(&env, &splits);
+
+        publish_event(
+            &env,
+            ("fee_splits_updated",),
+            splits
+        );
+    }
+
+    /// Send the whole protocol fee to a single recipient (admin only)
+    /// 
+    /// # Arguments
+    /// * `new_recipient` - Address to receive all protocol fees
+    pub fn update_fee_recipient(env: Env, new_recipient: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        let old_recipient = get_fee_recipient(&env);
+        set_fee_splits(&env, &Vec::from_array(&env, [FeeSplit {
+            recipient: new_recipient.clone(),
+            share_bps: 10_000,
+        }]));
+
+        emit_fee_recipient_updated(&env, old_recipient, new_recipient);
+    }
+
+    /// Get the recipients the protocol fee is split between
+    pub fn get_fee_splits(env: Env) -> Vec<FeeSplit> {
+        get_fee_splits(&env)
+    }
+
+    /// Set the minimum protocol fee for a token (admin only)
+    /// 
+    /// The floor applies to the basis-point fee, which would otherwise round
+    /// down to zero on small amounts. The charged fee never exceeds
+    /// `MAX_PROTOCOL_FEE_BPS` of the swap amount.
+    /// 
+    /// # Arguments
+    /// * `token` - Token the floor applies to
+    /// * `min_fee` - Minimum fee in the token's smallest unit (0 disables it)
+    pub fn set_min_fee(env: Env, token: Address, min_fee: i128) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if min_fee < 0 {
+            panic_with_error!(&env, HTLCError::InvalidFee);
+        }
+
+        set_min_fee(&env, &token, min_fee);
+
+        publish_event(
+            &env,
+            ("min_fee_updated",),
+            (token, min_fee)
+        );
+    }
+
+    /// Enable or disable permissioned mode (admin only)
+    /// 
+    /// While enabled, only allowed senders can create new swaps. Claims and
+    /// refunds of existing swaps are unaffected.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether swap creation is restricted to allowed senders
+    pub fn set_permissioned_mode(env: Env, enabled: bool) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_permissioned_mode(&env, enabled);
+
+        publish_event(
+            &env,
+            ("permissioned_mode_updated",),
+            enabled
+        );
+    }
+
+    /// Add a sender to the allowlist (admin only)
+    /// 
+    /// # Arguments
+    /// * `sender` - Address permitted to create swaps in permissioned mode
+    pub fn add_allowed_sender(env: Env, sender: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_allowed_sender(&env, &sender);
+
+        publish_event(
+            &env,
+            ("allowed_sender_added",),
+            sender
+        );
+    }
+
+    /// Remove a sender from the allowlist (admin only)
+    /// 
+    /// # Arguments
+    /// * `sender` - Address to remove from the allowlist
+    pub fn remove_allowed_sender(env: Env, sender: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        remove_allowed_sender(&env, &sender);
+
+        publish_event(
+            &env,
+            ("allowed_sender_removed",),
+            sender
+        );
+    }
+
+    /// Require or stop requiring recipient registration (admin only)
+    /// 
+    /// While required, new swaps can only pay registered recipients; others
+    /// are rejected with `RecipientNotRegistered`. Existing swaps are
+    /// unaffected.
+    /// 
+    /// # Arguments
+    /// * `required` - Whether swap recipients must be registered
+    pub fn set_recipient_registration(env: Env, required: bool) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_recipient_registration_required(&env, required);
+
+        publish_event(
+            &env,
+            ("recipient_registration_updated",),
+            required
+        );
+    }
+
+    /// Register a recipient (admin only)
+    /// 
+    /// # Arguments
+    /// * `recipient` - Address permitted to receive swaps while registration
+    ///   is required
+    pub fn add_registered_recipient(env: Env, recipient: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_registered_recipient(&env, &recipient);
+
+        publish_event(
+            &env,
+            ("recipient_registered",),
+            recipient
+        );
+    }
+
+    /// Remove a recipient from the registry (admin only)
+    /// 
+    /// # Arguments
+    /// * `recipient` - Address to remove from the registry
+    pub fn remove_registered_recipient(env: Env, recipient: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        remove_registered_recipient(&env, &recipient);
+
+        publish_event(
+            &env,
+            ("recipient_unregistered",),
+            recipient
+        );
+    }
+
+    /// Enable or disable permissioned swap creation (admin only)
+    /// 
+    /// While enabled, only addresses in the allowed creator set can create
+    /// swaps; others are rejected with `CreatorNotAllowed`.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether swap creation is restricted to allowed creators
+    pub fn set_permissioned_creation(env: Env, enabled: bool) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_permissioned_creation(&env, enabled);
+
+        publish_event(
+            &env,
+            ("permissioned_creation_updated",),
+            enabled
+        );
+    }
+
+    /// Add an address to the allowed creator set (admin only)
+    /// 
+    /// # Arguments
+    /// * `creator` - Address permitted to create swaps
+    pub fn add_allowed_creator(env: Env, creator: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_allowed_creator(&env, &creator);
+
+        publish_event(
+            &env,
+            ("allowed_creator_added",),
+            creator
+        );
+    }
+
+    /// Remove an address from the allowed creator set (admin only)
+    /// 
+    /// # Arguments
+    /// * `creator` - Address to remove
+    pub fn remove_allowed_creator(env: Env, creator: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        remove_allowed_creator(&env, &creator);
+
+        publish_event(
+            &env,
+            ("allowed_creator_removed",),
+            creator
+        );
+    }
+
+    /// Block an address from sending or receiving funds (admin only)
+    /// 
+    /// Blocked senders can still refund their existing swaps, since refunds
+    /// return funds to their origin.
+    /// 
+    /// # Arguments
+    /// * `addr` - Address to block
+    pub fn block_address(env: Env, addr: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_blocked_address(&env, &addr);
+
+        publish_event(
+            &env,
+            ("address_blocked",),
+            addr
+        );
+    }
+
+    /// Remove an address from the blocklist (admin only)
+    /// 
+    /// # Arguments
+    /// * `addr` - Address to unblock
+    pub fn unblock_address(env: Env, addr: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        remove_blocked_address(&env, &addr);
+
+        publish_event(
+            &env,
+            ("address_unblocked",),
+            addr
+        );
+    }
+
+    /// Exempt a partner from the protocol fee (admin only)
+    /// 
+    /// Claims of swaps whose sender or resolver is exempt pay no fee.
+    /// 
+    /// # Arguments
+    /// * `addr` - Sender or resolver to exempt
+    pub fn add_fee_exempt(env: Env, addr: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_fee_exempt(&env, &addr);
+
+        publish_event(
+            &env,
+            ("fee_exempt_added",),
+            addr
+        );
+    }
+
+    /// Remove a partner's protocol fee exemption (admin only)
+    /// 
+    /// # Arguments
+    /// * `addr` - Address to remove from the exemption list
+    pub fn remove_fee_exempt(env: Env, addr: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        remove_fee_exempt(&env, &addr);
+
+        publish_event(
+            &env,
+            ("fee_exempt_removed",),
+            addr
+        );
+    }
+
+    /// Set or clear the external fee policy contract (admin only)
+    /// 
+    /// # Arguments
+    /// * `policy` - Contract implementing `FeePolicy`, or `None` to use the
+    ///   internal basis-point fee
+    pub fn set_fee_policy(env: Env, policy: Option<Address>) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_fee_policy(&env, &policy);
+
+        publish_event(
+            &env,
+            ("fee_policy_updated",),
+            policy
+        );
+    }
+
+    /// Set or clear the price oracle checked at swap creation (admin only)
+    /// 
+    /// While an oracle is set, swaps recording a counter amount must be
+    /// priced within `max_deviation_bps` of the oracle's rate for the token.
+    /// 
+    /// # Arguments
+    /// * `oracle` - Contract implementing `PriceOracle`, or `None` to disable
+    ///   the check
+    /// * `max_deviation_bps` - Largest accepted deviation from the oracle
+    ///   price, in basis points
+    pub fn set_price_oracle(env: Env, oracle: Option<Address>, max_deviation_bps: u32) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if max_deviation_bps > 10_000 {
+            panic_with_error!(&env, HTLCError::InvalidAmount);
+        }
+
+        set_price_oracle(&env, &oracle, max_deviation_bps);
+
+        publish_event(
+            &env,
+            ("price_oracle_updated",),
+            (oracle, max_deviation_bps)
+        );
+    }
+
+    /// Get the price oracle and its maximum deviation in basis points, if set
+    pub fn get_price_oracle(env: Env) -> Option<(Address, u32)> {
+        get_price_oracle(&env).map(|oracle| (oracle, get_max_price_deviation_bps(&env)))
+    }
+
+    /// Set or clear the oracle allowed to link swaps to their Ethereum
+    /// escrows (admin only)
+    /// 
+    /// # Arguments
+    /// * `oracle` - Oracle address, or `None` to leave linking to resolvers
+    pub fn set_counterpart_oracle(env: Env, oracle: Option<Address>) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_counterpart_oracle(&env, &oracle);
+
+        publish_event(
+            &env,
+            ("counterpart_oracle_updated",),
+            oracle
+        );
+    }
+
+    /// Get the oracle allowed to link swaps to their Ethereum escrows, if set
+    pub fn get_counterpart_oracle(env: Env) -> Option<Address> {
+        get_counterpart_oracle(&env)
+    }
+
+    /// Register the Ethereum HTLC contract for a chain (admin only)
+    /// 
+    /// Swaps on a registered chain must reference this contract.
+    /// 
+    /// # Arguments
+    /// * `chain_id` - Ethereum chain ID
+    /// * `eth_contract` - Ethereum HTLC contract deployed on that chain
+    pub fn set_chain_contract(env: Env, chain_id: u64, eth_contract: Address) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_chain_contract(&env, chain_id, &eth_contract);
+
+        publish_event(
+            &env,
+            ("chain_contract_updated",),
+            (chain_id, eth_contract)
+        );
+    }
+
+    /// Set how long swaps on a chain wait for Ethereum finality (admin only)
+    /// 
+    /// Swaps created afterwards can only be claimed `delay` seconds after
+    /// creation, once the Ethereum lock can no longer be reorged out.
+    /// 
+    /// # Arguments
+    /// * `chain_id` - Ethereum chain ID
+    /// * `delay` - Finality delay in seconds (below `MIN_TIMELOCK_DURATION`)
+    pub fn set_chain_finality_delay(env: Env, chain_id: u64, delay: u64) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if delay >= MIN_TIMELOCK_DURATION {
+            panic_with_error!(&env, HTLCError::InvalidTimelock);
+        }
+
+        set_chain_finality_delay(&env, chain_id, delay);
+
+        publish_event(
+            &env,
+            ("chain_finality_delay_updated",),
+            (chain_id, delay)
+        );
+    }
+
+    /// Get how long swaps on a chain wait for Ethereum finality, in seconds
+    pub fn get_chain_finality_delay(env: Env, chain_id: u64) -> u64 {
+        get_chain_finality_delay(&env, chain_id)
+    }
+
+    /// Set the Ethereum address of the attester (admin only)
+    /// 
+    /// # Arguments
+    /// * `attester` - Ethereum address derived from the attester's secp256k1 key
+    pub fn set_eth_attester(env: Env, attester: BytesN<20>) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        set_eth_attester(&env, &attester);
+
+        publish_event(
+            &env,
+            ("eth_attester_updated",),
+            attester
+        );
+    }
+
+    /// Sweep tokens sent to the contract outside of any swap (admin only)
+    /// 
+    /// Only the balance not owed to open swaps, claimable payouts or
+    /// resolver collateral can be swept. Swept tokens go to the admin.
+    /// 
+    /// # Arguments
+    /// * `token` - Token to sweep
+    /// * `amount` - Amount to sweep, at most the unaccounted balance
+    pub fn sweep_stray_tokens(env: Env, token: Address, amount: i128) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, HTLCError::InvalidAmount);
+        }
+
+        enter_guard(&env);
+        let token_client = token::Client::new(&env, &token);
+        let stray = token_client.balance(&env.current_contract_address()) - get_locked_balance(&env, &token);
+        if amount > stray {
+            panic_with_error!(&env, HTLCError::InsufficientBalance);
+        }
+
+        transfer_or_fail(&env, &token_client, &env.current_contract_address(), &admin, amount);
+        exit_guard(&env);
+
+        publish_event(
+            &env,
+            ("tokens_swept",),
+            (token, amount, admin)
+        );
+    }
+
+    /// Mark a swap as failed (admin only)
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap to mark as failed
+    /// * `code` - Failure category
+    /// * `reason` - Reason for failure
+    pub fn mark_swap_failed(env: Env, swap_id: String, code: FailureCode, reason: String) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        let mut swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+
+        // Only allow marking as failed if not already claimed or refunded
+        if swap.status == SwapStatus::Claimed {
+            panic_with_error!(&env, HTLCError::AlreadyClaimed);
+        }
+        
+        if swap.status == SwapStatus::Refunded {
+            panic_with_error!(&env, HTLCError::AlreadyRefunded);
+        }
+
+        // Update swap status
+        transition(&env, &swap_id, &mut swap, SwapStatus::Failed);
+        set_swap_core(&env, &swap_id, &swap);
+        set_swap_failure(&env, &swap_id, &code, &reason);
+
+        // Emit events
+        let elapsed = swap_age(&env, &swap_id, env.ledger().timestamp());
+        if let Some(resolver) = &swap.resolver {
+            emit_resolver_defaulted(&env, swap_id.clone(), resolver.clone(), SwapStatus::Failed, elapsed);
+        }
+        emit_swap_failed(&env, swap_id, swap.sender.clone(), code, reason, swap.resolver.clone(), elapsed);
+    }
+
+    /// Replace the Ethereum escrow linked to a swap (admin only)
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `counterpart_id` - ID of the Ethereum escrow
+    pub fn relink_counterpart(env: Env, swap_id: String, counterpart_id: BytesN<32>) {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if get_swap_core(&env, &swap_id).is_none() {
+            panic_with_error!(&env, HTLCError::SwapNotFound);
+        }
+
+        if let Some(previous) = get_swap_counterpart(&env, &swap_id) {
+            remove_counterpart_swap(&env, &previous);
+        }
+        link_swap_counterpart(&env, &swap_id, &counterpart_id, admin);
+    }
+
+    /// Recompute swap statistics from stored swap records (admin only)
+    /// 
+    /// Corrects drift in the completed counter and the per-status counts by
+    /// counting the actual status of every swap. The count pages through
+    /// swaps in creation order, `limit` per call, resuming where the last
+    /// call stopped; swaps that change status mid-count are kept in step.
+    /// The counters are only replaced once the last swap has been counted.
+    /// 
+    /// # Arguments
+    /// * `limit` - Swaps to count in this call (at most `MAX_STATS_BATCH_SIZE`)
+    /// 
+    /// # Returns
+    /// The corrected statistics once the count is complete, otherwise None
+    pub fn recompute_stats(env: Env, limit: u32) -> Option<ContractStats> {
+        let admin = get_admin(&env);
+        admin.require_auth();
+
+        if limit > MAX_STATS_BATCH_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        let mut recount = get_stats_recount(&env).unwrap_or(StatsRecount {
+            next_index: 1,
+            counts: StatusCounts {
+                pending: 0,
+                active: 0,
+                claimed: 0,
+                refunded: 0,
+                failed: 0,
+            },
+        });
+        let counter = get_swap_counter(&env);
+        let end = recount.next_index.saturating_add(limit as u64).min(counter + 1);
+        for index in recount.next_index..end {
+            if let Some(swap) = get_swap_by_index(&env, index).and_then(|swap_id| get_swap_core(&env, &swap_id)) {
+                adjust_status_count(&mut recount.counts, &swap.status, 1);
+            }
+        }
+        recount.next_index = end;
+
+        if end <= counter {
+            set_stats_recount(&env, &recount);
+            return None;
+        }
+
+        let counts = recount.counts;
+        remove_stats_recount(&env);
+        set_total_swaps_completed(&env, counts.claimed);
+        set_status_count(&env, &SwapStatus::Pending, counts.pending);
+        set_status_count(&env, &SwapStatus::Active, counts.active);
+        set_status_count(&env, &SwapStatus::Claimed, counts.claimed);
+        set_status_count(&env, &SwapStatus::Refunded, counts.refunded);
+        set_status_count(&env, &SwapStatus::Failed, counts.failed);
+
+        publish_event(
+            &env,
+            ("stats_recomputed",),
+            (counts.claimed, counts.refunded, counts.failed)
+        );
+
+        Some(Self::get_contract_stats(env))
+    }
+
+    /// Check if a swap exists
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap to check
+    pub fn swap_exists(env: Env, swap_id: String) -> bool {
+        get_swap_core(&env, &swap_id).is_some()
+    }
+
+    // View functions
+
+    /// Get swap details by ID
+    pub fn get_swap_details(env: Env, swap_id: String) -> Option<Swap> {
+        get_swap(&env, &swap_id)
+    }
+
+    /// Get swap details along with the fee, payout and timing derived at
+    /// the current ledger time
+    /// 
+    /// A recipient-paid fee reflects the current fee settings and may
+    /// change before the claim.
+    pub fn get_swap_view(env: Env, swap_id: String) -> Option<SwapView> {
+        let swap = get_swap(&env, &swap_id)?;
+        let now = env.ledger().timestamp();
+        let (protocol_fee, net_amount) = match swap.fee_payer {
+            FeePayer::Sender => (swap.prepaid_fee, swap.amount),
+            FeePayer::Recipient => {
+                let fee = swap_fee(&env, &swap.token, swap.amount, &swap.sender, &swap.resolver);
+                (fee, swap.amount - fee)
+            }
+        };
+        let is_refundable = match swap.status {
+            SwapStatus::Failed => true,
+            SwapStatus::Pending | SwapStatus::Active => is_refundable_at(now, swap.timelock, swap.claim_deadline),
+            SwapStatus::Claimed | SwapStatus::Refunded => false,
+        };
+        Some(SwapView {
+            net_amount,
+            protocol_fee,
+            seconds_to_timelock: swap.timelock.saturating_sub(now),
+            is_claimable: is_swap_claimable(&env, &swap, now),
+            is_refundable,
+            swap,
+        })
+    }
+
+    /// Get the revealed preimage of a swap
+    /// 
+    /// Returns the preimage of a claimed swap, or one published with
+    /// `record_preimage`. A secret is otherwise never exposed before
+    /// settlement.
+    pub fn get_preimage(env: Env, swap_id: String) -> Option<BytesN<32>> {
+        let swap = get_swap_core(&env, &swap_id)?;
+        if swap.status != SwapStatus::Claimed {
+            return get_revealed_preimage(&env, &swap_id);
+        }
+        swap.preimage
+    }
+
+    /// Convert an amount between assets with different decimals
+    /// 
+    /// Scaling down truncates toward zero, so converting back may yield a
+    /// smaller amount than the original.
+    /// 
+    /// # Arguments
+    /// * `amount` - Amount in units of `from_decimals`
+    /// * `from_decimals` - Decimals of the source asset
+    /// * `to_decimals` - Decimals of the target asset
+    pub fn convert_amount(env: Env, amount: i128, from_decimals: u32, to_decimals: u32) -> i128 {
+        scale_amount(amount, from_decimals, to_decimals)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::InvalidAmount))
+    }
+
+    /// Check whether a preimage would claim a swap
+    /// 
+    /// Hashes the candidate (and salt, if any) with SHA-256 and compares it
+    /// to the hashlock. Returns `false` for unknown swaps. Status and
+    /// timelock are not checked.
+    pub fn verify_preimage(
+        env: Env,
+        swap_id: String,
+        preimage: BytesN<32>,
+        salt: Option<BytesN<32>>,
+    ) -> bool {
+        let Some(swap) = get_swap_core(&env, &swap_id) else {
+            return false;
+        };
+        matches_hashlock(&env, &swap, &preimage, &salt)
+    }
+
+    /// Explain whether a preimage would claim a swap
+    /// 
+    /// Returns the computed hash next to the stored hashlock so integrators
+    /// can tell an encoding or byte-order mistake from a wrong secret.
+    /// Status and timelock are not checked.
+    /// 
+    /// # Arguments
+    /// * `swap_id` - Unique identifier of the swap
+    /// * `preimage` - Candidate secret
+    /// * `salt` - Salt for swaps committed to as `sha256(preimage || salt)`
+    pub fn diagnose_claim(
+        env: Env,
+        swap_id: String,
+        preimage: BytesN<32>,
+        salt: Option<BytesN<32>>,
+    ) -> ClaimDiagnosis {
+        let swap = get_swap_core(&env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(&env, HTLCError::SwapNotFound));
+        ClaimDiagnosis {
+            computed_hash: hash_secret(&env, &preimage, &salt),
+            matches: matches_hashlock(&env, &swap, &preimage, &salt),
+            hashlock: swap.hashlock,
+            hashlock_len: swap.hashlock_len,
+        }
+    }
+
+    /// Get the agreed exchange rate of a swap
+    /// 
+    /// The rate is the Stellar amount per unit of the EVM counter asset,
+    /// with both sides normalized by their decimals and the result scaled
+    /// by `RATE_SCALE` (1e7).
+    /// 
+    /// # Returns
+    /// `None` if the swap is unknown, has no recorded counter asset, or the
+    /// rate does not fit in an `i128`
+    pub fn get_swap_rate(env: Env, swap_id: String) -> Option<i128> {
+        let swap = get_swap(&env, &swap_id)?;
+        normalized_rate(swap.amount, swap.stellar_decimals, swap.counter_amount?, swap.counter_decimals?)
+    }
+
+    /// Get the failure code and reason recorded for a failed swap
+    pub fn get_failure_info(env: Env, swap_id: String) -> Option<(FailureCode, String)> {
+        get_swap_failure(&env, &swap_id)
+    }
+
+    /// Get the Ethereum escrow linked to a swap
+    pub fn get_swap_counterpart(env: Env, swap_id: String) -> Option<BytesN<32>> {
+        get_swap_counterpart(&env, &swap_id)
+    }
+
+    /// Get the swap linked to an Ethereum escrow
+    pub fn get_swap_by_counterpart(env: Env, counterpart_id: BytesN<32>) -> Option<String> {
+        get_counterpart_swap(&env, &counterpart_id)
+    }
+
+    /// Get the latest verified Ethereum attestation for a swap
+    pub fn get_eth_attestation(env: Env, swap_id: String) -> Option<EthAttestation> {
+        get_eth_attestation(&env, &swap_id)
+    }
+
+    /// Get the protocol limits new swaps and configuration are checked against
+    /// 
+    /// Configurable limits reflect the current admin settings; the rest are
+    /// protocol constants. No per-user cap on open swaps is enforced, so
+    /// `max_open_swaps_per_user` is 0 (unlimited).
+    pub fn get_limits(env: Env) -> Limits {
+        Limits {
+            min_timelock_duration: MIN_TIMELOCK_DURATION,
+            max_timelock_duration: MAX_TIMELOCK_DURATION,
+            max_protocol_fee_bps: MAX_PROTOCOL_FEE_BPS,
+            min_swap_amount: MIN_SWAP_AMOUNT,
+            max_open_swaps_per_user: 0,
+            claim_cutoff_buffer: get_claim_cutoff_buffer(&env),
+            abandonment_period: get_abandonment_period(&env),
+        }
+    }
+
+    /// Get contract statistics
+    /// 
+    /// Never fails: on an uninitialized contract the counters are zero and
+    /// `admin` and `fee_recipient` are `None`.
+    pub fn get_contract_stats(env: Env) -> ContractStats {
+        ContractStats {
+            total_swaps_created: get_total_swaps_created(&env),
+            total_swaps_completed: get_total_swaps_completed(&env),
+            total_swaps_refunded: get_status_count(&env, &SwapStatus::Refunded),
+            total_swaps_failed: get_status_count(&env, &SwapStatus::Failed),
+            active_swaps: get_status_count(&env, &SwapStatus::Pending)
+                + get_status_count(&env, &SwapStatus::Active),
+            protocol_fee_bps: get_protocol_fee_bps(&env),
+            admin: try_get_admin(&env),
+            admin_renounced: is_admin_renounced(&env),
+            fee_recipient: try_get_fee_recipient(&env),
+        }
+    }
+
+    /// Get the number of swaps currently in each status
+    pub fn get_status_counts(env: Env) -> StatusCounts {
+        StatusCounts {
+            pending: get_status_count(&env, &SwapStatus::Pending),
+            active: get_status_count(&env, &SwapStatus::Active),
+            claimed: get_status_count(&env, &SwapStatus::Claimed),
+            refunded: get_status_count(&env, &SwapStatus::Refunded),
+            failed: get_status_count(&env, &SwapStatus::Failed),
+        }
+    }
+
+    /// Extend the TTL of the contract instance and its configuration
+    /// 
+    /// Anyone may call this; it only extends the TTL once it has fallen
+    /// below `INSTANCE_TTL_THRESHOLD`. Swaps and resolvers are extended
+    /// whenever they are written.
+    pub fn bump_instance_ttl(env: Env) {
+        extend_instance_ttl(&env);
+    }
+
+    /// Get the storage TTL thresholds and extensions, in ledgers
+    pub fn get_ttl_policy(_env: Env) -> TtlPolicy {
+        TtlPolicy {
+            instance_threshold: INSTANCE_TTL_THRESHOLD,
+            instance_extend_to: INSTANCE_TTL_EXTEND_TO,
+            persistent_threshold: PERSISTENT_TTL_THRESHOLD,
+            persistent_extend_to: PERSISTENT_TTL_EXTEND_TO,
+        }
+    }
+
+    /// Check contract invariants
+    /// 
+    /// # Returns
+    /// Names of violated invariants; empty when the contract is healthy
+    pub fn check_invariants(env: Env) -> Vec<String> {
+        invariant_violations(&env)
+    }
+
+    /// Check that the contract's accounting is consistent
+    /// 
+    /// Compares each token's tracked principal of open swaps with its
+    /// locked balance and the contract's holdings, next to the swap
+    /// counters. Only tracked totals are read, so the cost doesn't grow
+    /// with the number of swaps.
+    /// 
+    /// # Arguments
+    /// * `tokens` - Tokens to check (at most `MAX_HEALTH_CHECK_TOKENS`)
+    pub fn health_check(env: Env, tokens: Vec<Address>) -> HealthReport {
+        if tokens.len() > MAX_HEALTH_CHECK_TOKENS {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        let created = get_total_swaps_created(&env);
+        let completed = get_total_swaps_completed(&env);
+        let pending = get_status_count(&env, &SwapStatus::Pending);
+        let active = get_status_count(&env, &SwapStatus::Active);
+        let claimed = get_status_count(&env, &SwapStatus::Claimed);
+        let refunded = get_status_count(&env, &SwapStatus::Refunded);
+        let failed = get_status_count(&env, &SwapStatus::Failed);
+
+        let mut locked_non_negative = true;
+        let mut locked_covers_open_swaps = true;
+        let mut solvent = true;
+        for token in tokens.iter() {
+            let open_principal = get_total_locked(&env, &token);
+            let locked = get_locked_balance(&env, &token);
+            let held = token::Client::new(&env, &token).balance(&env.current_contract_address());
+            locked_non_negative &= locked >= 0 && open_principal >= 0;
+            locked_covers_open_swaps &= locked >= open_principal;
+            solvent &= held >= locked;
+        }
+
+        let status_counts_match = pending + active + claimed + refunded + failed == created;
+        let open_count_matches = created
+            .checked_sub(completed + refunded + failed)
+            .is_some_and(|open| open == pending + active);
+        let counters_non_negative = completed <= created && locked_non_negative;
+
+        HealthReport {
+            healthy: status_counts_match
+                && open_count_matches
+                && counters_non_negative
+                && locked_covers_open_swaps
+                && solvent,
+            status_counts_match,
+            open_count_matches,
+            counters_non_negative,
+            locked_covers_open_swaps,
+            solvent,
+        }
+    }
+
+    /// Get the minimum protocol fee for a token
+    pub fn get_min_fee(env: Env, token: Address) -> i128 {
+        get_min_fee(&env, &token)
+    }
+
+    /// Get the external fee policy contract, if any
+    pub fn get_fee_policy(env: Env) -> Option<Address> {
+        get_fee_policy(&env)
+    }
+
+    /// Quote the fee and transfers of a prospective swap
+    ///
+    /// Reflects the current fee settings; a recipient-paid fee is charged
+    /// at claim, so it may change before then.
+    ///
+    /// # Arguments
+    /// * `token` - Token of the swap
+    /// * `amount` - Swap amount
+    /// * `sender` - Address that would create the swap
+    /// * `resolver` - Resolver that would be assigned, if any
+    /// * `fee_payer` - Party the fee would be charged to
+    pub fn quote_swap(
+        env: Env,
+        token: Address,
+        amount: i128,
+        sender: Address,
+        resolver: Option<Address>,
+        fee_payer: FeePayer,
+    ) -> SwapQuote {
+        if amount <= 0 {
+            panic_with_error!(&env, HTLCError::InvalidAmount);
+        }
+
+        let fee = swap_fee(&env, &token, amount, &sender, &resolver);
+        match fee_payer {
+            FeePayer::Sender => SwapQuote { fee, sender_total: amount + fee, recipient_payout: amount },
+            FeePayer::Recipient => SwapQuote { fee, sender_total: amount, recipient_payout: amount - fee },
+        }
+    }
+
+    /// Check whether a resolver can currently be assigned swaps
+    /// 
+    /// A resolver is live when it is active and its latest heartbeat falls
+    /// within the staleness window.
+    pub fn is_resolver_live(env: Env, resolver: Address) -> bool {
+        get_resolver(&env, &resolver)
+            .map(|resolver_info| is_live_resolver(&env, &resolver_info))
+            .unwrap_or(false)
+    }
+
+    /// Get the resolvers with the highest claimed volume, highest first
+    /// 
+    /// # Arguments
+    /// * `limit` - Maximum number of resolvers to return (at most `MAX_LEADERBOARD_SIZE`)
+    pub fn get_top_resolvers(env: Env, limit: u32) -> Vec<ResolverInfo> {
+        let mut top = Vec::new(&env);
+        for resolver in get_resolver_leaderboard(&env).iter().take(limit as usize) {
+            if let Some(resolver_info) = get_resolver(&env, &resolver) {
+                top.push_back(resolver_info);
+            }
+        }
+        top
+    }
+
+    /// Get the registered Ethereum HTLC contract for a chain
+    pub fn get_chain_contract(env: Env, chain_id: u64) -> Option<Address> {
+        get_chain_contract(&env, chain_id)
+    }
+
+    /// Get the claimable balance credited to a recipient for a token
+    pub fn get_claimable_balance(env: Env, recipient: Address, token: Address) -> i128 {
+        get_claimable_balance(&env, &recipient, &token)
+    }
+
+    /// Get the balance of a token the contract owes to swaps, claimable
+    /// payouts and resolver collateral
+    pub fn get_locked_balance(env: Env, token: Address) -> i128 {
+        get_locked_balance(&env, &token)
+    }
+
+    /// Get the principal of a token held in open swaps
+    /// 
+    /// Unlike `get_locked_balance`, this excludes prepaid fees, claimable
+    /// payouts, collateral and reward pools; it drops by each swap's amount
+    /// as the swap is claimed or refunded.
+    pub fn get_total_locked(env: Env, token: Address) -> i128 {
+        get_total_locked(&env, &token)
+    }
+
+    /// Get the addresses watching a swap for status changes
+    pub fn get_swap_watchers(env: Env, swap_id: String) -> Vec<Address> {
+        get_swap_watchers(&env, &swap_id)
+    }
+
+    /// Get the reward a resolver could claim now in a token
+    pub fn get_resolver_rewards(env: Env, resolver: Address, token: Address) -> i128 {
+        get_resolver_reward(&env, &resolver, &token)
+    }
+
+    /// Get the fees set aside for resolver rewards in a token
+    pub fn get_reward_pool(env: Env, token: Address) -> i128 {
+        get_reward_pool(&env, &token)
+    }
+
+    /// Get the nonce a resolver must include on its next claim
+    pub fn get_resolver_nonce(env: Env, resolver: Address) -> u64 {
+        get_resolver_nonce(&env, &resolver)
+    }
+
+    /// Get the unsettled swaps assigned to a resolver
+    pub fn get_resolver_active_swaps(env: Env, resolver: Address) -> Vec<String> {
+        get_resolver_swap_ids(&env, &resolver)
+    }
+
+    /// Get a page of the unsettled swaps assigned to a resolver
+    /// 
+    /// Lets a resolver recover its open work after a restart; swaps leave
+    /// the list once claimed or refunded.
+    /// 
+    /// # Arguments
+    /// * `resolver` - Resolver whose swaps are listed
+    /// * `start` - Index of the first swap ID to return
+    /// * `limit` - Maximum number of swap IDs to return (at most `MAX_PAGE_SIZE`)
+    pub fn get_resolver_swaps(env: Env, resolver: Address, start: u32, limit: u32) -> Vec<String> {
+        if limit > MAX_PAGE_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        let swap_ids = get_resolver_swap_ids(&env, &resolver);
+        let start = start.min(swap_ids.len());
+        let end = start.saturating_add(limit).min(swap_ids.len());
+        swap_ids.slice(start..end)
+    }
+
+    /// Get the number of unsettled swaps assigned to a resolver
+    pub fn get_resolver_open_count(env: Env, resolver: Address) -> u32 {
+        get_resolver_swap_ids(&env, &resolver).len()
+    }
+
+    /// Get resolver information
+    pub fn get_resolver_info(env: Env, resolver: Address) -> Option<ResolverInfo> {
+        get_resolver(&env, &resolver)
+    }
+
+    /// Check whether an address is on the sender allowlist
+    pub fn is_allowed_sender(env: Env, addr: Address) -> bool {
+        is_allowed_sender(&env, &addr)
+    }
+
+    /// Check whether swap recipients must be registered
+    pub fn get_recipient_registration(env: Env) -> bool {
+        get_recipient_registration_required(&env)
+    }
+
+    /// Check whether an address is a registered recipient
+    pub fn is_registered_recipient(env: Env, addr: Address) -> bool {
+        is_registered_recipient(&env, &addr)
+    }
+
+    /// Check whether an address is in the allowed creator set
+    pub fn is_allowed_creator(env: Env, addr: Address) -> bool {
+        is_allowed_creator(&env, &addr)
+    }
+
+    /// Check whether an address is blocked
+    pub fn is_address_blocked(env: Env, addr: Address) -> bool {
+        is_blocked_address(&env, &addr)
+    }
+
+    /// Check whether an address is exempt from the protocol fee
+    pub fn is_fee_exempt(env: Env, address: Address) -> bool {
+        is_fee_exempt(&env, &address)
+    }
+
+    /// Get user's swap IDs
+    pub fn get_user_swaps(env: Env, user: Address) -> Vec<String> {
+        get_user_swap_ids(&env, &user)
+    }
+
+    /// Get a page of compact summaries of a user's swaps
+    /// 
+    /// Summaries are built from the hot swap records only, so list views
+    /// avoid loading full swaps.
+    /// 
+    /// # Arguments
+    /// * `user` - Address whose swaps are listed
+    /// * `start` - Index of the first swap to summarize
+    /// * `limit` - Maximum number of summaries to return (at most `MAX_PAGE_SIZE`)
+    pub fn get_user_swap_summaries(env: Env, user: Address, start: u32, limit: u32) -> Vec<SwapSummary> {
+        if limit > MAX_PAGE_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        let swap_ids = get_user_swap_ids(&env, &user);
+        let start = start.min(swap_ids.len());
+        let end = start.saturating_add(limit).min(swap_ids.len());
+        let mut summaries = Vec::new(&env);
+        for swap_id in swap_ids.slice(start..end).iter() {
+            if let Some(swap) = get_swap_core(&env, &swap_id) {
+                summaries.push_back(SwapSummary {
+                    id: swap.id,
+                    status: swap.status,
+                    amount: swap.amount,
+                    timelock: swap.timelock,
+                    recipient: swap.recipient,
+                });
+            }
+        }
+        summaries
+    }
+
+    /// Get the IDs of the swaps in an order group, in creation order
+    pub fn get_group_swaps(env: Env, order_group: BytesN<32>) -> Vec<String> {
+        get_group_swap_ids(&env, &order_group)
+    }
+
+    /// Get the completion progress of an order group
+    /// 
+    /// A swap counts as filled once it is claimed.
+    pub fn get_group_progress(env: Env, order_group: BytesN<32>) -> GroupProgress {
+        let mut progress = GroupProgress { swaps: 0, filled_swaps: 0, total_amount: 0, filled_amount: 0 };
+        for swap_id in get_group_swap_ids(&env, &order_group).iter() {
+            if let Some(swap) = get_swap_core(&env, &swap_id) {
+                progress.swaps += 1;
+                progress.total_amount += swap.amount;
+                if swap.status == SwapStatus::Claimed {
+                    progress.filled_swaps += 1;
+                    progress.filled_amount += swap.amount;
+                }
+            }
+        }
+        progress
+    }
+
+    /// Get a page of every swap ID in creation order
+    /// 
+    /// # Arguments
+    /// * `start` - Index of the first swap ID to return
+    /// * `limit` - Maximum number of swap IDs to return (at most `MAX_PAGE_SIZE`)
+    pub fn get_all_swaps(env: Env, start: u32, limit: u32) -> Vec<String> {
+        if limit > MAX_PAGE_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        // Swap counter values start at 1, one per swap ever created
+        let mut page = Vec::new(&env);
+        let end = (start as u64).saturating_add(limit as u64).min(get_swap_counter(&env));
+        for index in (start as u64 + 1)..=end {
+            if let Some(swap_id) = get_swap_by_index(&env, index) {
+                page.push_back(swap_id);
+            }
+        }
+        page
+    }
+
+    /// Get the ID of the swap created at a swap counter value
+    /// 
+    /// Indexes start at 1 and never change, so auditors can walk every
+    /// swap from 1 up to the current counter.
+    pub fn get_swap_id_by_index(env: Env, index: u64) -> Option<String> {
+        get_swap_by_index(&env, index)
+    }
+
+    /// Summarize the swaps created at a range of swap counter values
+    /// 
+    /// Indexes past the current counter are skipped.
+    /// 
+    /// # Arguments
+    /// * `from_index` - First index to include
+    /// * `to_index` - Last index to include (at most `MAX_RANGE_SIZE` indexes
+    ///   from `from_index`)
+    pub fn get_swaps_range(env: Env, from_index: u64, to_index: u64) -> Vec<SwapSummary> {
+        let mut summaries = Vec::new(&env);
+        if from_index > to_index {
+            return summaries;
+        }
+        if to_index - from_index >= MAX_RANGE_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        for index in from_index..=to_index.min(get_swap_counter(&env)) {
+            let Some(swap_id) = get_swap_by_index(&env, index) else {
+                continue;
+            };
+            if let Some(swap) = get_swap_core(&env, &swap_id) {
+                summaries.push_back(SwapSummary {
+                    id: swap.id,
+                    status: swap.status,
+                    amount: swap.amount,
+                    timelock: swap.timelock,
+                    recipient: swap.recipient,
+                });
+            }
+        }
+        summaries
+    }
+
+    /// Get a page of the swap IDs created within a time range
+    /// 
+    /// Results are ascending by creation time.
+    /// 
+    /// # Arguments
+    /// * `from_ts` - Earliest creation time to include
+    /// * `to_ts` - Latest creation time to include
+    /// * `start` - Index of the first matching swap ID to return
+    /// * `limit` - Maximum number of swap IDs to return (at most `MAX_PAGE_SIZE`)
+    pub fn get_swaps_created_between(
+        env: Env,
+        from_ts: u64,
+        to_ts: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<String> {
+        if limit > MAX_PAGE_SIZE {
+            panic_with_error!(&env, HTLCError::BatchTooLarge);
+        }
+
+        let counter = get_swap_counter(&env);
+        let created_at = |index: u64| get_created_at_by_index(&env, index).unwrap_or(u64::MAX);
+
+        // Binary search for the first swap created at or after `from_ts`;
+        // ledger time never decreases, so creation times follow the counter
+        let (mut low, mut high) = (1u64, counter + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if created_at(mid) < from_ts {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let mut page = Vec::new(&env);
+        let mut index = low.saturating_add(start as u64);
+        while index <= counter && page.len() < limit && created_at(index) <= to_ts {
+            if let Some(swap_id) = get_swap_by_index(&env, index) {
+                page.push_back(swap_id);
+            }
+            index += 1;
+        }
+        page
+    }
+}
+
+/// Helper function to validate and open a new swap
+/// 
+/// Applies the creation policy checks, locks the funds in the contract, and
+/// stores the swap. Callers are responsible for authorizing the sender.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `new_swap` - Terms of the swap to open
+/// 
+/// # Returns
+/// Identifier of the new swap
+fn open_swap(env: &Env, new_swap: NewSwap) -> String {
+    let NewSwap {
+        sender,
+        recipient,
+        hashlock,
+        timelock,
+        token,
+        amount,
+        eth_contract,
+        eth_chain_id,
+        options,
+    } = new_swap;
+    let SwapOptions {
+        resolver,
+        use_allowance,
+        claim_hook,
+        counter_amount,
+        counter_token,
+        counter_decimals,
+        memo,
+        refund_authority,
+        eth_finality_deadline,
+        hashlock_len,
+        public_unlock,
+        claim_deadline,
+        refund_to,
+        fee_payer,
+        order_group,
+        min_net_amount,
+        quote_token,
+        quote_amount,
+        refund_hook,
+    } = options;
+
+    // Refuse swaps until a pending initialization is confirmed
+    if get_initialization_deadline(env).is_some() {
+        panic_with_error!(env, HTLCError::InitializationUnconfirmed);
+    }
+    
+    // Enforce sender allowlist in permissioned mode
+    if get_permissioned_mode(env) && !is_allowed_sender(env, &sender) {
+        panic_with_error!(env, HTLCError::Unauthorized);
+    }
+    
+    // Enforce creator set when creation is permissioned
+    if get_permissioned_creation(env) && !is_allowed_creator(env, &sender) {
+        panic_with_error!(env, HTLCError::CreatorNotAllowed);
+    }
+    
+    // Reject blocked participants
+    if is_blocked_address(env, &sender) || is_blocked_address(env, &recipient) {
+        panic_with_error!(env, HTLCError::AddressBlocked);
+    }
+    
+    // Enforce the recipient registry when registration is required
+    if get_recipient_registration_required(env) && !is_registered_recipient(env, &recipient) {
+        panic_with_error!(env, HTLCError::RecipientNotRegistered);
+    }
+    
+    // Validate inputs
+    if amount < MIN_SWAP_AMOUNT {
+        panic_with_error!(env, HTLCError::InvalidAmount);
+    }
+    
+    if min_net_amount.is_some_and(|min_net_amount| min_net_amount <= 0 || min_net_amount > amount) {
+        panic_with_error!(env, HTLCError::InvalidAmount);
+    }
+    
+    if let Some(memo) = &memo {
+        if memo.len() > MAX_MEMO_LENGTH {
+            panic_with_error!(env, HTLCError::MemoTooLong);
+        }
+    }
+    
+    // Exchange-rate fields are recorded all together or not at all
+    match (counter_amount, &counter_token, counter_decimals) {
+        (None, None, None) => {}
+        (Some(counter_amount), Some(_), Some(counter_decimals)) => {
+            if counter_amount <= 0 || counter_decimals > MAX_COUNTER_DECIMALS {
+                panic_with_error!(env, HTLCError::InvalidAmount);
+            }
+        }
+        _ => panic_with_error!(env, HTLCError::InvalidAmount),
+    }
+    
+    // Quote fields are recorded together or not at all
+    match (&quote_token, quote_amount) {
+        (None, None) => {}
+        (Some(_), Some(quote_amount)) if quote_amount > 0 => {}
+        _ => panic_with_error!(env, HTLCError::InvalidAmount),
+    }
+    
+    let current_time = env.ledger().timestamp();
+    if !is_valid_timelock(current_time, timelock) {
+        panic_with_error!(env, HTLCError::InvalidTimelock);
+    }
+    
+    if eth_finality_deadline.is_some_and(|deadline| deadline > timelock) {
+        panic_with_error!(env, HTLCError::InvalidTimelock);
+    }
+    
+    if public_unlock.is_some_and(|unlock| unlock >= timelock) {
+        panic_with_error!(env, HTLCError::InvalidTimelock);
+    }
+    
+    if claim_deadline.is_some_and(|deadline| deadline <= current_time || deadline > timelock) {
+        panic_with_error!(env, HTLCError::InvalidTimelock);
+    }
+    
+    // The Ethereum lock must reach finality while the swap is still claimable
+    let finality_delay = get_chain_finality_delay(env, eth_chain_id);
+    let finality_at = current_time.saturating_add(finality_delay);
+    if finality_at > claim_cutoff(timelock, get_claim_cutoff_buffer(env)) {
+        panic_with_error!(env, HTLCError::InvalidTimelock);
+    }
+    
+    let hashlock_len = hashlock_len.unwrap_or(32);
+    if !(MIN_HASHLOCK_LEN..=32).contains(&hashlock_len) {
+        panic_with_error!(env, HTLCError::InvalidHashlockLength);
+    }
+    
+    // Keep group views within budget
+    if let Some(order_group) = &order_group {
+        if get_group_swap_ids(env, order_group).len() >= MAX_GROUP_SWAPS {
+            panic_with_error!(env, HTLCError::BatchTooLarge);
+        }
+    }
+    
+    // Swaps on a registered chain must target its Ethereum contract
+    if let Some(registered) = get_chain_contract(env, eth_chain_id) {
+        if registered != eth_contract {
+            panic_with_error!(env, HTLCError::UnknownEthContract);
+        }
+    }
+    
+    // Check resolver if provided
+    let resolver_info = resolver.as_ref().map(|resolver| eligible_resolver(env, resolver, amount));
+    
+    // Generate unique swap ID
+    let mut swap_counter = get_swap_counter(env);
+    swap_counter += 1;
+    set_swap_counter(env, swap_counter);
+    
+    let swap_id = generate_swap_id(env, swap_counter, &sender, &recipient, &token, amount, &hashlock);
+    
+    // Check if swap already exists
+    if get_swap_core(env, &swap_id).is_some() {
+        panic_with_error!(env, HTLCError::SwapAlreadyExists);
+    }
+
+    enter_guard(env);
+
+    // A sender-paid fee is locked on top of the amount until the swap settles
+    let prepaid_fee = match fee_payer {
+        FeePayer::Sender => swap_fee(env, &token, amount, &sender, &resolver),
+        FeePayer::Recipient => 0,
+    };
+    let locked = amount + prepaid_fee;
+
+    // Lock funds in the contract
+    let token_client = token::Client::new(env, &token);
+    let stellar_decimals = token_client.decimals();
+    let evm_decimals = counter_decimals.unwrap_or(DEFAULT_EVM_DECIMALS);
+    if let (Some(counter_amount), Some(counter_decimals)) = (counter_amount, counter_decimals) {
+        check_oracle_price(env, &token, amount, stellar_decimals, counter_amount, counter_decimals);
+    }
+    let contract_address = env.current_contract_address();
+    if use_allowance {
+        if token_client.allowance(&sender, &contract_address) < locked {
+            panic_with_error!(env, HTLCError::InsufficientBalance);
+        }
+        if token_client.try_transfer_from(&contract_address, &sender, &contract_address, &locked).is_err() {
+            panic_with_error!(env, HTLCError::TokenTransferFailed);
+        }
+    } else {
+        transfer_or_fail(env, &token_client, &sender, &contract_address, locked);
+    }
+    add_locked_balance(env, &token, locked);
+    add_total_locked(env, &token, amount);
+    exit_guard(env);
+
+    // Create swap object
+    let swap = Swap {
+        id: swap_id.clone(),
+        index: swap_counter,
+        sender: sender.clone(),
+        recipient: recipient.clone(),
+        token: token.clone(),
+        amount,
+        hashlock: hashlock.clone(),
+        hashlock_len,
+        timelock,
+        status: SwapStatus::Pending,
+        created_at: current_time,
+        claimed_at: None,
+        refunded_at: None,
+        preimage: None,
+        salt: None,
+        eth_contract,
+        eth_chain_id,
+        resolver,
+        claim_hook,
+        refund_hook,
+        counter_amount,
+        counter_token: counter_token.clone(),
+        counter_decimals,
+        stellar_decimals,
+        evm_decimals,
+        memo,
+        order_group: order_group.clone(),
+        quote_token: quote_token.clone(),
+        quote_amount,
+        refund_authority,
+        eth_finality_deadline,
+        public_unlock,
+        claim_deadline,
+        refund_to: refund_to.unwrap_or_else(|| sender.clone()),
+        fee_payer: fee_payer.clone(),
+        prepaid_fee,
+        min_net_amount,
+        finality_at,
+        resolver_reward: 0,
+    };
+
+    // Store the swap
+    set_swap(env, &swap_id, &swap);
+    
+    // Track user swaps
+    add_user_swap(env, &sender, &swap_id);
+    set_swap_by_index(env, swap_counter, &swap_id);
+    set_created_at_by_index(env, swap_counter, swap.created_at);
+    if let Some(order_group) = &order_group {
+        add_group_swap(env, order_group, &swap_id);
+    }
+    
+    // Count the swap against the resolver until it is settled
+    if let Some(mut resolver_info) = resolver_info {
+        resolver_info.open_swaps += 1;
+        resolver_info.open_value += amount;
+        set_resolver(env, &resolver_info.resolver, &resolver_info);
+        add_resolver_swap(env, &resolver_info.resolver, &swap_id);
+    }
+    
+    // Update statistics
+    let total_swaps = get_total_swaps_created(env) + 1;
+    set_total_swaps_created(env, total_swaps);
+    record_status_transition(env, None, SwapStatus::Pending);
+
+    // Emit event; the fields outgrow a tuple, so they are listed as a
+    // vector with the same encoding
+    let fields: Vec<Val> = vec![
+        env,
+        swap_id.into_val(env),
+        sender.into_val(env),
+        recipient.into_val(env),
+        amount.into_val(env),
+        timelock.into_val(env),
+        counter_amount.into_val(env),
+        counter_token.into_val(env),
+        counter_decimals.into_val(env),
+        stellar_decimals.into_val(env),
+        evm_decimals.into_val(env),
+        claim_deadline.into_val(env),
+        swap.refund_to.into_val(env),
+        fee_payer.into_val(env),
+        finality_delay.into_val(env),
+        quote_token.into_val(env),
+        quote_amount.into_val(env),
+    ];
+    publish_event(
+        env,
+        ("swap_created",),
+        fields
+    );
+
+    swap_id
+}
+
+/// Helper function to claim a swap for its recipient
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap to claim
+/// * `preimage` - Secret that hashes to the swap's hashlock
+/// * `salt` - Salt for swaps committed to as `sha256(preimage || salt)`
+/// * `resolver_nonce` - Nonce of the swap's resolver when it submits the
+///   claim, `None` when the recipient claims
+fn execute_claim(
+    env: &Env,
+    swap_id: String,
+    preimage: BytesN<32>,
+    salt: Option<BytesN<32>>,
+    resolver_nonce: Option<u64>,
+) {
+    enter_guard(env);
+
+    let mut swap = get_swap_core(env, &swap_id)
+        .unwrap_or_else(|| panic_with_error!(env, HTLCError::SwapNotFound));
+
+    // Check swap status
+    if swap.status == SwapStatus::Claimed {
+        panic_with_error!(env, HTLCError::AlreadyClaimed);
+    }
+    
+    if swap.status == SwapStatus::Refunded {
+        panic_with_error!(env, HTLCError::AlreadyRefunded);
+    }
+    
+    // Failed swaps can only be refunded, even if the secret is known
+    if swap.status == SwapStatus::Failed {
+        panic_with_error!(env, HTLCError::SwapNotClaimable);
+    }
+
+    // Check timelock hasn't expired
+    let current_time = env.ledger().timestamp();
+    if current_time >= swap.timelock {
+        panic_with_error!(env, HTLCError::TimelockExpired);
+    }
+
+    // Stop claims shortly before the timelock so a late claim cannot
+    // race the counterpart refund
+    if current_time > claim_cutoff(swap.timelock, get_claim_cutoff_buffer(env)) {
+        panic_with_error!(env, HTLCError::ClaimWindowClosed);
+    }
+
+    // Stale orders stop being claimable at their claim deadline
+    if is_past_claim_deadline(current_time, swap.claim_deadline) {
+        panic_with_error!(env, HTLCError::ClaimWindowClosed);
+    }
+
+    // The Ethereum leg could still reorg out after its finality deadline
+    if swap.eth_finality_deadline.is_some_and(|deadline| current_time > deadline) {
+        panic_with_error!(env, HTLCError::EthFinalityExpired);
+    }
+
+    // Nor can the claim come before the Ethereum lock is final
+    if current_time < swap.finality_at {
+        panic_with_error!(env, HTLCError::FinalityDelayActive);
+    }
+
+    // With auto-confirm enabled a pending swap is claimable only once the
+    // delay since creation has passed; the cold record is read just here
+    let auto_confirm_after = get_auto_confirm_after(env);
+    if swap.status == SwapStatus::Pending && auto_confirm_after > 0 {
+        let created_at = get_swap_meta(env, &swap_id)
+            .unwrap_or_else(|| panic_with_error!(env, HTLCError::SwapNotFound))
+            .created_at;
+        if current_time < created_at.saturating_add(auto_confirm_after) {
+            panic_with_error!(env, HTLCError::SwapNotConfirmed);
+        }
+        transition(env, &swap_id, &mut swap, SwapStatus::Active);
+        publish_event(
+            env,
+            ("swap_auto_confirmed",),
+            swap_id.clone()
+        );
     }
 
-    /// Get resolver information
-    pub fn get_resolver_info(env: Env, resolver: Address) -> Option<ResolverInfo> {
-        get_resolver(&env, &resolver)
+    // Verify preimage matches hashlock
+    if !matches_hashlock(env, &swap, &preimage, &salt) {
+        panic_with_error!(env, HTLCError::InvalidPreimage);
     }
 
-    /// Get user's swap IDs
-    pub fn get_user_swaps(env: Env, user: Address) -> Vec<String> {
-        get_user_swap_ids(&env, &user)
+    // Blocked recipients cannot receive funds
+    if is_blocked_address(env, &swap.recipient) {
+        panic_with_error!(env, HTLCError::AddressBlocked);
+    }
+
+    match resolver_nonce {
+        // Once the public window opens anyone may submit the claim; the
+        // funds still go to the recipient
+        None if swap.public_unlock.is_some_and(|unlock| current_time >= unlock) => {}
+        // As can a relayer once the recipient preauthorized the claim
+        None if is_claim_preauthorized(env, &swap_id) => {}
+        // Otherwise only recipient can claim; the authorization is scoped to this
+        // swap and preimage so a signed entry can't be replayed against another swap
+        None => swap.recipient.require_auth_for_args(
+            (swap_id.clone(), preimage.clone()).into_val(env)
+        ),
+        // The swap's resolver may claim for the recipient with its next nonce,
+        // so a re-broadcast of an earlier resolver claim is rejected
+        Some(nonce) => {
+            let Some(resolver) = &swap.resolver else {
+                panic_with_error!(env, HTLCError::Unauthorized);
+            };
+            resolver.require_auth_for_args(
+                (swap_id.clone(), preimage.clone(), nonce).into_val(env)
+            );
+            if nonce != get_resolver_nonce(env, resolver) {
+                panic_with_error!(env, HTLCError::InvalidResolverNonce);
+            }
+            set_resolver_nonce(env, resolver, nonce + 1);
+        }
+    }
+
+    // The recipient is paid net of the protocol fee unless the sender
+    // prepaid it
+    let (fee_amount, payout) = match swap.fee_payer {
+        FeePayer::Sender => (swap.prepaid_fee, swap.amount),
+        FeePayer::Recipient => {
+            let fee_amount = swap_fee(env, &swap.token, swap.amount, &swap.sender, &swap.resolver);
+            (fee_amount, swap.amount - fee_amount)
+        }
+    };
+
+    // Fail rather than deliver less than the recipient agreed to
+    if swap.min_net_amount.is_some_and(|min_net_amount| payout < min_net_amount) {
+        panic_with_error!(env, HTLCError::PayoutBelowMinimum);
+    }
+    
+    // Never mark a swap claimed that the contract can't pay out in full
+    let token_client = token::Client::new(env, &swap.token);
+    if token_client.balance(&env.current_contract_address()) < swap.amount + swap.prepaid_fee {
+        panic_with_error!(env, HTLCError::InsufficientBalance);
+    }
+    
+    // Update swap before any token call
+    transition(env, &swap_id, &mut swap, SwapStatus::Claimed);
+    swap.claimed_at = Some(current_time);
+    swap.preimage = Some(preimage.clone());
+    swap.salt = salt;
+    // Part of the fee on resolver swaps stays locked in the reward pool
+    swap.resolver_reward = accrue_resolver_reward(env, &swap, fee_amount);
+    
+    set_swap_core(env, &swap_id, &swap);
+    settle_resolver_swap(env, &swap_id, &swap.resolver, swap.amount, true);
+    release_total_locked(env, &swap.token, swap.amount);
+
+    // Update statistics
+    let total_completed = get_total_swaps_completed(env) + 1;
+    set_total_swaps_completed(env, total_completed);
+
+    let distributed = fee_amount - swap.resolver_reward;
+
+    // Release locked funds to the recipient and the fee recipients
+    if distributed > 0 {
+        distribute_fee(env, &token_client, &swap_id, &swap.token, distributed);
+    }
+    // A rejected push transfer is credited to an internal claimable
+    // balance instead of reverting the claim, so the revealed preimage
+    // stays on record and the recipient can withdraw once it can receive
+    // the token again
+    let delivery = token_client.try_transfer(&env.current_contract_address(), &swap.recipient, &payout);
+    let delivered = delivery.is_ok();
+    if let Err(error) = delivery {
+        let error_code = match error {
+            Ok(error) => error.get_code(),
+            Err(InvokeError::Contract(code)) => code,
+            Err(InvokeError::Abort) => 0,
+        };
+        add_claimable_balance(env, &swap.recipient, &swap.token, payout);
+        emit_claim_fallback(env, swap_id.clone(), swap.recipient.clone(), swap.token.clone(), payout, error_code);
+    }
+    // An undelivered payout stays locked as a claimable balance
+    let released = if delivered { payout + distributed } else { distributed };
+    release_locked_balance(env, &swap.token, released);
+    exit_guard(env);
+
+    // Emit event
+    publish_event(
+        env,
+        ("swap_claimed",),
+        (swap_id.clone(), swap.recipient.clone(), preimage, payout)
+    );
+    if let (Some(resolver), true) = (&swap.resolver, swap.resolver_reward > 0) {
+        publish_event(
+            env,
+            ("resolver_rewarded",),
+            (swap_id.clone(), resolver.clone(), swap.token.clone(), swap.resolver_reward)
+        );
+    }
+
+    // Notify the claim hook once funds are delivered; a failing hook must
+    // not undo the claim
+    if let (true, Some(hook)) = (delivered, &swap.claim_hook) {
+        let result = ClaimHookClient::new(env, hook).try_on_htlc_claimed(
+            &swap_id,
+            &swap.recipient,
+            &payout,
+        );
+        if result.is_err() {
+            publish_event(
+                env,
+                ("claim_hook_failed",),
+                (swap_id, hook.clone())
+            );
+        }
+    }
+}
+
+/// Helper function to collect violated contract invariants
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// 
+/// # Returns
+/// Names of violated invariants
+fn invariant_violations(env: &Env) -> Vec<String> {
+    let mut violations = Vec::new(env);
+    let mut check = |holds: bool, name: &str| {
+        if !holds {
+            violations.push_back(String::from_str(env, name));
+        }
+    };
+
+    check(has_admin(env) || is_admin_renounced(env), "admin_set");
+    check(has_fee_splits(env), "fee_recipient_set");
+    check(get_protocol_fee_bps(env) <= MAX_PROTOCOL_FEE_BPS, "fee_within_max");
+
+    // Every created swap is in exactly one status bucket
+    let created = get_total_swaps_created(env);
+    let pending = get_status_count(env, &SwapStatus::Pending);
+    let active = get_status_count(env, &SwapStatus::Active);
+    let claimed = get_status_count(env, &SwapStatus::Claimed);
+    let refunded = get_status_count(env, &SwapStatus::Refunded);
+    let failed = get_status_count(env, &SwapStatus::Failed);
+    check(
+        pending + active + claimed + refunded + failed == created,
+        "status_counts_match_created",
+    );
+    check(
+        get_total_swaps_completed(env) + refunded + failed <= created,
+        "settled_within_created",
+    );
+
+    check(
+        MIN_TIMELOCK_DURATION < MAX_TIMELOCK_DURATION
+            && get_claim_cutoff_buffer(env) < MIN_TIMELOCK_DURATION,
+        "timelock_bounds",
+    );
+
+    violations
+}
+
+/// Helper function to compare a stored swap with requested terms
+/// 
+/// # Returns
+/// Whether every stored term of `swap` equals the request
+fn is_same_swap(swap: &Swap, new_swap: &NewSwap) -> bool {
+    swap.sender == new_swap.sender
+        && swap.recipient == new_swap.recipient
+        && swap.hashlock == new_swap.hashlock
+        && swap.hashlock_len == new_swap.options.hashlock_len.unwrap_or(32)
+        && swap.timelock == new_swap.timelock
+        && swap.token == new_swap.token
+        && swap.amount == new_swap.amount
+        && swap.eth_contract == new_swap.eth_contract
+        && swap.eth_chain_id == new_swap.eth_chain_id
+        && swap.resolver == new_swap.options.resolver
+        && swap.claim_hook == new_swap.options.claim_hook
+        && swap.counter_amount == new_swap.options.counter_amount
+        && swap.counter_token == new_swap.options.counter_token
+        && swap.counter_decimals == new_swap.options.counter_decimals
+        && swap.memo == new_swap.options.memo
+        && swap.refund_authority == new_swap.options.refund_authority
+        && swap.eth_finality_deadline == new_swap.options.eth_finality_deadline
+        && swap.public_unlock == new_swap.options.public_unlock
+        && swap.claim_deadline == new_swap.options.claim_deadline
+        && swap.refund_to == new_swap.options.refund_to.clone().unwrap_or_else(|| new_swap.sender.clone())
+        && swap.fee_payer == new_swap.options.fee_payer
+        && swap.order_group == new_swap.options.order_group
+        && swap.min_net_amount == new_swap.options.min_net_amount
+        && swap.quote_token == new_swap.options.quote_token
+        && swap.quote_amount == new_swap.options.quote_amount
+        && swap.refund_hook == new_swap.options.refund_hook
+}
+
+/// Helper function to check resolver liveness
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `resolver_info` - Resolver to check
+/// 
+/// # Returns
+/// Whether the resolver is active and heard from within the staleness window
+fn is_live_resolver(env: &Env, resolver_info: &ResolverInfo) -> bool {
+    let elapsed = env.ledger().timestamp().saturating_sub(resolver_info.last_active);
+    resolver_info.is_active && elapsed <= get_staleness_window(env)
+}
+
+/// Helper function to check whether a claim with the right preimage would
+/// pass every status and timing check at `now`
+fn is_swap_claimable(env: &Env, swap: &Swap, now: u64) -> bool {
+    let auto_confirm_after = get_auto_confirm_after(env);
+    matches!(swap.status, SwapStatus::Pending | SwapStatus::Active)
+        && is_claimable_at(now, swap.timelock, get_claim_cutoff_buffer(env), swap.claim_deadline)
+        && swap.eth_finality_deadline.is_none_or(|deadline| now <= deadline)
+        && now >= swap.finality_at
+        && !(swap.status == SwapStatus::Pending
+            && auto_confirm_after > 0
+            && now < swap.created_at.saturating_add(auto_confirm_after))
+}
+
+/// Helper function to check whether an unsettled swap can be refunded
+/// 
+/// Swaps the admin marked failed or whose counterpart was attested
+/// refunded on Ethereum are refundable at once; others once the timelock
+/// expires or an unmet claim deadline passes.
+fn is_swap_refundable(env: &Env, swap_id: &String, swap: &SwapCore, now: u64) -> bool {
+    swap.status == SwapStatus::Failed
+        || is_refundable_at(now, swap.timelock, swap.claim_deadline)
+        || get_eth_attestation(env, swap_id).is_some_and(|attestation| attestation.event == CounterpartEvent::Refunded)
+}
+
+/// Helper function to refund a swap, paying the caller a bounty
+/// 
+/// Returns the locked funds, net of the bounty, to the swap's refund
+/// address and marks the swap refunded. Callers check the swap is
+/// refundable and emit their own event.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap to refund
+/// * `swap` - Swap to refund, updated in place
+/// * `caller` - Address receiving the bounty
+/// * `bounty_bps` - Bounty in basis points of the swap amount
+/// 
+/// # Returns
+/// Bounty paid to the caller
+fn refund_with_bounty(env: &Env, swap_id: &String, swap: &mut SwapCore, caller: &Address, bounty_bps: u32) -> i128 {
+    enter_guard(env);
+
+    // Update swap before any token call
+    let current_time = env.ledger().timestamp();
+    transition(env, swap_id, swap, SwapStatus::Refunded);
+    swap.refunded_at = Some(current_time);
+    
+    set_swap_core(env, swap_id, swap);
+    settle_resolver_swap(env, swap_id, &swap.resolver, swap.amount, false);
+    release_total_locked(env, &swap.token, swap.amount);
+
+    // Return locked funds to the refund address, net of the bounty
+    let token_client = token::Client::new(env, &swap.token);
+    let bounty = calculate_protocol_fee(swap.amount, bounty_bps);
+    if bounty > 0 {
+        transfer_or_fail(env, &token_client, &env.current_contract_address(), caller, bounty);
+    }
+    let locked = swap.amount + swap.prepaid_fee;
+    transfer_or_fail(
+        env,
+        &token_client,
+        &env.current_contract_address(),
+        &swap.refund_to,
+        locked - bounty,
+    );
+    release_locked_balance(env, &swap.token, locked);
+    exit_guard(env);
+
+    // Emit events
+    let elapsed = swap_age(env, swap_id, current_time);
+    emit_funds_refunded(env, swap_id.clone(), swap.sender.clone(), locked - bounty, swap.resolver.clone(), elapsed);
+    if let Some(resolver) = &swap.resolver {
+        emit_resolver_defaulted(env, swap_id.clone(), resolver.clone(), SwapStatus::Refunded, elapsed);
+    }
+    notify_refund_hook(env, swap_id, swap, locked - bounty);
+
+    bounty
+}
+
+/// Helper function to notify a swap's refund hook
+/// 
+/// Runs after the refund has settled; a failing hook is reported with a
+/// `refund_hook_failed` event and never undoes the refund.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the refunded swap
+/// * `swap` - Refunded swap
+/// * `amount` - Amount returned to the refund address
+fn notify_refund_hook(env: &Env, swap_id: &String, swap: &SwapCore, amount: i128) {
+    if let Some(hook) = &swap.refund_hook {
+        if RefundHookClient::new(env, hook).try_on_swap_refunded(swap_id, &amount).is_err() {
+            publish_event(
+                env,
+                ("refund_hook_failed",),
+                (swap_id.clone(), hook.clone())
+            );
+        }
+    }
+}
+
+/// Helper function to load a resolver that may be assigned a swap
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `resolver` - Resolver to assign
+/// * `amount` - Amount of the swap
+/// 
+/// # Returns
+/// The resolver's information, failing if it isn't live, the amount
+/// exceeds its limit or its collateral wouldn't cover its open swaps
+fn eligible_resolver(env: &Env, resolver: &Address, amount: i128) -> ResolverInfo {
+    let resolver_info = get_resolver(env, resolver)
+        .filter(|resolver_info| is_live_resolver(env, resolver_info))
+        .unwrap_or_else(|| panic_with_error!(env, HTLCError::ResolverNotActive));
+    if resolver_info.max_swap_amount > 0 && amount > resolver_info.max_swap_amount {
+        panic_with_error!(env, HTLCError::SwapExceedsResolverLimit);
+    }
+    if resolver_info.deposited_collateral < required_collateral(env, resolver_info.open_value + amount) {
+        panic_with_error!(env, HTLCError::InsufficientCollateral);
+    }
+    resolver_info
+}
+
+/// Helper function to compute the collateral required against open swaps
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `open_value` - Total amount of a resolver's open swaps
+/// 
+/// # Returns
+/// `open_value` scaled by the collateral ratio (0 when no ratio is set)
+fn required_collateral(env: &Env, open_value: i128) -> i128 {
+    calculate_protocol_fee(open_value, get_collateral_ratio_bps(env))
+}
+
+/// Helper function to release a settled swap from its resolver
+/// 
+/// Releases the swap from the resolver's open count, open value and active
+/// swap list and, for claims, credits the volume, with a single resolver
+/// read and write.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Settled swap
+/// * `resolver` - Resolver assigned to the swap, if any
+/// * `amount` - Amount of the swap
+/// * `claimed` - Whether the swap was claimed
+fn settle_resolver_swap(
+    env: &Env,
+    swap_id: &String,
+    resolver: &Option<Address>,
+    amount: i128,
+    claimed: bool,
+) {
+    let Some(resolver) = resolver else {
+        return;
+    };
+    remove_resolver_swap(env, resolver, swap_id);
+    let Some(mut resolver_info) = get_resolver(env, resolver) else {
+        return;
+    };
+    resolver_info.open_swaps = resolver_info.open_swaps.saturating_sub(1);
+    resolver_info.open_value = (resolver_info.open_value - amount).max(0);
+    if claimed {
+        resolver_info.total_resolved += 1;
+        resolver_info.total_volume += amount;
+    }
+    set_resolver(env, resolver, &resolver_info);
+    if claimed {
+        update_resolver_leaderboard(env, &resolver_info);
+    }
+}
+
+/// Helper function to rescale an amount between decimal precisions
+/// 
+/// # Returns
+/// The rescaled amount, truncated toward zero when scaling down, or `None`
+/// if the result overflows `i128`
+fn scale_amount(amount: i128, from_decimals: u32, to_decimals: u32) -> Option<i128> {
+    if to_decimals >= from_decimals {
+        amount.checked_mul(10i128.checked_pow(to_decimals - from_decimals)?)
+    } else {
+        // Divisors beyond i128 truncate every amount to zero
+        Some(10i128.checked_pow(from_decimals - to_decimals).map_or(0, |divisor| amount / divisor))
+    }
+}
+
+/// Helper function to hash a swap secret
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `preimage` - Secret revealed at claim
+/// * `salt` - Salt appended to the secret for salted commitments
+/// 
+/// # Returns
+/// `sha256(preimage)`, or `sha256(preimage || salt)` when a salt is given
+fn hash_secret(env: &Env, preimage: &BytesN<32>, salt: &Option<BytesN<32>>) -> BytesN<32> {
+    let mut secret = Bytes::from_array(env, &preimage.to_array());
+    if let Some(salt) = salt {
+        secret.append(&Bytes::from_array(env, &salt.to_array()));
+    }
+    env.crypto().sha256(&secret).into()
+}
+
+/// Helper function to pay a collected fee out to the fee splits
+/// 
+/// Each recipient gets its share rounded down and the first recipient also
+/// takes the remainder, so the full fee is always paid out.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `token_client` - Client for the fee token
+/// * `swap_id` - Swap the fee was taken from
+/// * `token` - Fee token
+/// * `fee_amount` - Total fee to distribute
+fn distribute_fee(env: &Env, token_client: &token::Client, swap_id: &String, token: &Address, fee_amount: i128) {
+    let splits = get_fee_splits(env);
+    let mut remainder = fee_amount;
+    for split in splits.iter() {
+        remainder -= calculate_protocol_fee(fee_amount, split.share_bps);
+    }
+    for (i, split) in splits.iter().enumerate() {
+        let mut share = calculate_protocol_fee(fee_amount, split.share_bps);
+        if i == 0 {
+            share += remainder;
+        }
+        if share > 0 {
+            transfer_or_fail(env, token_client, &env.current_contract_address(), &split.recipient, share);
+            emit_fee_collected(env, swap_id.clone(), token.clone(), share, split.recipient);
+        }
+    }
+}
+
+/// Helper function to set aside the reward for a claimed resolver swap
+/// 
+/// Credits the reward, at most the fee collected, to the resolver's next
+/// payout and moves it into the token's reward pool.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap` - Claimed swap
+/// * `fee_amount` - Protocol fee collected on the swap
+/// 
+/// # Returns
+/// Part of the fee moved into the reward pool
+fn accrue_resolver_reward(env: &Env, swap: &SwapCore, fee_amount: i128) -> i128 {
+    let Some(resolver) = &swap.resolver else {
+        return 0;
+    };
+    let reward_bps = get_resolver_reward_bps(env);
+    if reward_bps == 0 {
+        return 0;
+    }
+
+    let reserved = calculate_protocol_fee(swap.amount, reward_bps).min(fee_amount);
+    let accrued = get_resolver_reward(env, resolver, &swap.token);
+    set_resolver_reward(env, resolver, &swap.token, accrued + reserved);
+    set_reward_pool(env, &swap.token, get_reward_pool(env, &swap.token) + reserved);
+    reserved
+}
+
+/// Helper function to validate and store a new or re-registered resolver
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `resolver` - Resolver address
+/// * `collateral_token` - Token used for collateral
+/// * `min_collateral` - Minimum collateral amount required
+fn add_resolver(env: &Env, resolver: Address, collateral_token: Address, min_collateral: i128) {
+    if min_collateral <= 0 {
+        panic_with_error!(env, HTLCError::InsufficientCollateral);
+    }
+
+    if is_blocked_address(env, &resolver) {
+        panic_with_error!(env, HTLCError::AddressBlocked);
+    }
+
+    // Re-registering a resolver updates its terms in place, keeping its
+    // collateral and swap accounting; it doesn't take another slot
+    let resolver_info = match get_resolver(env, &resolver) {
+        Some(mut existing) => {
+            // Deposited collateral is held in the old token
+            if existing.collateral_token != collateral_token && existing.deposited_collateral > 0 {
+                panic_with_error!(env, HTLCError::ResolverInUse);
+            }
+            existing.collateral_token = collateral_token;
+            existing.min_collateral = min_collateral;
+            existing.is_active = true;
+            existing
+        }
+        None => {
+            let count = get_resolver_count(env);
+            let max_resolvers = get_max_resolvers(env);
+            if max_resolvers > 0 && count >= max_resolvers {
+                panic_with_error!(env, HTLCError::ResolverLimitReached);
+            }
+            set_resolver_count(env, count + 1);
+
+            ResolverInfo {
+                resolver: resolver.clone(),
+                collateral_token,
+                min_collateral,
+                deposited_collateral: 0,
+                open_swaps: 0,
+                open_value: 0,
+                max_swap_amount: 0,
+                is_active: true,
+                total_resolved: 0,
+                total_volume: 0,
+                created_at: env.ledger().timestamp(),
+                last_active: env.ledger().timestamp(),
+            }
+        }
+    };
+
+    set_resolver(env, &resolver, &resolver_info);
+
+    publish_event(
+        env,
+        ("resolver_registered",),
+        (resolver, min_collateral)
+    );
+}
+
+/// Helper function to check a secret against a swap's hashlock
+/// 
+/// Only the first `hashlock_len` bytes of the digest and hashlock are
+/// compared, so truncated Ethereum commitments can be matched.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap` - Swap whose hashlock is checked
+/// * `preimage` - Secret revealed at claim
+/// * `salt` - Salt appended to the secret for salted commitments
+fn matches_hashlock(env: &Env, swap: &SwapCore, preimage: &BytesN<32>, salt: &Option<BytesN<32>>) -> bool {
+    let len = swap.hashlock_len as usize;
+    hash_secret(env, preimage, salt).to_array()[..len] == swap.hashlock.to_array()[..len]
+}
+
+/// Helper function to determine the fee taken from a swap
+/// 
+/// Quotes the configured fee policy contract when one is set, capping the
+/// quote at `MAX_PROTOCOL_FEE_BPS` of the amount. Falls back to the internal
+/// basis-point fee, raised to the token's minimum fee, if no policy is set
+/// or the policy call fails.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `token` - Token of the swap
+/// * `amount` - Swap amount the fee is taken from
+/// * `sender` - Address that created the swap
+/// 
+/// # Returns
+/// Fee amount to collect
+fn compute_fee(env: &Env, token: &Address, amount: i128, sender: &Address) -> i128 {
+    let max_fee = calculate_protocol_fee(amount, MAX_PROTOCOL_FEE_BPS);
+    if let Some(policy) = get_fee_policy(env) {
+        if let Ok(Ok(fee)) = FeePolicyClient::new(env, &policy).try_get_fee(token, &amount, sender) {
+            return fee.clamp(0, max_fee);
+        }
+    }
+    let bps_fee = calculate_protocol_fee(amount, get_protocol_fee_bps(env));
+    bps_fee.max(get_min_fee(env, token).min(max_fee))
+}
+
+/// Helper function to link a swap to its Ethereum escrow
+/// 
+/// Rejects escrows already linked to another swap. Callers check who may
+/// link and clear any previous link of the swap.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap
+/// * `counterpart_id` - ID of the Ethereum escrow
+/// * `proof_source` - Address vouching for the link
+fn link_swap_counterpart(env: &Env, swap_id: &String, counterpart_id: &BytesN<32>, proof_source: Address) {
+    if get_counterpart_swap(env, counterpart_id).is_some_and(|linked| linked != *swap_id) {
+        panic_with_error!(env, HTLCError::CounterpartAlreadyLinked);
+    }
+    set_swap_counterpart(env, swap_id, counterpart_id);
+    emit_counterpart_linked(env, swap_id.clone(), counterpart_id.clone(), proof_source);
+}
+
+/// Helper function to keep an unfinished statistics recount in step
+/// 
+/// A swap the recount has already passed is moved to its new status in
+/// the partial counts, so it isn't counted in its old one.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap
+/// * `from` - Status the swap leaves
+/// * `to` - Status the swap enters
+fn track_recounted_transition(env: &Env, swap_id: &String, from: &SwapStatus, to: &SwapStatus) {
+    let Some(mut recount) = get_stats_recount(env) else {
+        return;
+    };
+    let counted = get_swap_meta(env, swap_id).is_some_and(|meta| meta.index < recount.next_index);
+    if counted {
+        adjust_status_count(&mut recount.counts, from, -1);
+        adjust_status_count(&mut recount.counts, to, 1);
+        set_stats_recount(env, &recount);
+    }
+}
+
+/// Helper function to add `delta` swaps to one status in a set of counts
+fn adjust_status_count(counts: &mut StatusCounts, status: &SwapStatus, delta: i64) {
+    let count = match status {
+        SwapStatus::Pending => &mut counts.pending,
+        SwapStatus::Active => &mut counts.active,
+        SwapStatus::Claimed => &mut counts.claimed,
+        SwapStatus::Refunded => &mut counts.refunded,
+        SwapStatus::Failed => &mut counts.failed,
+    };
+    *count = count.saturating_add_signed(delta);
+}
+
+/// Helper function to move a swap to a new status
+/// 
+/// Every status change goes through here: it rejects transitions outside
+/// the swap state machine with `SwapNotPending`, keeps the status
+/// counts in step, notifies watchers and emits `status`. The caller still
+/// persists the swap.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap
+/// * `swap` - Swap to update in place
+/// * `new_status` - Status to move to
+fn transition(env: &Env, swap_id: &String, swap: &mut SwapCore, new_status: SwapStatus) {
+    if !is_valid_transition(&swap.status, &new_status) {
+        panic_with_error!(env, HTLCError::SwapNotPending);
+    }
+    record_status_transition(env, Some(swap.status.clone()), new_status.clone());
+    track_recounted_transition(env, swap_id, &swap.status, &new_status);
+    notify_watchers(env, swap_id, swap.status.clone(), new_status.clone());
+    emit_swap_status_updated(env, swap_id.clone(), swap.status.clone(), new_status.clone());
+    swap.status = new_status;
+}
+
+/// Helper function to notify a swap's watchers of a status transition
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap
+/// * `from` - Status before the transition
+/// * `to` - Status after the transition
+fn notify_watchers(env: &Env, swap_id: &String, from: SwapStatus, to: SwapStatus) {
+    let watchers = get_swap_watchers(env, swap_id);
+    if !watchers.is_empty() {
+        emit_swap_status_changed(env, swap_id.clone(), watchers, from, to);
+    }
+}
+
+/// Helper function to get the time elapsed since a swap was created
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `swap_id` - Unique identifier of the swap
+/// * `now` - Current ledger timestamp
+fn swap_age(env: &Env, swap_id: &String, now: u64) -> u64 {
+    get_swap_meta(env, swap_id).map_or(0, |meta| now.saturating_sub(meta.created_at))
+}
+
+/// Helper function to compute the fee charged on a swap
+/// 
+/// Swaps of fee-exempt partners, as sender or resolver, pay no fee.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `token` - Token of the swap
+/// * `amount` - Swap amount the fee is based on
+/// * `sender` - Address that created the swap
+/// * `resolver` - Resolver assigned to the swap, if any
+/// 
+/// # Returns
+/// Fee amount to collect
+fn swap_fee(env: &Env, token: &Address, amount: i128, sender: &Address, resolver: &Option<Address>) -> i128 {
+    let fee_exempt = is_fee_exempt(env, sender)
+        || resolver.as_ref().is_some_and(|resolver| is_fee_exempt(env, resolver));
+    if fee_exempt {
+        0
+    } else {
+        compute_fee(env, token, amount, sender)
+    }
+}
+
+/// Helper function to check a swap's price against the price oracle
+/// 
+/// Does nothing while no oracle is set. A failing oracle or a non-positive
+/// price rejects the swap, as its amount can't be validated.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `token` - Token of the swap
+/// * `amount` - Swap amount
+/// * `stellar_decimals` - Decimals of the token
+/// * `counter_amount` - Agreed amount of the EVM asset
+/// * `counter_decimals` - Decimals of the EVM asset
+fn check_oracle_price(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+    stellar_decimals: u32,
+    counter_amount: i128,
+    counter_decimals: u32,
+) {
+    let Some(oracle) = get_price_oracle(env) else {
+        return;
+    };
+    let price = match PriceOracleClient::new(env, &oracle).try_price(token) {
+        Ok(Ok(price)) if price > 0 => price,
+        _ => panic_with_error!(env, HTLCError::PriceDeviation),
+    };
+    let in_range = normalized_rate(amount, stellar_decimals, counter_amount, counter_decimals)
+        .is_some_and(|rate| is_within_deviation(rate, price, get_max_price_deviation_bps(env)));
+    if !in_range {
+        panic_with_error!(env, HTLCError::PriceDeviation);
+    }
+}
+
+/// Helper function to mark the start of a section that calls token contracts
+/// 
+/// Fails with `ReentrantCall` if another guarded section is already in
+/// progress, so a token can't call back into the contract mid-operation.
+fn enter_guard(env: &Env) {
+    if is_in_progress(env) {
+        panic_with_error!(env, HTLCError::ReentrantCall);
+    }
+    set_in_progress(env, true);
+}
+
+/// Helper function to mark the end of a guarded section
+fn exit_guard(env: &Env) {
+    set_in_progress(env, false);
+}
+
+/// Helper function to transfer tokens, failing with a contract error
+/// 
+/// Token contracts such as the Stellar Asset Contract reject transfers to
+/// or from deauthorized accounts with a host error; this surfaces those as
+/// `TokenTransferFailed` instead.
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `token_client` - Client of the token to transfer
+/// * `from` - Address the tokens are taken from
+/// * `to` - Address the tokens are sent to
+/// * `amount` - Amount to transfer
+fn transfer_or_fail(env: &Env, token_client: &token::Client, from: &Address, to: &Address, amount: i128) {
+    if token_client.try_transfer(from, to, &amount).is_err() {
+        panic_with_error!(env, HTLCError::TokenTransferFailed);
     }
 }
 
+/// Helper function to derive an Ethereum address from a public key
+/// 
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `public_key` - Uncompressed SEC-1 secp256k1 public key (0x04 || x || y)
+/// 
+/// # Returns
+/// Last 20 bytes of the keccak256 hash of `x || y`
+fn eth_address_from_public_key(env: &Env, public_key: &BytesN<65>) -> BytesN<20> {
+    let key_bytes = Bytes::from_array(env, &public_key.to_array()).slice(1..);
+    let hash = env.crypto().keccak256(&key_bytes).to_array();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    BytesN::from_array(env, &address)
+}
+
 /// Helper function to generate unique swap ID
 /// 
 /// Generates a deterministic but unique identifier for each swap based on
-/// ledger sequence, timestamp, amount, and hashlock. This ensures uniqueness
-/// while remaining deterministic for cross-chain coordination.
+/// the swap counter, ledger sequence, timestamp, amount, and hashlock. The
+/// SHA-256 digest of these fields is hex-encoded to form the identifier.
 /// 
 /// # Arguments
 /// * `env` - Soroban environment
+/// * `swap_counter` - Monotonic swap counter value for this swap
 /// * `sender` - Swap initiator address
 /// * `recipient` - Swap recipient address  
 /// * `token` - Token contract address
@@ -406,36 +4052,23 @@ impl StellarHTLC {
 /// Unique string identifier for the swap
 fn generate_swap_id(
     env: &Env,
+    swap_counter: u64,
     _sender: &Address,
     _recipient: &Address,
     _token: &Address,
     amount: i128,
     hashlock: &BytesN<32>,
 ) -> String {
-    let mut data = Bytes::new(env);
-    
-    // Add current ledger sequence for uniqueness
-    let ledger_seq = env.ledger().sequence();
-    data.extend_from_slice(&ledger_seq.to_be_bytes());
-    
-    // Add timestamp for additional uniqueness
-    let timestamp = env.ledger().timestamp();
-    data.extend_from_slice(&timestamp.to_be_bytes());
-    
-    // Add amount
-    data.extend_from_slice(&amount.to_be_bytes());
+    // The counter keeps every ID distinct; ledger sequence and timestamp add
+    // uniqueness across deployments
+    let data = swap_id_data(
+        swap_counter,
+        env.ledger().sequence(),
+        env.ledger().timestamp(),
+        amount,
+        &hashlock.to_array(),
+    );
+    let hash = env.crypto().sha256(&Bytes::from_array(env, &data)).to_array();
     
-    // Add hashlock
-    data.extend_from_slice(&hashlock.to_array());
-    
-    let _hash = env.crypto().sha256(&data);
-    
-    // Create a simple unique identifier based on timestamp and ledger sequence
-    // This ensures uniqueness while avoiding complex string operations in no_std
-    // In production, this could be enhanced with base64 encoding of the hash
-    if timestamp % 2 == 0 {
-        String::from_str(env, "swap_even")
-    } else {
-        String::from_str(env, "swap_odd")
-    }
+    String::from_bytes(env, &hex_encode(&hash))
 }
@@ -0,0 +1,136 @@
+//! Pure helpers shared by the contract and the host-side `client` module
+//!
+//! Nothing here touches the environment, so clients can reproduce the
+//! contract's derivations byte for byte.
+
+use crate::types::{SwapStatus, MAX_TIMELOCK_DURATION, MIN_TIMELOCK_DURATION, RATE_SCALE};
+
+/// Length of the data hashed into a swap ID
+pub const SWAP_ID_DATA_LEN: usize = 68;
+
+/// Lay out the data hashed into a swap ID
+///
+/// # Arguments
+/// * `swap_counter` - Contract swap counter after the swap is opened
+/// * `ledger_sequence` - Ledger sequence the swap is opened in
+/// * `timestamp` - Ledger timestamp the swap is opened at
+/// * `amount` - Swap amount
+/// * `hashlock` - Swap hashlock
+pub fn swap_id_data(
+    swap_counter: u64,
+    ledger_sequence: u32,
+    timestamp: u64,
+    amount: i128,
+    hashlock: &[u8; 32],
+) -> [u8; SWAP_ID_DATA_LEN] {
+    let mut data = [0u8; SWAP_ID_DATA_LEN];
+    data[0..8].copy_from_slice(&swap_counter.to_be_bytes());
+    data[8..12].copy_from_slice(&ledger_sequence.to_be_bytes());
+    data[12..20].copy_from_slice(&timestamp.to_be_bytes());
+    data[20..36].copy_from_slice(&amount.to_be_bytes());
+    data[36..68].copy_from_slice(hashlock);
+    data
+}
+
+/// Lowercase hex-encode a swap ID digest without heap allocation
+pub fn hex_encode(hash: &[u8; 32]) -> [u8; 64] {
+    const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = [0u8; 64];
+    for (i, byte) in hash.iter().enumerate() {
+        hex[i * 2] = HEX_CHARS[(byte >> 4) as usize];
+        hex[i * 2 + 1] = HEX_CHARS[(byte & 0x0f) as usize];
+    }
+    hex
+}
+
+/// Calculate the protocol fee for an amount
+///
+/// # Arguments
+/// * `amount` - Swap amount the fee is taken from
+/// * `fee_bps` - Protocol fee in basis points
+///
+/// # Returns
+/// Fee amount, rounded down
+pub fn calculate_protocol_fee(amount: i128, fee_bps: u32) -> i128 {
+    // Split the amount so large swaps can't overflow the multiplication
+    let fee_bps = fee_bps as i128;
+    amount / 10_000 * fee_bps + amount % 10_000 * fee_bps / 10_000
+}
+
+/// Compute the exchange rate of a swap
+///
+/// The rate is the Stellar amount per unit of the EVM counter asset, with
+/// both sides normalized by their decimals and the result scaled by
+/// `RATE_SCALE`.
+///
+/// # Returns
+/// `None` if the rate does not fit in an `i128`
+pub fn normalized_rate(amount: i128, stellar_decimals: u32, counter_amount: i128, counter_decimals: u32) -> Option<i128> {
+    // rate = (amount / 10^stellar_decimals) / (counter_amount / 10^counter_decimals) * RATE_SCALE
+    if counter_decimals >= stellar_decimals {
+        let scale = 10i128.checked_pow(counter_decimals - stellar_decimals)?;
+        amount
+            .checked_mul(scale)?
+            .checked_mul(RATE_SCALE)?
+            .checked_div(counter_amount)
+    } else {
+        let scale = 10i128.checked_pow(stellar_decimals - counter_decimals)?;
+        amount
+            .checked_mul(RATE_SCALE)?
+            .checked_div(counter_amount.checked_mul(scale)?)
+    }
+}
+
+/// Check a rate lies within `max_deviation_bps` of a reference price
+pub fn is_within_deviation(rate: i128, price: i128, max_deviation_bps: u32) -> bool {
+    let deviation = rate.abs_diff(price);
+    deviation.saturating_mul(10_000) <= price.unsigned_abs().saturating_mul(max_deviation_bps as u128)
+}
+
+/// Check a timelock is within the allowed duration from `now`
+pub fn is_valid_timelock(now: u64, timelock: u64) -> bool {
+    timelock > now.saturating_add(MIN_TIMELOCK_DURATION)
+        && timelock <= now.saturating_add(MAX_TIMELOCK_DURATION)
+}
+
+/// Last timestamp at which a claim passes the cutoff buffer check
+pub fn claim_cutoff(timelock: u64, cutoff_buffer: u64) -> u64 {
+    timelock.saturating_sub(cutoff_buffer)
+}
+
+/// Check a swap's optional claim deadline has passed at `now`
+pub fn is_past_claim_deadline(now: u64, claim_deadline: Option<u64>) -> bool {
+    claim_deadline.is_some_and(|deadline| now > deadline)
+}
+
+/// Check a swap can still be claimed at `now`
+pub fn is_claimable_at(now: u64, timelock: u64, cutoff_buffer: u64, claim_deadline: Option<u64>) -> bool {
+    now < timelock
+        && now <= claim_cutoff(timelock, cutoff_buffer)
+        && !is_past_claim_deadline(now, claim_deadline)
+}
+
+/// Check a swap can be refunded at `now`
+///
+/// Refunds open at the timelock, or earlier once an unmet claim deadline
+/// has passed.
+pub fn is_refundable_at(now: u64, timelock: u64, claim_deadline: Option<u64>) -> bool {
+    now >= timelock || is_past_claim_deadline(now, claim_deadline)
+}
+
+/// Check a swap may move from one status to another
+///
+/// Open swaps (`Pending`, or `Active` once the counterpart is confirmed)
+/// settle as `Claimed` or `Refunded`, or are marked `Failed`; a failed swap
+/// can still be refunded. `Pending` may settle directly, as the Ethereum
+/// leg isn't always confirmed on-chain. Settled swaps never change again.
+pub fn is_valid_transition(from: &SwapStatus, to: &SwapStatus) -> bool {
+    matches!(
+        (from, to),
+        (SwapStatus::Pending, SwapStatus::Active)
+            | (SwapStatus::Pending | SwapStatus::Active, SwapStatus::Claimed)
+            | (SwapStatus::Pending | SwapStatus::Active, SwapStatus::Refunded)
+            | (SwapStatus::Pending | SwapStatus::Active, SwapStatus::Failed)
+            | (SwapStatus::Failed, SwapStatus::Refunded)
+    )
+}
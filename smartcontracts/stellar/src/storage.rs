@@ -1,113 +1,1061 @@
-use soroban_sdk::{Env, Address, String, contracttype, Vec};
-use crate::types::{Swap, ResolverInfo};
+//! Contract storage
+//! 
+//! Keys are split into one enum per domain. A key is encoded as its variant
+//! name followed by its payload, so variant names are unique across all
+//! enums and two domains can never address the same entry.
+//! 
+//! Storage layout:
+//! 
+//! | Enum | Durability | Keys |
+//! |------|------------|------|
+//! | `ConfigKey` | instance | `Admin`, `FeeSplits`, `ProtocolFeeBps`, `FeePolicy`, `PriceOracle`, `MaxPriceDeviationBps`, `EthAttester`, `CounterpartOracle`, `SwapCounter`, `TemplateCounter`, `ResolverRewardBps`, `TotalSwapsCreated`, `TotalSwapsCompleted`, `StatusCount`, `PermissionedMode`, `RecipientRegistrationRequired`, `PermissionedCreation`, `StalenessWindow`, `ResolverCount`, `MaxResolvers`, `CollateralRatioBps`, `AbandonmentPeriod`, `AbandonmentBountyBps`, `RefundBountyBps`, `ClaimCutoffBuffer`, `CancelCooldown`, `StatsRecount`, `AutoConfirmAfter`, `RenounceRequestedAt`, `AdminRenounced`, `InitializationDeadline`, `InProgress` |
+//! | `SwapKey` | persistent | `Swap`, `SwapMeta`, `EthAttestation`, `RevealedPreimage`, `ClaimPreauthorized`, `SwapFailure`, `SwapCounterpart`, `CounterpartSwap`, `Template`, `GroupSwaps`, `SwapWatchers`, `SwapByIndex`, `CreatedAtByIndex`, `IdempotentSwap` |
+//! | `ResolverKey` | persistent | `Resolver`, `ResolverSwaps`, `ResolverNonce`, `ResolverReward`, `ResolverLeaderboard` |
+//! | `UserKey` | persistent | `UserSwaps`, `MakerKey`, `UsedNonce`, `ClaimableBalance`, `AllowedSender`, `RegisteredRecipient`, `BlockedAddress`, `FeeExempt`, `AllowedCreator` |
+//! | `TokenKey` | persistent | `MinFee`, `LockedBalance`, `TotalLocked`, `RewardPool` |
+//! | `ChainKey` | persistent | `ChainConfig`, `ChainFinalityDelay` |
+//! 
+//! Instance entries share the contract's TTL; swap and resolver records
+//! extend their own TTL when written.
 
-/// Storage keys for contract data
+use soroban_sdk::{Env, Address, BytesN, IntoVal, String, Val, contracttype, panic_with_error, Vec};
+use crate::errors::HTLCError;
+use crate::types::{
+    FailureCode, FeeSplit, Swap, SwapCore, SwapMeta, SwapStatus, ResolverInfo, StatsRecount, EthAttestation, TemplateInfo, DEFAULT_ABANDONMENT_BOUNTY_BPS,
+    DEFAULT_ABANDONMENT_PERIOD, DEFAULT_CLAIM_CUTOFF_BUFFER, DEFAULT_STALENESS_WINDOW,
+    INSTANCE_TTL_EXTEND_TO, INSTANCE_TTL_THRESHOLD, MAX_LEADERBOARD_SIZE, PERSISTENT_TTL_EXTEND_TO,
+    PERSISTENT_TTL_THRESHOLD,
+};
+
+/// Contract configuration and counters, kept in instance storage
 #[contracttype]
 #[derive(Clone)]
-pub enum StorageKey {
+pub enum ConfigKey {
     /// Contract administrator
     Admin,
-    /// Fee recipient address
-    FeeRecipient,
+    /// Recipients the protocol fee is split between
+    FeeSplits,
     /// Protocol fee in basis points
     ProtocolFeeBps,
+    /// External fee policy contract
+    FeePolicy,
+    /// Price oracle checked at swap creation
+    PriceOracle,
+    /// Largest accepted deviation from the oracle price, in basis points
+    MaxPriceDeviationBps,
+    /// Ethereum address of the registered attester
+    EthAttester,
+    /// Oracle allowed to link swaps to their Ethereum escrows
+    CounterpartOracle,
     /// Swap counter for unique ID generation
     SwapCounter,
-    /// Individual swap data
+    /// Number of swap templates created
+    TemplateCounter,
+    /// Reward paid to resolvers per unit of claimed volume, in basis points
+    ResolverRewardBps,
+    /// Total swaps created counter
+    TotalSwapsCreated,
+    /// Total swaps completed counter
+    TotalSwapsCompleted,
+    /// Number of swaps currently in each status
+    StatusCount(SwapStatus),
+    /// Whether swap creation is restricted to allowed senders
+    PermissionedMode,
+    /// Whether swaps may only pay registered recipients
+    RecipientRegistrationRequired,
+    /// Whether swap creation is restricted to allowed creators
+    PermissionedCreation,
+    /// Time a resolver stays live without a heartbeat
+    StalenessWindow,
+    /// Number of registered resolvers
+    ResolverCount,
+    /// Largest number of registered resolvers (0 = unlimited)
+    MaxResolvers,
+    /// Collateral a resolver must hold against its open swaps, in basis points of their value
+    CollateralRatioBps,
+    /// Time after the timelock before anyone may refund a swap
+    AbandonmentPeriod,
+    /// Bounty paid for refunding an abandoned swap, in basis points
+    AbandonmentBountyBps,
+    /// Bounty paid to a keeper refunding an expired swap, in basis points
+    RefundBountyBps,
+    /// Time before the timelock when claims stop being accepted
+    ClaimCutoffBuffer,
+    /// Time after creation before a sender may cancel a swap
+    CancelCooldown,
+    /// Progress of a paged statistics recount
+    StatsRecount,
+    /// Time after creation when a pending swap counts as confirmed
+    AutoConfirmAfter,
+    /// Time the admin asked to renounce, pending confirmation
+    RenounceRequestedAt,
+    /// Set once the admin has been renounced
+    AdminRenounced,
+    /// Ledger by which the admin must confirm the initialization
+    InitializationDeadline,
+    /// Set while an operation is calling out to a token contract
+    InProgress,
+}
+
+/// Per-swap records and swap indexes, kept in persistent storage
+#[contracttype]
+#[derive(Clone)]
+pub enum SwapKey {
+    /// Hot swap record read on claim and refund
     Swap(String),
+    /// Cold swap metadata only read by views
+    SwapMeta(String),
+    /// Latest verified Ethereum attestation for a swap
+    EthAttestation(String),
+    /// Preimage published for a swap outside a successful claim
+    RevealedPreimage(String),
+    /// Whether a swap's recipient consented to claims submitted by anyone
+    ClaimPreauthorized(String),
+    /// Failure code and reason recorded for a failed swap
+    SwapFailure(String),
+    /// Ethereum escrow linked to a swap
+    SwapCounterpart(String),
+    /// Swap linked to an Ethereum escrow
+    CounterpartSwap(BytesN<32>),
+    /// Swap template by ID
+    Template(u64),
+    /// Swaps sharing an order group, in creation order
+    GroupSwaps(BytesN<32>),
+    /// Addresses notified of a swap's status changes
+    SwapWatchers(String),
+    /// Swap ID by the swap counter value it was created at
+    SwapByIndex(u64),
+    /// Creation time of the swap created at a swap counter value
+    CreatedAtByIndex(u64),
+    /// Swap created idempotently for a sender and hashlock
+    IdempotentSwap(Address, BytesN<32>),
+}
+
+/// Resolver records, kept in persistent storage
+#[contracttype]
+#[derive(Clone)]
+pub enum ResolverKey {
     /// Resolver information
     Resolver(Address),
+    /// Unsettled swaps assigned to a resolver
+    ResolverSwaps(Address),
+    /// Next nonce expected on a resolver's claim
+    ResolverNonce(Address),
+    /// Resolver rewards accrued since its last payout (resolver, token)
+    ResolverReward(Address, Address),
+    /// Resolvers ordered by claimed volume, highest first
+    ResolverLeaderboard,
+}
+
+/// Per-address permissions and balances, kept in persistent storage
+#[contracttype]
+#[derive(Clone)]
+pub enum UserKey {
     /// User's swap IDs list
     UserSwaps(Address),
-    /// Total swaps created counter
-    TotalSwapsCreated,
-    /// Total swaps completed counter
-    TotalSwapsCompleted,
+    /// Ed25519 key that signs a maker's orders
+    MakerKey(Address),
+    /// Order nonce already consumed by a maker
+    UsedNonce(Address, u64),
+    /// Funds owed to a recipient whose payout transfer failed (recipient, token)
+    ClaimableBalance(Address, Address),
+    /// Sender permitted to create swaps in permissioned mode
+    AllowedSender(Address),
+    /// Recipient registered for swaps while registration is required
+    RegisteredRecipient(Address),
+    /// Address barred from sending or receiving funds
+    BlockedAddress(Address),
+    /// Sender or resolver whose swaps pay no protocol fee
+    FeeExempt(Address),
+    /// Creator permitted to create swaps when creation is permissioned
+    AllowedCreator(Address),
+}
+
+/// Per-token accounting and settings, kept in persistent storage
+#[contracttype]
+#[derive(Clone)]
+pub enum TokenKey {
+    /// Minimum protocol fee for a token, in its smallest unit
+    MinFee(Address),
+    /// Funds the contract owes per token (open swaps, claimable balances, collateral, rewards)
+    LockedBalance(Address),
+    /// Principal of open swaps per token
+    TotalLocked(Address),
+    /// Fees set aside per token to pay resolver rewards
+    RewardPool(Address),
+}
+
+/// Per-chain settings, kept in persistent storage
+#[contracttype]
+#[derive(Clone)]
+pub enum ChainKey {
+    /// Registered Ethereum HTLC contract for a chain ID
+    ChainConfig(u64),
+    /// Time after creation before swaps on a chain ID can be claimed
+    ChainFinalityDelay(u64),
+}
+
+// TTL functions
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage().instance().extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Write a persistent entry and extend its TTL
+/// 
+/// Every persistent entry backs funds, an index or a replay guard, so each
+/// write keeps it alive for `PERSISTENT_TTL_EXTEND_TO` ledgers.
+fn set_persistent<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    env.storage().persistent().extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
 }
 
 // Configuration functions
 pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().instance().set(&StorageKey::Admin, admin);
+    env.storage().instance().set(&ConfigKey::Admin, admin);
 }
 
 pub fn get_admin(env: &Env) -> Address {
-    env.storage().instance().get(&StorageKey::Admin)
-        .unwrap_or_else(|| panic!("Admin not set"))
+    try_get_admin(env).unwrap_or_else(|| {
+        if is_admin_renounced(env) {
+            panic_with_error!(env, HTLCError::AdminRenounced)
+        }
+        panic_with_error!(env, HTLCError::NotInitialized)
+    })
+}
+
+pub fn try_get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ConfigKey::Admin)
+}
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&ConfigKey::Admin)
+}
+
+pub fn set_renounce_requested_at(env: &Env, requested_at: Option<u64>) {
+    match requested_at {
+        Some(requested_at) => env.storage().instance().set(&ConfigKey::RenounceRequestedAt, &requested_at),
+        None => env.storage().instance().remove(&ConfigKey::RenounceRequestedAt),
+    }
 }
 
-pub fn set_fee_recipient(env: &Env, recipient: &Address) {
-    env.storage().instance().set(&StorageKey::FeeRecipient, recipient);
+pub fn get_renounce_requested_at(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&ConfigKey::RenounceRequestedAt)
 }
 
+/// Clear the admin for good
+pub fn renounce_admin(env: &Env) {
+    env.storage().instance().remove(&ConfigKey::Admin);
+    env.storage().instance().set(&ConfigKey::AdminRenounced, &true);
+}
+
+pub fn is_admin_renounced(env: &Env) -> bool {
+    env.storage().instance().get(&ConfigKey::AdminRenounced).unwrap_or(false)
+}
+
+pub fn set_initialization_deadline(env: &Env, deadline: Option<u32>) {
+    match deadline {
+        Some(deadline) => env.storage().instance().set(&ConfigKey::InitializationDeadline, &deadline),
+        None => env.storage().instance().remove(&ConfigKey::InitializationDeadline),
+    }
+}
+
+pub fn get_initialization_deadline(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&ConfigKey::InitializationDeadline)
+}
+
+pub fn set_fee_splits(env: &Env, splits: &Vec<FeeSplit>) {
+    env.storage().instance().set(&ConfigKey::FeeSplits, splits);
+}
+
+pub fn get_fee_splits(env: &Env) -> Vec<FeeSplit> {
+    try_get_fee_splits(env).unwrap_or_else(|| panic_with_error!(env, HTLCError::NotInitialized))
+}
+
+pub fn try_get_fee_splits(env: &Env) -> Option<Vec<FeeSplit>> {
+    env.storage().instance().get(&ConfigKey::FeeSplits)
+}
+
+pub fn has_fee_splits(env: &Env) -> bool {
+    env.storage().instance().has(&ConfigKey::FeeSplits)
+}
+
+/// Primary fee recipient, which also takes any rounding remainder
 pub fn get_fee_recipient(env: &Env) -> Address {
-    env.storage().instance().get(&StorageKey::FeeRecipient)
-        .unwrap_or_else(|| panic!("Fee recipient not set"))
+    get_fee_splits(env).get_unchecked(0).recipient
+}
+
+pub fn try_get_fee_recipient(env: &Env) -> Option<Address> {
+    try_get_fee_splits(env).map(|splits| splits.get_unchecked(0).recipient)
 }
 
 pub fn set_protocol_fee_bps(env: &Env, fee_bps: u32) {
-    env.storage().instance().set(&StorageKey::ProtocolFeeBps, &fee_bps);
+    env.storage().instance().set(&ConfigKey::ProtocolFeeBps, &fee_bps);
 }
 
 pub fn get_protocol_fee_bps(env: &Env) -> u32 {
-    env.storage().instance().get(&StorageKey::ProtocolFeeBps)
+    env.storage().instance().get(&ConfigKey::ProtocolFeeBps)
         .unwrap_or(30) // Default 0.3%
 }
 
+pub fn set_min_fee(env: &Env, token: &Address, min_fee: i128) {
+    let key = TokenKey::MinFee(token.clone());
+    if min_fee == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_persistent(env, &key, &min_fee);
+    }
+}
+
+pub fn get_min_fee(env: &Env, token: &Address) -> i128 {
+    env.storage().persistent().get(&TokenKey::MinFee(token.clone())).unwrap_or(0)
+}
+
+pub fn set_fee_policy(env: &Env, policy: &Option<Address>) {
+    match policy {
+        Some(policy) => env.storage().instance().set(&ConfigKey::FeePolicy, policy),
+        None => env.storage().instance().remove(&ConfigKey::FeePolicy),
+    }
+}
+
+pub fn get_fee_policy(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ConfigKey::FeePolicy)
+}
+
+pub fn set_price_oracle(env: &Env, oracle: &Option<Address>, max_deviation_bps: u32) {
+    match oracle {
+        Some(oracle) => {
+            env.storage().instance().set(&ConfigKey::PriceOracle, oracle);
+            env.storage().instance().set(&ConfigKey::MaxPriceDeviationBps, &max_deviation_bps);
+        }
+        None => {
+            env.storage().instance().remove(&ConfigKey::PriceOracle);
+            env.storage().instance().remove(&ConfigKey::MaxPriceDeviationBps);
+        }
+    }
+}
+
+pub fn get_price_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ConfigKey::PriceOracle)
+}
+
+pub fn get_max_price_deviation_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::MaxPriceDeviationBps).unwrap_or(0)
+}
+
+pub fn set_counterpart_oracle(env: &Env, oracle: &Option<Address>) {
+    match oracle {
+        Some(oracle) => env.storage().instance().set(&ConfigKey::CounterpartOracle, oracle),
+        None => env.storage().instance().remove(&ConfigKey::CounterpartOracle),
+    }
+}
+
+pub fn get_counterpart_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ConfigKey::CounterpartOracle)
+}
+
+pub fn set_eth_attester(env: &Env, attester: &BytesN<20>) {
+    env.storage().instance().set(&ConfigKey::EthAttester, attester);
+}
+
+pub fn get_eth_attester(env: &Env) -> Option<BytesN<20>> {
+    env.storage().instance().get(&ConfigKey::EthAttester)
+}
+
 // Counter functions
 pub fn set_swap_counter(env: &Env, counter: u64) {
-    env.storage().instance().set(&StorageKey::SwapCounter, &counter);
+    env.storage().instance().set(&ConfigKey::SwapCounter, &counter);
 }
 
 pub fn get_swap_counter(env: &Env) -> u64 {
-    env.storage().instance().get(&StorageKey::SwapCounter)
+    env.storage().instance().get(&ConfigKey::SwapCounter)
         .unwrap_or(0)
 }
 
 pub fn set_total_swaps_created(env: &Env, total: u64) {
-    env.storage().instance().set(&StorageKey::TotalSwapsCreated, &total);
+    env.storage().instance().set(&ConfigKey::TotalSwapsCreated, &total);
 }
 
 pub fn get_total_swaps_created(env: &Env) -> u64 {
-    env.storage().instance().get(&StorageKey::TotalSwapsCreated)
+    env.storage().instance().get(&ConfigKey::TotalSwapsCreated)
         .unwrap_or(0)
 }
 
 pub fn set_total_swaps_completed(env: &Env, total: u64) {
-    env.storage().instance().set(&StorageKey::TotalSwapsCompleted, &total);
+    env.storage().instance().set(&ConfigKey::TotalSwapsCompleted, &total);
 }
 
 pub fn get_total_swaps_completed(env: &Env) -> u64 {
-    env.storage().instance().get(&StorageKey::TotalSwapsCompleted)
+    env.storage().instance().get(&ConfigKey::TotalSwapsCompleted)
+        .unwrap_or(0)
+}
+
+pub fn set_status_count(env: &Env, status: &SwapStatus, count: u64) {
+    env.storage().instance().set(&ConfigKey::StatusCount(status.clone()), &count);
+}
+
+pub fn get_status_count(env: &Env, status: &SwapStatus) -> u64 {
+    env.storage().instance().get(&ConfigKey::StatusCount(status.clone()))
+        .unwrap_or(0)
+}
+
+/// Move one swap between status buckets (`from` is `None` for new swaps)
+pub fn set_stats_recount(env: &Env, recount: &StatsRecount) {
+    env.storage().instance().set(&ConfigKey::StatsRecount, recount);
+}
+
+pub fn get_stats_recount(env: &Env) -> Option<StatsRecount> {
+    env.storage().instance().get(&ConfigKey::StatsRecount)
+}
+
+pub fn remove_stats_recount(env: &Env) {
+    env.storage().instance().remove(&ConfigKey::StatsRecount);
+}
+
+pub fn record_status_transition(env: &Env, from: Option<SwapStatus>, to: SwapStatus) {
+    if let Some(from) = from {
+        if from == to {
+            return;
+        }
+        set_status_count(env, &from, get_status_count(env, &from).saturating_sub(1));
+    }
+    set_status_count(env, &to, get_status_count(env, &to) + 1);
+}
+
+// Permissioned mode functions
+pub fn set_permissioned_mode(env: &Env, enabled: bool) {
+    env.storage().instance().set(&ConfigKey::PermissionedMode, &enabled);
+}
+
+pub fn get_permissioned_mode(env: &Env) -> bool {
+    env.storage().instance().get(&ConfigKey::PermissionedMode)
+        .unwrap_or(false)
+}
+
+pub fn set_allowed_sender(env: &Env, sender: &Address) {
+    set_persistent(env, &UserKey::AllowedSender(sender.clone()), &true);
+}
+
+pub fn remove_allowed_sender(env: &Env, sender: &Address) {
+    env.storage().persistent().remove(&UserKey::AllowedSender(sender.clone()));
+}
+
+pub fn is_allowed_sender(env: &Env, sender: &Address) -> bool {
+    env.storage().persistent().has(&UserKey::AllowedSender(sender.clone()))
+}
+
+// Recipient registration functions
+pub fn set_recipient_registration_required(env: &Env, required: bool) {
+    env.storage().instance().set(&ConfigKey::RecipientRegistrationRequired, &required);
+}
+
+pub fn get_recipient_registration_required(env: &Env) -> bool {
+    env.storage().instance().get(&ConfigKey::RecipientRegistrationRequired)
+        .unwrap_or(false)
+}
+
+pub fn set_registered_recipient(env: &Env, recipient: &Address) {
+    set_persistent(env, &UserKey::RegisteredRecipient(recipient.clone()), &true);
+}
+
+pub fn remove_registered_recipient(env: &Env, recipient: &Address) {
+    env.storage().persistent().remove(&UserKey::RegisteredRecipient(recipient.clone()));
+}
+
+pub fn is_registered_recipient(env: &Env, recipient: &Address) -> bool {
+    env.storage().persistent().has(&UserKey::RegisteredRecipient(recipient.clone()))
+}
+
+// Permissioned creation functions
+pub fn set_permissioned_creation(env: &Env, enabled: bool) {
+    env.storage().instance().set(&ConfigKey::PermissionedCreation, &enabled);
+}
+
+pub fn get_permissioned_creation(env: &Env) -> bool {
+    env.storage().instance().get(&ConfigKey::PermissionedCreation)
+        .unwrap_or(false)
+}
+
+pub fn set_allowed_creator(env: &Env, creator: &Address) {
+    set_persistent(env, &UserKey::AllowedCreator(creator.clone()), &true);
+}
+
+pub fn remove_allowed_creator(env: &Env, creator: &Address) {
+    env.storage().persistent().remove(&UserKey::AllowedCreator(creator.clone()));
+}
+
+pub fn is_allowed_creator(env: &Env, creator: &Address) -> bool {
+    env.storage().persistent().has(&UserKey::AllowedCreator(creator.clone()))
+}
+
+// Resolver liveness functions
+pub fn set_staleness_window(env: &Env, window: u64) {
+    env.storage().instance().set(&ConfigKey::StalenessWindow, &window);
+}
+
+pub fn get_staleness_window(env: &Env) -> u64 {
+    env.storage().instance().get(&ConfigKey::StalenessWindow)
+        .unwrap_or(DEFAULT_STALENESS_WINDOW)
+}
+
+pub fn set_claim_cutoff_buffer(env: &Env, buffer: u64) {
+    env.storage().instance().set(&ConfigKey::ClaimCutoffBuffer, &buffer);
+}
+
+pub fn get_claim_cutoff_buffer(env: &Env) -> u64 {
+    env.storage().instance().get(&ConfigKey::ClaimCutoffBuffer)
+        .unwrap_or(DEFAULT_CLAIM_CUTOFF_BUFFER)
+}
+
+pub fn set_cancel_cooldown(env: &Env, cooldown: u64) {
+    env.storage().instance().set(&ConfigKey::CancelCooldown, &cooldown);
+}
+
+pub fn get_cancel_cooldown(env: &Env) -> u64 {
+    env.storage().instance().get(&ConfigKey::CancelCooldown).unwrap_or(0)
+}
+
+/// Set the auto-confirm delay; zero disables auto-confirm
+pub fn set_auto_confirm_after(env: &Env, delay: u64) {
+    env.storage().instance().set(&ConfigKey::AutoConfirmAfter, &delay);
+}
+
+pub fn get_auto_confirm_after(env: &Env) -> u64 {
+    env.storage().instance().get(&ConfigKey::AutoConfirmAfter).unwrap_or(0)
+}
+
+// Reentrancy guard functions
+pub fn set_in_progress(env: &Env, in_progress: bool) {
+    if in_progress {
+        env.storage().instance().set(&ConfigKey::InProgress, &true);
+    } else {
+        env.storage().instance().remove(&ConfigKey::InProgress);
+    }
+}
+
+pub fn is_in_progress(env: &Env) -> bool {
+    env.storage().instance().has(&ConfigKey::InProgress)
+}
+
+// Abandoned swap functions
+pub fn set_abandonment_period(env: &Env, period: u64) {
+    env.storage().instance().set(&ConfigKey::AbandonmentPeriod, &period);
+}
+
+pub fn get_abandonment_period(env: &Env) -> u64 {
+    env.storage().instance().get(&ConfigKey::AbandonmentPeriod)
+        .unwrap_or(DEFAULT_ABANDONMENT_PERIOD)
+}
+
+pub fn set_abandonment_bounty_bps(env: &Env, bounty_bps: u32) {
+    env.storage().instance().set(&ConfigKey::AbandonmentBountyBps, &bounty_bps);
+}
+
+pub fn get_abandonment_bounty_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::AbandonmentBountyBps)
+        .unwrap_or(DEFAULT_ABANDONMENT_BOUNTY_BPS)
+}
+
+pub fn set_refund_bounty_bps(env: &Env, bounty_bps: u32) {
+    env.storage().instance().set(&ConfigKey::RefundBountyBps, &bounty_bps);
+}
+
+pub fn get_refund_bounty_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::RefundBountyBps).unwrap_or(0)
+}
+
+pub fn set_resolver_reward_bps(env: &Env, reward_bps: u32) {
+    env.storage().instance().set(&ConfigKey::ResolverRewardBps, &reward_bps);
+}
+
+pub fn get_resolver_reward_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::ResolverRewardBps).unwrap_or(0)
+}
+
+pub fn set_reward_pool(env: &Env, token: &Address, amount: i128) {
+    set_persistent(env, &TokenKey::RewardPool(token.clone()), &amount);
+}
+
+pub fn get_reward_pool(env: &Env, token: &Address) -> i128 {
+    env.storage().persistent().get(&TokenKey::RewardPool(token.clone())).unwrap_or(0)
+}
+
+pub fn set_resolver_reward(env: &Env, resolver: &Address, token: &Address, reward: i128) {
+    let key = ResolverKey::ResolverReward(resolver.clone(), token.clone());
+    if reward == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_persistent(env, &key, &reward);
+    }
+}
+
+pub fn get_resolver_reward(env: &Env, resolver: &Address, token: &Address) -> i128 {
+    env.storage().persistent()
+        .get(&ResolverKey::ResolverReward(resolver.clone(), token.clone()))
         .unwrap_or(0)
 }
 
+// Chain configuration functions
+pub fn set_chain_contract(env: &Env, chain_id: u64, eth_contract: &Address) {
+    set_persistent(env, &ChainKey::ChainConfig(chain_id), eth_contract);
+}
+
+pub fn get_chain_contract(env: &Env, chain_id: u64) -> Option<Address> {
+    env.storage().persistent().get(&ChainKey::ChainConfig(chain_id))
+}
+
+pub fn set_chain_finality_delay(env: &Env, chain_id: u64, delay: u64) {
+    set_persistent(env, &ChainKey::ChainFinalityDelay(chain_id), &delay);
+}
+
+pub fn get_chain_finality_delay(env: &Env, chain_id: u64) -> u64 {
+    env.storage().persistent().get(&ChainKey::ChainFinalityDelay(chain_id)).unwrap_or(0)
+}
+
+// Blocklist functions
+pub fn set_blocked_address(env: &Env, addr: &Address) {
+    set_persistent(env, &UserKey::BlockedAddress(addr.clone()), &true);
+}
+
+pub fn remove_blocked_address(env: &Env, addr: &Address) {
+    env.storage().persistent().remove(&UserKey::BlockedAddress(addr.clone()));
+}
+
+pub fn is_blocked_address(env: &Env, addr: &Address) -> bool {
+    env.storage().persistent().has(&UserKey::BlockedAddress(addr.clone()))
+}
+
+// Fee exemption functions
+pub fn set_fee_exempt(env: &Env, addr: &Address) {
+    set_persistent(env, &UserKey::FeeExempt(addr.clone()), &true);
+}
+
+pub fn remove_fee_exempt(env: &Env, addr: &Address) {
+    env.storage().persistent().remove(&UserKey::FeeExempt(addr.clone()));
+}
+
+pub fn is_fee_exempt(env: &Env, addr: &Address) -> bool {
+    env.storage().persistent().has(&UserKey::FeeExempt(addr.clone()))
+}
+
 // Swap functions
+
+/// Store a swap's core record; every swap write also keeps the contract
+/// instance alive
+pub fn set_swap_core(env: &Env, swap_id: &String, core: &SwapCore) {
+    set_persistent(env, &SwapKey::Swap(swap_id.clone()), core);
+    extend_instance_ttl(env);
+}
+
+pub fn get_swap_core(env: &Env, swap_id: &String) -> Option<SwapCore> {
+    env.storage().persistent().get(&SwapKey::Swap(swap_id.clone()))
+}
+
+pub fn set_swap_meta(env: &Env, swap_id: &String, meta: &SwapMeta) {
+    let key = SwapKey::SwapMeta(swap_id.clone());
+    set_persistent(env, &key, meta);
+}
+
+pub fn get_swap_meta(env: &Env, swap_id: &String) -> Option<SwapMeta> {
+    env.storage().persistent().get(&SwapKey::SwapMeta(swap_id.clone()))
+}
+
+/// Store a full swap, split into its hot core and cold metadata records
 pub fn set_swap(env: &Env, swap_id: &String, swap: &Swap) {
-    env.storage().persistent().set(&StorageKey::Swap(swap_id.clone()), swap);
+    let core = SwapCore {
+        id: swap.id.clone(),
+        sender: swap.sender.clone(),
+        recipient: swap.recipient.clone(),
+        token: swap.token.clone(),
+        amount: swap.amount,
+        hashlock: swap.hashlock.clone(),
+        hashlock_len: swap.hashlock_len,
+        timelock: swap.timelock,
+        status: swap.status.clone(),
+        claimed_at: swap.claimed_at,
+        refunded_at: swap.refunded_at,
+        preimage: swap.preimage.clone(),
+        salt: swap.salt.clone(),
+        resolver: swap.resolver.clone(),
+        claim_hook: swap.claim_hook.clone(),
+        refund_hook: swap.refund_hook.clone(),
+        refund_authority: swap.refund_authority.clone(),
+        refund_to: swap.refund_to.clone(),
+        eth_finality_deadline: swap.eth_finality_deadline,
+        public_unlock: swap.public_unlock,
+        claim_deadline: swap.claim_deadline,
+        fee_payer: swap.fee_payer.clone(),
+        prepaid_fee: swap.prepaid_fee,
+        min_net_amount: swap.min_net_amount,
+        finality_at: swap.finality_at,
+        resolver_reward: swap.resolver_reward,
+    };
+    let meta = SwapMeta {
+        index: swap.index,
+        created_at: swap.created_at,
+        eth_contract: swap.eth_contract.clone(),
+        eth_chain_id: swap.eth_chain_id,
+        counter_amount: swap.counter_amount,
+        counter_token: swap.counter_token.clone(),
+        counter_decimals: swap.counter_decimals,
+        stellar_decimals: swap.stellar_decimals,
+        evm_decimals: swap.evm_decimals,
+        memo: swap.memo.clone(),
+        order_group: swap.order_group.clone(),
+        quote_token: swap.quote_token.clone(),
+        quote_amount: swap.quote_amount,
+    };
+    set_swap_core(env, swap_id, &core);
+    set_swap_meta(env, swap_id, &meta);
 }
 
+/// Load a full swap by stitching its core and metadata records together
 pub fn get_swap(env: &Env, swap_id: &String) -> Option<Swap> {
-    env.storage().persistent().get(&StorageKey::Swap(swap_id.clone()))
+    let core = get_swap_core(env, swap_id)?;
+    let meta = get_swap_meta(env, swap_id)?;
+    Some(Swap {
+        id: core.id,
+        index: meta.index,
+        sender: core.sender,
+        recipient: core.recipient,
+        token: core.token,
+        amount: core.amount,
+        hashlock: core.hashlock,
+        hashlock_len: core.hashlock_len,
+        timelock: core.timelock,
+        status: core.status,
+        created_at: meta.created_at,
+        claimed_at: core.claimed_at,
+        refunded_at: core.refunded_at,
+        preimage: core.preimage,
+        salt: core.salt,
+        eth_contract: meta.eth_contract,
+        eth_chain_id: meta.eth_chain_id,
+        resolver: core.resolver,
+        claim_hook: core.claim_hook,
+        refund_hook: core.refund_hook,
+        counter_amount: meta.counter_amount,
+        counter_token: meta.counter_token,
+        counter_decimals: meta.counter_decimals,
+        stellar_decimals: meta.stellar_decimals,
+        evm_decimals: meta.evm_decimals,
+        memo: meta.memo,
+        order_group: meta.order_group,
+        quote_token: meta.quote_token,
+        quote_amount: meta.quote_amount,
+        refund_authority: core.refund_authority,
+        refund_to: core.refund_to,
+        eth_finality_deadline: core.eth_finality_deadline,
+        public_unlock: core.public_unlock,
+        claim_deadline: core.claim_deadline,
+        fee_payer: core.fee_payer,
+        prepaid_fee: core.prepaid_fee,
+        min_net_amount: core.min_net_amount,
+        finality_at: core.finality_at,
+        resolver_reward: core.resolver_reward,
+    })
+}
+
+pub fn set_swap_failure(env: &Env, swap_id: &String, code: &FailureCode, reason: &String) {
+    set_persistent(env, &SwapKey::SwapFailure(swap_id.clone()), &(code.clone(), reason.clone()));
+}
+
+pub fn get_swap_failure(env: &Env, swap_id: &String) -> Option<(FailureCode, String)> {
+    env.storage().persistent().get(&SwapKey::SwapFailure(swap_id.clone()))
+}
+
+pub fn set_swap_counterpart(env: &Env, swap_id: &String, counterpart_id: &BytesN<32>) {
+    set_persistent(env, &SwapKey::SwapCounterpart(swap_id.clone()), counterpart_id);
+    set_persistent(env, &SwapKey::CounterpartSwap(counterpart_id.clone()), swap_id);
+}
+
+pub fn get_swap_counterpart(env: &Env, swap_id: &String) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&SwapKey::SwapCounterpart(swap_id.clone()))
+}
+
+pub fn get_counterpart_swap(env: &Env, counterpart_id: &BytesN<32>) -> Option<String> {
+    env.storage().persistent().get(&SwapKey::CounterpartSwap(counterpart_id.clone()))
+}
+
+pub fn remove_counterpart_swap(env: &Env, counterpart_id: &BytesN<32>) {
+    env.storage().persistent().remove(&SwapKey::CounterpartSwap(counterpart_id.clone()));
+}
+
+pub fn set_idempotent_swap(env: &Env, sender: &Address, hashlock: &BytesN<32>, swap_id: &String) {
+    set_persistent(env, &SwapKey::IdempotentSwap(sender.clone(), hashlock.clone()), swap_id);
+}
+
+pub fn get_idempotent_swap(env: &Env, sender: &Address, hashlock: &BytesN<32>) -> Option<String> {
+    env.storage().persistent().get(&SwapKey::IdempotentSwap(sender.clone(), hashlock.clone()))
+}
+
+pub fn set_eth_attestation(env: &Env, swap_id: &String, attestation: &EthAttestation) {
+    set_persistent(env, &SwapKey::EthAttestation(swap_id.clone()), attestation);
+}
+
+pub fn get_eth_attestation(env: &Env, swap_id: &String) -> Option<EthAttestation> {
+    env.storage().persistent().get(&SwapKey::EthAttestation(swap_id.clone()))
+}
+
+pub fn set_revealed_preimage(env: &Env, swap_id: &String, preimage: &BytesN<32>) {
+    set_persistent(env, &SwapKey::RevealedPreimage(swap_id.clone()), preimage);
+}
+
+pub fn get_revealed_preimage(env: &Env, swap_id: &String) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&SwapKey::RevealedPreimage(swap_id.clone()))
+}
+
+pub fn set_claim_preauthorized(env: &Env, swap_id: &String) {
+    set_persistent(env, &SwapKey::ClaimPreauthorized(swap_id.clone()), &true);
+}
+
+pub fn is_claim_preauthorized(env: &Env, swap_id: &String) -> bool {
+    env.storage().persistent().has(&SwapKey::ClaimPreauthorized(swap_id.clone()))
 }
 
 // Resolver functions
 pub fn set_resolver(env: &Env, resolver: &Address, info: &ResolverInfo) {
-    env.storage().persistent().set(&StorageKey::Resolver(resolver.clone()), info);
+    let key = ResolverKey::Resolver(resolver.clone());
+    set_persistent(env, &key, info);
 }
 
 pub fn get_resolver(env: &Env, resolver: &Address) -> Option<ResolverInfo> {
-    env.storage().persistent().get(&StorageKey::Resolver(resolver.clone()))
+    env.storage().persistent().get(&ResolverKey::Resolver(resolver.clone()))
+}
+
+pub fn remove_resolver(env: &Env, resolver: &Address) {
+    env.storage().persistent().remove(&ResolverKey::Resolver(resolver.clone()));
+}
+
+pub fn set_resolver_count(env: &Env, count: u32) {
+    env.storage().instance().set(&ConfigKey::ResolverCount, &count);
+}
+
+pub fn get_resolver_count(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::ResolverCount).unwrap_or(0)
+}
+
+pub fn set_max_resolvers(env: &Env, max_resolvers: u32) {
+    env.storage().instance().set(&ConfigKey::MaxResolvers, &max_resolvers);
+}
+
+pub fn get_max_resolvers(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::MaxResolvers).unwrap_or(0)
+}
+
+pub fn set_collateral_ratio_bps(env: &Env, ratio_bps: u32) {
+    env.storage().instance().set(&ConfigKey::CollateralRatioBps, &ratio_bps);
+}
+
+pub fn get_collateral_ratio_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&ConfigKey::CollateralRatioBps).unwrap_or(0)
+}
+
+pub fn get_resolver_leaderboard(env: &Env) -> Vec<Address> {
+    env.storage().persistent().get(&ResolverKey::ResolverLeaderboard).unwrap_or(Vec::new(env))
+}
+
+/// Move a resolver to its position on the volume leaderboard, dropping the
+/// lowest entry once the board holds `MAX_LEADERBOARD_SIZE` resolvers
+pub fn update_resolver_leaderboard(env: &Env, resolver: &ResolverInfo) {
+    let mut leaderboard = get_resolver_leaderboard(env);
+    if let Some(index) = leaderboard.first_index_of(&resolver.resolver) {
+        leaderboard.remove(index);
+    }
+
+    // Insert after every entry with at least as much volume
+    let position = leaderboard
+        .iter()
+        .position(|other| {
+            get_resolver(env, &other)
+                .is_none_or(|info| info.total_volume < resolver.total_volume)
+        })
+        .map_or(leaderboard.len(), |index| index as u32);
+    if position >= MAX_LEADERBOARD_SIZE {
+        return;
+    }
+    leaderboard.insert(position, resolver.resolver.clone());
+    if leaderboard.len() > MAX_LEADERBOARD_SIZE {
+        leaderboard.pop_back();
+    }
+
+    set_persistent(env, &ResolverKey::ResolverLeaderboard, &leaderboard);
+}
+
+pub fn remove_from_resolver_leaderboard(env: &Env, resolver: &Address) {
+    let mut leaderboard = get_resolver_leaderboard(env);
+    if let Some(index) = leaderboard.first_index_of(resolver) {
+        leaderboard.remove(index);
+        set_persistent(env, &ResolverKey::ResolverLeaderboard, &leaderboard);
+    }
+}
+
+// Signed order functions
+pub fn set_maker_key(env: &Env, maker: &Address, public_key: &BytesN<32>) {
+    set_persistent(env, &UserKey::MakerKey(maker.clone()), public_key);
+}
+
+pub fn get_maker_key(env: &Env, maker: &Address) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&UserKey::MakerKey(maker.clone()))
+}
+
+pub fn set_nonce_used(env: &Env, maker: &Address, nonce: u64) {
+    set_persistent(env, &UserKey::UsedNonce(maker.clone(), nonce), &true);
+}
+
+pub fn is_nonce_used(env: &Env, maker: &Address, nonce: u64) -> bool {
+    env.storage().persistent().has(&UserKey::UsedNonce(maker.clone(), nonce))
+}
+
+// Swap template functions
+pub fn set_template_counter(env: &Env, counter: u64) {
+    env.storage().instance().set(&ConfigKey::TemplateCounter, &counter);
+}
+
+pub fn get_template_counter(env: &Env) -> u64 {
+    env.storage().instance().get(&ConfigKey::TemplateCounter)
+        .unwrap_or(0)
+}
+
+pub fn set_template(env: &Env, template_id: u64, info: &TemplateInfo) {
+    set_persistent(env, &SwapKey::Template(template_id), info);
+}
+
+pub fn get_template(env: &Env, template_id: u64) -> Option<TemplateInfo> {
+    env.storage().persistent().get(&SwapKey::Template(template_id))
+}
+
+// Claimable balance functions
+pub fn set_claimable_balance(env: &Env, recipient: &Address, token: &Address, amount: i128) {
+    let key = UserKey::ClaimableBalance(recipient.clone(), token.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_persistent(env, &key, &amount);
+    }
+}
+
+pub fn get_claimable_balance(env: &Env, recipient: &Address, token: &Address) -> i128 {
+    let key = UserKey::ClaimableBalance(recipient.clone(), token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn add_claimable_balance(env: &Env, recipient: &Address, token: &Address, amount: i128) {
+    let balance = get_claimable_balance(env, recipient, token);
+    set_claimable_balance(env, recipient, token, balance + amount);
+}
+
+// Locked balance functions
+pub fn get_locked_balance(env: &Env, token: &Address) -> i128 {
+    env.storage().persistent().get(&TokenKey::LockedBalance(token.clone())).unwrap_or(0)
+}
+
+pub fn add_locked_balance(env: &Env, token: &Address, amount: i128) {
+    let balance = get_locked_balance(env, token);
+    set_persistent(env, &TokenKey::LockedBalance(token.clone()), &(balance + amount));
+}
+
+pub fn release_locked_balance(env: &Env, token: &Address, amount: i128) {
+    let balance = get_locked_balance(env, token);
+    set_persistent(env, &TokenKey::LockedBalance(token.clone()), &(balance - amount));
+}
+
+pub fn get_total_locked(env: &Env, token: &Address) -> i128 {
+    env.storage().persistent().get(&TokenKey::TotalLocked(token.clone())).unwrap_or(0)
+}
+
+pub fn add_total_locked(env: &Env, token: &Address, amount: i128) {
+    let total = get_total_locked(env, token);
+    set_persistent(env, &TokenKey::TotalLocked(token.clone()), &(total + amount));
+}
+
+pub fn release_total_locked(env: &Env, token: &Address, amount: i128) {
+    let total = get_total_locked(env, token);
+    set_persistent(env, &TokenKey::TotalLocked(token.clone()), &(total - amount));
 }
 
 // User swap tracking
 pub fn add_user_swap(env: &Env, user: &Address, swap_id: &String) {
-    let key = StorageKey::UserSwaps(user.clone());
+    let key = UserKey::UserSwaps(user.clone());
     let mut swaps: Vec<String> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
     swaps.push_back(swap_id.clone());
-    env.storage().persistent().set(&key, &swaps);
+    set_persistent(env, &key, &swaps);
 }
 
 pub fn get_user_swap_ids(env: &Env, user: &Address) -> Vec<String> {
-    let key = StorageKey::UserSwaps(user.clone());
+    let key = UserKey::UserSwaps(user.clone());
     env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
-}
\ No newline at end of file
+}
+
+pub fn set_resolver_nonce(env: &Env, resolver: &Address, nonce: u64) {
+    set_persistent(env, &ResolverKey::ResolverNonce(resolver.clone()), &nonce);
+}
+
+pub fn get_resolver_nonce(env: &Env, resolver: &Address) -> u64 {
+    env.storage().persistent().get(&ResolverKey::ResolverNonce(resolver.clone())).unwrap_or(0)
+}
+
+pub fn add_resolver_swap(env: &Env, resolver: &Address, swap_id: &String) {
+    let mut swaps = get_resolver_swap_ids(env, resolver);
+    swaps.push_back(swap_id.clone());
+    set_persistent(env, &ResolverKey::ResolverSwaps(resolver.clone()), &swaps);
+}
+
+pub fn remove_resolver_swap(env: &Env, resolver: &Address, swap_id: &String) {
+    let key = ResolverKey::ResolverSwaps(resolver.clone());
+    let mut swaps = get_resolver_swap_ids(env, resolver);
+    if let Some(index) = swaps.first_index_of(swap_id) {
+        swaps.remove(index);
+    }
+    if swaps.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_persistent(env, &key, &swaps);
+    }
+}
+
+pub fn get_resolver_swap_ids(env: &Env, resolver: &Address) -> Vec<String> {
+    env.storage().persistent().get(&ResolverKey::ResolverSwaps(resolver.clone())).unwrap_or(Vec::new(env))
+}
+
+pub fn add_group_swap(env: &Env, order_group: &BytesN<32>, swap_id: &String) {
+    let mut swaps = get_group_swap_ids(env, order_group);
+    swaps.push_back(swap_id.clone());
+    set_persistent(env, &SwapKey::GroupSwaps(order_group.clone()), &swaps);
+}
+
+pub fn get_group_swap_ids(env: &Env, order_group: &BytesN<32>) -> Vec<String> {
+    env.storage().persistent().get(&SwapKey::GroupSwaps(order_group.clone())).unwrap_or(Vec::new(env))
+}
+
+pub fn set_swap_watchers(env: &Env, swap_id: &String, watchers: &Vec<Address>) {
+    let key = SwapKey::SwapWatchers(swap_id.clone());
+    if watchers.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_persistent(env, &key, watchers);
+    }
+}
+
+pub fn get_swap_watchers(env: &Env, swap_id: &String) -> Vec<Address> {
+    env.storage().persistent().get(&SwapKey::SwapWatchers(swap_id.clone())).unwrap_or(Vec::new(env))
+}
+
+pub fn set_swap_by_index(env: &Env, index: u64, swap_id: &String) {
+    set_persistent(env, &SwapKey::SwapByIndex(index), swap_id);
+}
+
+pub fn get_swap_by_index(env: &Env, index: u64) -> Option<String> {
+    env.storage().persistent().get(&SwapKey::SwapByIndex(index))
+}
+
+pub fn set_created_at_by_index(env: &Env, index: u64, created_at: u64) {
+    set_persistent(env, &SwapKey::CreatedAtByIndex(index), &created_at);
+}
+
+pub fn get_created_at_by_index(env: &Env, index: u64) -> Option<u64> {
+    env.storage().persistent().get(&SwapKey::CreatedAtByIndex(index))
+}
@@ -17,4 +17,23 @@ fn test_error_codes() {
     assert_eq!(HTLCError::InvalidAmount as u32, 1000);
     assert_eq!(HTLCError::SwapNotFound as u32, 2000);
     assert_eq!(HTLCError::Unauthorized as u32, 4000);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_swap_status_transitions() {
+    use SwapStatus::*;
+    let statuses = [Pending, Active, Claimed, Refunded, Failed];
+    // Rows are the current status, columns the next, in `statuses` order
+    let allowed = [
+        [false, true, true, true, true],      // Pending
+        [false, false, true, true, true],     // Active
+        [false, false, false, false, false],  // Claimed
+        [false, false, false, false, false],  // Refunded
+        [false, false, false, true, false],   // Failed
+    ];
+    for (from, row) in statuses.iter().zip(allowed) {
+        for (to, expected) in statuses.iter().zip(row) {
+            assert_eq!(is_valid_transition(from, to), expected, "{:?} -> {:?}", from, to);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(test)]
+
+use crate::test_fixtures::{self, Fixture, SWAP_DURATION};
+use soroban_sdk::{testutils::Ledger, Env};
+
+/// CPU instruction ceiling for a single swap entrypoint
+///
+/// Tests run the contract natively, so this covers host work only. It sits
+/// about 25% above `claim_swap`, the costliest at ~811k instructions.
+const MAX_CPU_INSTRUCTIONS: u64 = 1_000_000;
+
+/// Memory ceiling for a single swap entrypoint, in bytes
+///
+/// About 50% above `claim_swap`, the hungriest at ~134 KB.
+const MAX_MEMORY_BYTES: u64 = 200_000;
+
+fn setup() -> Fixture {
+    let s = test_fixtures::setup();
+    s.mint(&s.sender, 10_000_000);
+    s
+}
+
+/// Fail if the last invocation exceeded the budget thresholds
+fn assert_within_budget(env: &Env, entrypoint: &str) {
+    let budget = env.cost_estimate().budget();
+    let cpu = budget.cpu_instruction_cost();
+    let memory = budget.memory_bytes_cost();
+    assert!(
+        cpu <= MAX_CPU_INSTRUCTIONS,
+        "{} used {} CPU instructions, limit is {}", entrypoint, cpu, MAX_CPU_INSTRUCTIONS
+    );
+    assert!(
+        memory <= MAX_MEMORY_BYTES,
+        "{} used {} bytes of memory, limit is {}", entrypoint, memory, MAX_MEMORY_BYTES
+    );
+}
+
+#[test]
+fn test_create_swap_budget() {
+    let s = setup();
+
+    s.create_swap(&s.swap());
+    assert_within_budget(&s.env, "create_swap");
+}
+
+#[test]
+fn test_claim_swap_budget() {
+    let s = setup();
+    let swap_id = s.create_swap(&s.swap());
+
+    s.client.claim_swap(&swap_id, &s.preimage(), &None);
+    assert_within_budget(&s.env, "claim_swap");
+}
+
+#[test]
+fn test_refund_swap_budget() {
+    let s = setup();
+    let swap_id = s.create_swap(&s.swap());
+    s.env.ledger().with_mut(|li| li.timestamp = SWAP_DURATION);
+
+    s.client.refund_swap(&swap_id, &None);
+    assert_within_budget(&s.env, "refund_swap");
+}
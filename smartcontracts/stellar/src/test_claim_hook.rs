@@ -0,0 +1,160 @@
+#![cfg(test)]
+
+use super::*;
+use crate::test_fixtures::{self, Fixture, SwapTerms, SWAP_DURATION};
+use panicking_hook::PanickingHook;
+use panicking_refund_hook::PanickingRefundHook;
+use recording_hook::{RecordingHook, RecordingHookClient};
+use recording_refund_hook::{RecordingRefundHook, RecordingRefundHookClient};
+use soroban_sdk::{
+    testutils::{Events, Ledger},
+    Address, IntoVal, Val,
+};
+
+/// Hook that records the arguments of the last notification
+mod recording_hook {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String};
+
+    #[contract]
+    pub struct RecordingHook;
+
+    #[contractimpl]
+    impl RecordingHook {
+        pub fn on_htlc_claimed(env: Env, swap_id: String, recipient: Address, amount: i128) {
+            env.storage().instance().set(&symbol_short!("last"), &(swap_id, recipient, amount));
+        }
+
+        pub fn last_call(env: Env) -> Option<(String, Address, i128)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+/// Hook that always fails
+mod panicking_hook {
+    use soroban_sdk::{contract, contractimpl, Address, Env, String};
+
+    #[contract]
+    pub struct PanickingHook;
+
+    #[contractimpl]
+    impl PanickingHook {
+        pub fn on_htlc_claimed(_env: Env, _swap_id: String, _recipient: Address, _amount: i128) {
+            panic!("hook failure");
+        }
+    }
+}
+
+/// Refund hook that records the arguments of the last notification
+mod recording_refund_hook {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env, String};
+
+    #[contract]
+    pub struct RecordingRefundHook;
+
+    #[contractimpl]
+    impl RecordingRefundHook {
+        pub fn on_swap_refunded(env: Env, swap_id: String, amount: i128) {
+            env.storage().instance().set(&symbol_short!("last"), &(swap_id, amount));
+        }
+
+        pub fn last_call(env: Env) -> Option<(String, i128)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+/// Refund hook that always fails
+mod panicking_refund_hook {
+    use soroban_sdk::{contract, contractimpl, Env, String};
+
+    #[contract]
+    pub struct PanickingRefundHook;
+
+    #[contractimpl]
+    impl PanickingRefundHook {
+        pub fn on_swap_refunded(_env: Env, _swap_id: String, _amount: i128) {
+            panic!("hook failure");
+        }
+    }
+}
+
+fn create_hooked_swap(s: &Fixture, claim_hook: Option<Address>, refund_hook: Option<Address>) -> String {
+    s.mint(&s.sender, 1_000_000);
+    s.create_swap(&SwapTerms {
+        options: SwapOptions { claim_hook, refund_hook, ..SwapOptions::default() },
+        ..s.swap()
+    })
+}
+
+#[test]
+fn test_claim_hook_receives_claim_details() {
+    let s = test_fixtures::setup();
+    let hook_id = s.env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&s.env, &hook_id);
+    let swap_id = create_hooked_swap(&s, Some(hook_id.clone()), None);
+
+    // Hook is not called before the claim
+    assert_eq!(hook.last_call(), None);
+
+    s.client.claim_swap(&swap_id, &s.preimage(), &None);
+
+    // Hook sees the payout net of the 0.3% protocol fee
+    assert_eq!(hook.last_call(), Some((swap_id, s.recipient.clone(), 997_000i128)));
+}
+
+#[test]
+fn test_panicking_claim_hook_does_not_undo_claim() {
+    let s = test_fixtures::setup();
+    let hook_id = s.env.register(PanickingHook, ());
+    let swap_id = create_hooked_swap(&s, Some(hook_id.clone()), None);
+
+    s.client.claim_swap(&swap_id, &s.preimage(), &None);
+
+    // Claim and transfer stand despite the hook failure
+    let swap = s.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Claimed);
+    assert_eq!(s.token_client().balance(&s.recipient), 997_000);
+}
+
+
+#[test]
+fn test_refund_hook_receives_refund_details() {
+    let s = test_fixtures::setup();
+    let hook_id = s.env.register(RecordingRefundHook, ());
+    let hook = RecordingRefundHookClient::new(&s.env, &hook_id);
+    let swap_id = create_hooked_swap(&s, None, Some(hook_id));
+    assert_eq!(s.client.get_swap_details(&swap_id).unwrap().refund_hook, Some(hook.address.clone()));
+
+    // Hook is not called before the refund
+    assert_eq!(hook.last_call(), None);
+
+    s.env.ledger().with_mut(|li| li.timestamp = SWAP_DURATION);
+    s.client.refund_swap(&swap_id, &None);
+
+    // Hook sees the full locked amount returned
+    assert_eq!(hook.last_call(), Some((swap_id, 1_000_000i128)));
+}
+
+#[test]
+fn test_panicking_refund_hook_does_not_undo_refund() {
+    let s = test_fixtures::setup();
+    let env = &s.env;
+    let hook_id = env.register(PanickingRefundHook, ());
+    let swap_id = create_hooked_swap(&s, None, Some(hook_id.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp = SWAP_DURATION);
+    s.client.refund_swap(&swap_id, &None);
+
+    // The failure is reported without reverting the refund
+    let expected_topics: Vec<Val> = (String::from_str(env, "refund_hook_failed"), EVENT_SCHEMA_VERSION).into_val(env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &s.contract_id && topics == &expected_topics)
+        .expect("refund hook failure not reported");
+    let data: (String, Address) = data.into_val(env);
+    assert_eq!(data, (swap_id.clone(), hook_id));
+
+    let swap = s.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Refunded);
+    assert_eq!(s.token_client().balance(&s.sender), 1_000_000);
+}
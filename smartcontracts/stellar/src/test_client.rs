@@ -0,0 +1,121 @@
+#![cfg(all(test, feature = "client-utils"))]
+
+use super::*;
+use crate::client;
+use crate::test_fixtures::{self, Fixture, SwapTerms};
+use soroban_sdk::{testutils::Ledger, Bytes, BytesN};
+
+fn setup() -> Fixture {
+    let s = test_fixtures::setup();
+    s.env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+        li.sequence_number = 42;
+    });
+    s.mint(&s.sender, 10_000_000_000);
+    s
+}
+
+#[test]
+fn test_derive_swap_id_matches_contract() {
+    let s = setup();
+    let hashlock = [7u8; 32];
+
+    let first = s.create_swap(&SwapTerms { hashlock: BytesN::from_array(&s.env, &hashlock), ..s.swap() });
+    let expected = client::derive_swap_id(1, 42, 1_000, 1_000_000, &hashlock);
+    assert_eq!(first, String::from_str(&s.env, &expected));
+
+    // The counter keeps identical terms in the same ledger distinct
+    let second = s.create_swap(&SwapTerms { hashlock: BytesN::from_array(&s.env, &hashlock), ..s.swap() });
+    let expected = client::derive_swap_id(2, 42, 1_000, 1_000_000, &hashlock);
+    assert_eq!(second, String::from_str(&s.env, &expected));
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_hash_preimage_matches_contract() {
+    let s = setup();
+    let secret = [42u8; 32];
+    let salt = [9u8; 32];
+
+    let hashlock = client::hash_preimage_sha256(&secret);
+    assert_eq!(
+        hashlock,
+        s.env.crypto().sha256(&Bytes::from_array(&s.env, &secret)).to_array()
+    );
+
+    let mut salted = [0u8; 64];
+    salted[..32].copy_from_slice(&secret);
+    salted[32..].copy_from_slice(&salt);
+    let salted_hashlock = client::hash_preimage_sha256(&salted);
+    let swap_id = s.create_swap(&SwapTerms { hashlock: BytesN::from_array(&s.env, &salted_hashlock), ..s.swap() });
+    assert!(s.client.verify_preimage(
+        &swap_id,
+        &BytesN::from_array(&s.env, &secret),
+        &Some(BytesN::from_array(&s.env, &salt)),
+    ));
+
+    assert_eq!(
+        client::hash_preimage_keccak(&secret),
+        s.env.crypto().keccak256(&Bytes::from_array(&s.env, &secret)).to_array()
+    );
+}
+
+#[test]
+fn test_compute_fee_matches_contract() {
+    let s = setup();
+    let preimage = [42u8; 32];
+    let hashlock = BytesN::from_array(&s.env, &client::hash_preimage_sha256(&preimage));
+    let token_client = s.token_client();
+
+    let mut collected = 0;
+    for amount in [1i128, 9_999, 10_001, 1_000_000, 123_456_789] {
+        let swap_id = s.create_swap(&SwapTerms { hashlock: hashlock.clone(), amount, ..s.swap() });
+        s.client.claim_swap(&swap_id, &BytesN::from_array(&s.env, &preimage), &None);
+        collected += client::compute_fee(amount, 30);
+        assert_eq!(token_client.balance(&s.fee_recipient), collected);
+    }
+}
+
+#[test]
+fn test_timelock_helpers_match_contract() {
+    let s = setup();
+    let hashlock = [7u8; 32];
+    let now = s.env.ledger().timestamp();
+
+    // Timelocks just inside and outside the allowed window
+    for (timelock, valid) in [
+        (now + MIN_TIMELOCK_DURATION, false),
+        (now + MIN_TIMELOCK_DURATION + 1, true),
+        (now + MAX_TIMELOCK_DURATION, true),
+        (now + MAX_TIMELOCK_DURATION + 1, false),
+    ] {
+        assert_eq!(client::is_valid_timelock(now, timelock), valid);
+        let result = s.try_create_swap(&SwapTerms {
+            hashlock: BytesN::from_array(&s.env, &hashlock),
+            timelock,
+            ..s.swap()
+        });
+        assert_eq!(result.is_ok(), valid);
+    }
+
+    // The last claimable second is the cutoff, refunds open at the timelock
+    let timelock = 8_200;
+    let cutoff = client::claim_cutoff(timelock, DEFAULT_CLAIM_CUTOFF_BUFFER);
+    assert!(client::is_claimable_at(cutoff, timelock, DEFAULT_CLAIM_CUTOFF_BUFFER, None));
+    assert!(!client::is_claimable_at(cutoff + 1, timelock, DEFAULT_CLAIM_CUTOFF_BUFFER, None));
+    assert!(!client::is_refundable_at(timelock - 1, timelock, None));
+    assert!(client::is_refundable_at(timelock, timelock, None));
+
+    let preimage = [42u8; 32];
+    let claim_hashlock = BytesN::from_array(&s.env, &client::hash_preimage_sha256(&preimage));
+    let late_claim = s.create_swap(&SwapTerms { hashlock: claim_hashlock.clone(), ..s.swap() });
+    let on_time_claim = s.create_swap(&SwapTerms { hashlock: claim_hashlock.clone(), ..s.swap() });
+
+    s.env.ledger().with_mut(|li| li.timestamp = cutoff);
+    assert!(s.client.try_claim_swap(&on_time_claim, &BytesN::from_array(&s.env, &preimage), &None).is_ok());
+    s.env.ledger().with_mut(|li| li.timestamp = cutoff + 1);
+    assert_eq!(
+        s.client.try_claim_swap(&late_claim, &BytesN::from_array(&s.env, &preimage), &None),
+        Err(Ok(HTLCError::ClaimWindowClosed.into()))
+    );
+}
@@ -1,18 +1,8 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Env, Address, BytesN, Bytes};
-
-fn create_test_env() -> (Env, Address, Address, Address) {
-    let env = Env::default();
-    env.mock_all_auths();
-    
-    let admin = Address::generate(&env);
-    let fee_recipient = Address::generate(&env);
-    let token = Address::generate(&env);
-    
-    (env, admin, fee_recipient, token)
-}
+use crate::test_fixtures::{create_test_env, fund_account};
+use soroban_sdk::{testutils::{storage::{Instance as _, Persistent as _}, Address as _, Events, IssuerFlags, Ledger, MockAuth, MockAuthInvoke}, symbol_short, token, Env, Address, BytesN, Bytes, IntoVal, TryFromVal, Val};
 
 #[test]
 fn test_contract_initialization() {
@@ -21,17 +11,172 @@ fn test_contract_initialization() {
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
     // Verify initialization
     let stats = client.get_contract_stats();
-    assert_eq!(stats.admin, admin);
-    assert_eq!(stats.fee_recipient, fee_recipient);
+    assert_eq!(stats.admin, Some(admin));
+    assert!(!stats.admin_renounced);
+    assert_eq!(stats.fee_recipient, Some(fee_recipient));
     assert_eq!(stats.protocol_fee_bps, 30);
     assert_eq!(stats.total_swaps_created, 0);
     assert_eq!(stats.total_swaps_completed, 0);
 }
 
+#[test]
+fn test_initialization_rejects_contract_address() {
+    let (env, admin, fee_recipient, _) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // The contract can't be its own admin
+    let result = client.try_initialize(&contract_id, &fee_recipient, &30, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAdmin.into())));
+    
+    // Nor its own fee recipient
+    let result = client.try_initialize(&admin, &contract_id, &30, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidRecipient.into())));
+    
+    // Rejected attempts leave the contract uninitialized
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    assert_eq!(client.get_contract_stats().admin, Some(admin));
+    assert_eq!(client.get_initialization_deadline(), None);
+}
+
+#[test]
+fn test_views_before_initialization() {
+    let (env, _, _, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    let swap_id = String::from_str(&env, "missing");
+    let user = Address::generate(&env);
+    
+    // Stats and counters read as empty rather than failing
+    let stats = client.get_contract_stats();
+    assert_eq!(stats.admin, None);
+    assert_eq!(stats.fee_recipient, None);
+    assert!(!stats.admin_renounced);
+    assert_eq!(stats.total_swaps_created, 0);
+    assert_eq!(stats.active_swaps, 0);
+    assert_eq!(client.get_status_counts().pending, 0);
+    assert_eq!(client.get_limits().max_protocol_fee_bps, MAX_PROTOCOL_FEE_BPS);
+    assert!(client.health_check(&soroban_sdk::vec![&env, token.clone()]).healthy);
+    assert_eq!(client.get_initialization_deadline(), None);
+    
+    // Missing configuration is reported, not raised
+    assert_eq!(client.check_invariants(), soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "admin_set"),
+        String::from_str(&env, "fee_recipient_set"),
+    ]);
+    
+    // Lookups come back empty
+    assert_eq!(client.get_swap_details(&swap_id), None);
+    assert_eq!(client.get_swap_view(&swap_id), None);
+    assert!(!client.swap_exists(&swap_id));
+    assert_eq!(client.get_all_swaps(&0, &10).len(), 0);
+    assert_eq!(client.get_user_swaps(&user).len(), 0);
+    assert_eq!(client.get_resolver_info(&user), None);
+    assert_eq!(client.get_top_resolvers(&10).len(), 0);
+    assert_eq!(client.get_total_locked(&token), 0);
+    assert_eq!(client.get_fee_policy(), None);
+    
+    // Views that need configuration or a swap fail with a contract error
+    assert_eq!(client.try_get_fee_splits(), Err(Ok(HTLCError::NotInitialized.into())));
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let result = client.try_diagnose_claim(&swap_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotFound.into())));
+    assert_eq!(client.try_update_protocol_fee(&50), Err(Ok(HTLCError::NotInitialized.into())));
+}
+
+#[test]
+fn test_unconfirmed_initialization_locks_swaps() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    let sender = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    
+    let create = |client: &StellarHTLCClient, hashlock: u8| client.try_create_swap(
+        &sender,
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[hashlock; 32]),
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &Address::generate(&env),
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Initialization with a confirmation challenge announces its deadline
+    let deadline = env.ledger().sequence() + 100;
+    client.initialize(&admin, &fee_recipient, &30, &Some(100));
+    let expected_topics: Vec<Val> = (String::from_str(&env, "initialization_pending"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+        .expect("initialization_pending event not emitted");
+    let data: (Address, u32) = data.into_val(&env);
+    assert_eq!(data, (admin.clone(), deadline));
+    assert_eq!(client.get_initialization_deadline(), Some(deadline));
+    
+    // No swaps until the admin confirms
+    assert_eq!(create(&client, 1).err(), Some(Ok(HTLCError::InitializationUnconfirmed.into())));
+    
+    env.ledger().with_mut(|li| li.sequence_number = deadline);
+    client.confirm_initialization();
+    let expected_topics: Vec<Val> = (String::from_str(&env, "initialization_confirmed"), EVENT_SCHEMA_VERSION).into_val(&env);
+    assert!(env.events().all().iter()
+        .any(|(contract, topics, _)| contract == contract_id && topics == expected_topics));
+    assert_eq!(client.get_initialization_deadline(), None);
+    assert!(create(&client, 1).is_ok());
+    
+    // Confirming twice is rejected
+    let result = client.try_confirm_initialization();
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyInitialized.into())));
+    
+    // A contract left unconfirmed past the deadline stays locked
+    let locked_id = env.register(StellarHTLC, ());
+    let locked = StellarHTLCClient::new(&env, &locked_id);
+    locked.initialize(&admin, &fee_recipient, &30, &Some(10));
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    let result = locked.try_confirm_initialization();
+    assert_eq!(result, Err(Ok(HTLCError::InitializationUnconfirmed.into())));
+    assert_eq!(create(&locked, 2).err(), Some(Ok(HTLCError::InitializationUnconfirmed.into())));
+}
+
+#[test]
+fn test_get_limits() {
+    let (env, admin, fee_recipient, _) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let mut expected = Limits {
+        min_timelock_duration: 3_600,
+        max_timelock_duration: 604_800,
+        max_protocol_fee_bps: 500,
+        min_swap_amount: 1,
+        max_open_swaps_per_user: 0,
+        claim_cutoff_buffer: DEFAULT_CLAIM_CUTOFF_BUFFER,
+        abandonment_period: DEFAULT_ABANDONMENT_PERIOD,
+    };
+    assert_eq!(client.get_limits(), expected);
+    
+    // Admin changes show up immediately
+    client.set_claim_cutoff_buffer(&600);
+    client.set_abandonment_policy(&86_400u64, &50);
+    expected.claim_cutoff_buffer = 600;
+    expected.abandonment_period = 86_400;
+    assert_eq!(client.get_limits(), expected);
+    
+    // The fee setter is bounded by the advertised maximum
+    let limits = client.get_limits();
+    client.update_protocol_fee(&limits.max_protocol_fee_bps);
+    let result = client.try_update_protocol_fee(&(limits.max_protocol_fee_bps + 1));
+    assert_eq!(result, Err(Ok(HTLCError::InvalidFee.into())));
+}
+
 #[test]
 fn test_create_swap() {
     let (env, admin, fee_recipient, token) = create_test_env();
@@ -39,12 +184,13 @@ fn test_create_swap() {
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
     // Create test data - note: in Soroban test env timestamp starts at 0
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
     let hashlock = BytesN::from_array(&env, &[1u8; 32]);
     let timelock = 7200u64; // 2 hours from epoch (well above minimum)
     let amount = 1_000_000i128;
@@ -59,7 +205,7 @@ fn test_create_swap() {
         &amount,
         &eth_contract,
         &11155111u64, // Sepolia chain ID
-        &None,
+        &SwapOptions::default(),
     );
     
     // Verify swap was created
@@ -73,12 +219,63 @@ fn test_create_swap() {
     assert_eq!(swap.timelock, timelock);
     assert_eq!(swap.status, SwapStatus::Pending);
     
+    // Funds are locked in the contract
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&sender), 0);
+    assert_eq!(token_client.balance(&contract_id), amount);
+    
     // Check stats
     let stats = client.get_contract_stats();
     assert_eq!(stats.total_swaps_created, 1);
     assert_eq!(stats.total_swaps_completed, 0);
 }
 
+#[test]
+fn test_create_swap_with_duration() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let now = 1_700_000_000u64;
+    env.ledger().with_mut(|li| li.timestamp = now);
+    let sender = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    
+    let create = |duration: u64, hashlock: u8| client.try_create_swap_with_duration(
+        &sender,
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[hashlock; 32]),
+        &duration,
+        &token,
+        &1_000_000i128,
+        &Address::generate(&env),
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Durations at or beyond the bounds are rejected
+    let invalid = Some(Ok(HTLCError::InvalidTimelock.into()));
+    assert_eq!(create(MIN_TIMELOCK_DURATION, 1).err(), invalid);
+    assert_eq!(create(MAX_TIMELOCK_DURATION + 1, 1).err(), invalid);
+    assert_eq!(create(u64::MAX, 1).err(), invalid);
+    
+    // The shortest valid duration resolves against ledger time
+    let swap_id = create(MIN_TIMELOCK_DURATION + 1, 1).unwrap().unwrap();
+    let created: Vec<Val> = (String::from_str(&env, "swap_created"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &created)
+        .expect("creation event not emitted");
+    let data: Vec<Val> = data.into_val(&env);
+    let timelock: u64 = data.get(4).unwrap().into_val(&env);
+    assert_eq!(timelock, now + MIN_TIMELOCK_DURATION + 1);
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().timelock, timelock);
+    
+    // So does the longest
+    let swap_id = create(MAX_TIMELOCK_DURATION, 2).unwrap().unwrap();
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().timelock, now + MAX_TIMELOCK_DURATION);
+}
+
 #[test] 
 fn test_claim_swap() {
     let (env, admin, fee_recipient, token) = create_test_env();
@@ -86,12 +283,13 @@ fn test_claim_swap() {
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
     // Create test data
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
     
     // Create a preimage and its hash
     let preimage = BytesN::from_array(&env, &[42u8; 32]);
@@ -111,11 +309,11 @@ fn test_claim_swap() {
         &amount,
         &eth_contract,
         &11155111u64,
-        &None,
+        &SwapOptions::default(),
     );
     
     // Claim swap with correct preimage
-    client.claim_swap(&swap_id, &preimage);
+    client.claim_swap(&swap_id, &preimage, &None);
     
     // Verify claim
     let swap = client.get_swap_details(&swap_id).unwrap();
@@ -123,11 +321,111 @@ fn test_claim_swap() {
     assert_eq!(swap.preimage.unwrap(), preimage);
     assert!(swap.claimed_at.is_some());
     
+    // Funds were released to the recipient, net of the 0.3% protocol fee
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&recipient), amount - 3_000);
+    assert_eq!(token_client.balance(&fee_recipient), 3_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    
     // Check stats
     let stats = client.get_contract_stats();
     assert_eq!(stats.total_swaps_completed, 1);
 }
 
+#[test]
+fn test_claim_requires_funded_contract() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_003_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    // The sender prepays the fee, so 1,003,000 is locked
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &Address::generate(&env),
+        &11155111u64,
+        &SwapOptions {
+            fee_payer: FeePayer::Sender,
+            ..SwapOptions::default()
+        },
+    );
+    
+    // Simulate an accounting bug leaving the contract one unit short
+    let token_client = token::Client::new(&env, &token);
+    env.as_contract(&contract_id, || {
+        token_client.transfer(&contract_id, &Address::generate(&env), &1);
+    });
+    
+    // The claim reverts before the swap is marked claimed
+    let result = client.try_claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientBalance.into())));
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Pending);
+    assert_eq!(swap.preimage, None);
+    assert_eq!(token_client.balance(&recipient), 0);
+    
+    // Restoring the balance lets the claim through
+    fund_account(&env, &token, &contract_id, 1);
+    client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), 1_000_000);
+    assert_eq!(token_client.balance(&fee_recipient), 3_000);
+}
+
+#[test]
+fn test_event_schema_version_topic() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    // Every event of a call ends with the schema version topic
+    let assert_versioned = |name: &str| {
+        let named = String::from_str(&env, name);
+        let mut found = false;
+        for (contract, topics, _) in env.events().all().iter() {
+            if contract != contract_id {
+                continue;
+            }
+            let version: u32 = topics.last().unwrap().into_val(&env);
+            assert_eq!(version, EVENT_SCHEMA_VERSION);
+            found |= String::try_from_val(&env, &topics.first().unwrap()) == Ok(named.clone());
+        }
+        assert!(found, "{} event not emitted", name);
+    };
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &Address::generate(&env),
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &Address::generate(&env),
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_versioned("swap_created");
+    
+    client.claim_swap(&swap_id, &preimage, &None);
+    assert_versioned("swap_claimed");
+}
+
 #[test]
 fn test_refund_swap() {
     let (env, admin, fee_recipient, token) = create_test_env();
@@ -135,12 +433,13 @@ fn test_refund_swap() {
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
     // Create test data
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
     let hashlock = BytesN::from_array(&env, &[1u8; 32]);
     let timelock = 7200u64; // 2 hours
     let amount = 1_000_000i128;
@@ -155,7 +454,7 @@ fn test_refund_swap() {
         &amount,
         &eth_contract,
         &11155111u64,
-        &None,
+        &SwapOptions::default(),
     );
     
     // Fast forward past timelock
@@ -164,99 +463,223 @@ fn test_refund_swap() {
     });
     
     // Refund swap
-    client.refund_swap(&swap_id);
+    client.refund_swap(&swap_id, &None);
     
     // Verify refund
     let swap = client.get_swap_details(&swap_id).unwrap();
     assert_eq!(swap.status, SwapStatus::Refunded);
     assert!(swap.refunded_at.is_some());
+    
+    // Funds were returned to the sender
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&sender), amount);
+    assert_eq!(token_client.balance(&contract_id), 0);
 }
 
 #[test]
-fn test_register_resolver() {
-    let (env, admin, fee_recipient, _) = create_test_env();
+fn test_claim_cutoff_buffer() {
+    let (env, admin, fee_recipient, token) = create_test_env();
     let contract_id = env.register(StellarHTLC, ());
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
-    // Register resolver
-    let resolver = Address::generate(&env);
-    let collateral_token = Address::generate(&env);
-    let min_collateral = 5_000_000i128;
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
     
-    client.register_resolver(&resolver, &collateral_token, &min_collateral);
+    let mut swap_ids = Vec::new(&env);
+    for _ in 0..2 {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        ));
+    }
     
-    // Verify resolver
-    let resolver_info = client.get_resolver_info(&resolver).unwrap();
-    assert_eq!(resolver_info.resolver, resolver);
-    assert_eq!(resolver_info.collateral_token, collateral_token);
-    assert_eq!(resolver_info.min_collateral, min_collateral);
-    assert!(resolver_info.is_active);
+    // Inside the claim window (up to timelock - 300s)
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock - 300;
+    });
+    client.claim_swap(&swap_ids.get(0).unwrap(), &preimage, &None);
+    
+    // Inside the buffer neither claim nor refund is possible
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock - 299;
+    });
+    let result = client.try_claim_swap(&swap_ids.get(1).unwrap(), &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::ClaimWindowClosed.into())));
+    let result = client.try_refund_swap(&swap_ids.get(1).unwrap(), &None);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockNotExpired.into())));
+    
+    // After the timelock only a refund is possible
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock;
+    });
+    let result = client.try_claim_swap(&swap_ids.get(1).unwrap(), &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockExpired.into())));
+    client.refund_swap(&swap_ids.get(1).unwrap(), &None);
+    
+    // The buffer must stay below the minimum timelock duration
+    let result = client.try_set_claim_cutoff_buffer(&MIN_TIMELOCK_DURATION);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidTimelock.into())));
 }
 
 #[test]
-fn test_mark_swap_failed() {
+fn test_eth_finality_deadline() {
     let (env, admin, fee_recipient, token) = create_test_env();
     let contract_id = env.register(StellarHTLC, ());
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
+    let deadline = 3600u64;
+    
+    let mut swap_ids = Vec::new(&env);
+    for _ in 0..2 {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                eth_finality_deadline: Some(deadline),
+                ..SwapOptions::default()
+            },
+        ));
+    }
+    
+    // Claims up to the deadline succeed
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline;
+    });
+    client.claim_swap(&swap_ids.get(0).unwrap(), &preimage, &None);
+    
+    // Claims after the deadline fail even though the timelock is still open
+    env.ledger().with_mut(|li| {
+        li.timestamp = deadline + 1;
+    });
+    let result = client.try_claim_swap(&swap_ids.get(1).unwrap(), &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::EthFinalityExpired.into())));
+    
+    // The deadline cannot fall after the timelock
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &(deadline + 7200),
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            eth_finality_deadline: Some(deadline + 7201),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::InvalidTimelock.into())));
+}
+
+#[test]
+fn test_refund_authority() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
-    // Create test data
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
     let hashlock = BytesN::from_array(&env, &[1u8; 32]);
-    let timelock = 7200u64; // 2 hours
-    let amount = 1_000_000i128;
+    let timelock = 7200u64;
     
-    // Create swap
     let swap_id = client.create_swap(
         &sender,
         &recipient,
         &hashlock,
         &timelock,
         &token,
-        &amount,
+        &1_000_000i128,
         &eth_contract,
         &11155111u64,
-        &None,
+        &SwapOptions {
+            refund_authority: Some(keeper.clone()),
+            ..SwapOptions::default()
+        },
     );
     
-    // Verify swap exists and is pending
-    assert!(client.swap_exists(&swap_id));
-    let swap = client.get_swap_details(&swap_id).unwrap();
-    assert_eq!(swap.status, SwapStatus::Pending);
+    // Fast forward past timelock
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 1;
+    });
     
-    // Mark swap as failed (admin only)
-    let failure_reason = String::from_str(&env, "Network error");
-    client.mark_swap_failed(&swap_id, &failure_reason);
+    // Parties other than the sender and keeper are rejected
+    let result = client.try_refund_swap(&swap_id, &Some(stranger.clone()));
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
     
-    // Verify swap is marked as failed
-    let updated_swap = client.get_swap_details(&swap_id).unwrap();
-    assert_eq!(updated_swap.status, SwapStatus::Failed);
+    // The keeper's authorization alone refunds to the sender
+    env.mock_auths(&[MockAuth {
+        address: &keeper,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "refund_swap",
+            args: (swap_id.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.refund_swap(&swap_id, &Some(keeper.clone()));
+    
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Refunded);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&sender), 1_000_000);
+    assert_eq!(token_client.balance(&keeper), 0);
 }
 
 #[test]
-fn test_swap_exists() {
+fn test_refund_abandoned_swap() {
     let (env, admin, fee_recipient, token) = create_test_env();
     let contract_id = env.register(StellarHTLC, ());
     let client = StellarHTLCClient::new(&env, &contract_id);
     
-    // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
-    
-    // Test non-existent swap
-    let non_existent_id = String::from_str(&env, "non_existent_swap");
-    assert!(!client.swap_exists(&non_existent_id));
+    // Initialize contract with a 1 day abandonment period and 0.5% bounty
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    client.set_abandonment_policy(&86_400u64, &50);
     
-    // Create swap and test existence
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
     let hashlock = BytesN::from_array(&env, &[1u8; 32]);
     let timelock = 7200u64;
     let amount = 1_000_000i128;
@@ -270,36 +693,119 @@ fn test_swap_exists() {
         &amount,
         &eth_contract,
         &11155111u64,
-        &None,
+        &SwapOptions::default(),
     );
     
-    // Verify swap exists
-    assert!(client.swap_exists(&swap_id));
+    // Past the timelock but within the abandonment period
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 86_399;
+    });
+    let result = client.try_refund_abandoned(&swap_id, &keeper);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockNotExpired.into())));
+    
+    // Once the period elapses anyone can refund for the bounty
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 86_400;
+    });
+    assert_eq!(client.refund_abandoned(&swap_id, &keeper), 5_000);
+    
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Refunded);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&keeper), 5_000);
+    assert_eq!(token_client.balance(&sender), 995_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    
+    // A refunded swap cannot be refunded again
+    let result = client.try_refund_abandoned(&swap_id, &keeper);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyRefunded.into())));
+    
+    // Bounties are capped
+    let result = client.try_set_abandonment_policy(&86_400u64, &(MAX_ABANDONMENT_BOUNTY_BPS + 1));
+    assert_eq!(result, Err(Ok(HTLCError::InvalidFee.into())));
 }
 
 #[test]
-fn test_cannot_mark_claimed_swap_as_failed() {
+fn test_keeper_refund_bounty() {
     let (env, admin, fee_recipient, token) = create_test_env();
     let contract_id = env.register(StellarHTLC, ());
     let client = StellarHTLCClient::new(&env, &contract_id);
     
-    // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    // Initialize contract; keepers earn nothing until a bounty is set
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    assert_eq!(client.get_refund_bounty(), 0);
     
-    // Create and claim a swap
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
-    let preimage = BytesN::from_array(&env, &[1u8; 32]);
+    let keeper = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let token_client = token::Client::new(&env, &token);
     
-    // Calculate hashlock as SHA-256 of preimage
-    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
-    let hash_result = env.crypto().sha256(&preimage_bytes);
-    let hashlock = BytesN::from_array(&env, &hash_result.to_array());
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    let unrewarded = create();
+    let rewarded = create();
+    
+    // Keepers can't refund before expiry
+    let result = client.try_keeper_refund(&unrewarded, &keeper);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockNotExpired.into())));
+    
+    // Right at expiry a keeper refunds the whole amount to the sender
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    assert_eq!(client.keeper_refund(&unrewarded, &keeper), 0);
+    assert_eq!(token_client.balance(&sender), 1_000_000);
+    assert_eq!(token_client.balance(&keeper), 0);
+    
+    // With a 0.5% bounty the keeper takes its share and the sender the rest
+    client.set_refund_bounty(&50);
+    assert_eq!(client.keeper_refund(&rewarded, &keeper), 5_000);
+    assert_eq!(client.get_swap_details(&rewarded).unwrap().status, SwapStatus::Refunded);
+    assert_eq!(token_client.balance(&keeper), 5_000);
+    assert_eq!(token_client.balance(&sender), 1_995_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(client.get_locked_balance(&token), 0);
     
+    // Refunded swaps can't be refunded again, and bounties are capped
+    let result = client.try_keeper_refund(&rewarded, &keeper);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyRefunded.into())));
+    let result = client.try_set_refund_bounty(&(MAX_REFUND_BOUNTY_BPS + 1));
+    assert_eq!(result, Err(Ok(HTLCError::InvalidFee.into())));
+}
+
+#[test]
+fn test_create_swap_with_allowance() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
     let timelock = 7200u64;
     let amount = 1_000_000i128;
     
+    // Grant the contract an allowance instead of authorizing a direct transfer
+    let token_client = token::Client::new(&env, &token);
+    token_client.approve(&sender, &contract_id, &amount, &1000);
+    
     let swap_id = client.create_swap(
         &sender,
         &recipient,
@@ -309,38 +815,40 @@ fn test_cannot_mark_claimed_swap_as_failed() {
         &amount,
         &eth_contract,
         &11155111u64,
-        &None,
+        &SwapOptions {
+            use_allowance: true,
+            ..SwapOptions::default()
+        },
     );
     
-    // Claim the swap
-    client.claim_swap(&swap_id, &preimage);
-    
-    // Try to mark claimed swap as failed - should panic
-    let _failure_reason = String::from_str(&env, "Test failure");
-    
-    // This should panic with AlreadyClaimed error
-    // Note: In a real test environment, you would use proper assertion
-    // for panic testing based on the Soroban test framework
+    assert!(client.swap_exists(&swap_id));
+    assert_eq!(token_client.balance(&sender), 0);
+    assert_eq!(token_client.balance(&contract_id), amount);
+    assert_eq!(token_client.allowance(&sender, &contract_id), 0);
 }
 
 #[test]
-fn test_failed_status_integration() {
+fn test_create_swap_insufficient_allowance() {
     let (env, admin, fee_recipient, token) = create_test_env();
     let contract_id = env.register(StellarHTLC, ());
     let client = StellarHTLCClient::new(&env, &contract_id);
     
     // Initialize contract
-    client.initialize(&admin, &fee_recipient, &30);
+    client.initialize(&admin, &fee_recipient, &30, &None);
     
-    // Create swap
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
     let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
     let hashlock = BytesN::from_array(&env, &[1u8; 32]);
     let timelock = 7200u64;
     let amount = 1_000_000i128;
     
-    let swap_id = client.create_swap(
+    // Allowance covers only part of the swap amount
+    let token_client = token::Client::new(&env, &token);
+    token_client.approve(&sender, &contract_id, &(amount / 2), &1000);
+    
+    let result = client.try_create_swap(
         &sender,
         &recipient,
         &hashlock,
@@ -349,19 +857,4494 @@ fn test_failed_status_integration() {
         &amount,
         &eth_contract,
         &11155111u64,
-        &None,
+        &SwapOptions {
+            use_allowance: true,
+            ..SwapOptions::default()
+        },
     );
     
-    // Mark as failed
-    let failure_reason = String::from_str(&env, "Cross-chain coordination failed");
-    client.mark_swap_failed(&swap_id, &failure_reason);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientBalance.into())));
+    assert_eq!(token_client.balance(&sender), amount);
+}
+
+#[test]
+fn test_register_resolver() {
+    let (env, admin, fee_recipient, _) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
     
-    // Verify status
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Register resolver
+    let resolver = Address::generate(&env);
+    let collateral_token = Address::generate(&env);
+    let min_collateral = 5_000_000i128;
+    
+    client.register_resolver(&resolver, &collateral_token, &min_collateral);
+    
+    // Verify resolver
+    let resolver_info = client.get_resolver_info(&resolver).unwrap();
+    assert_eq!(resolver_info.resolver, resolver);
+    assert_eq!(resolver_info.collateral_token, collateral_token);
+    assert_eq!(resolver_info.min_collateral, min_collateral);
+    assert!(resolver_info.is_active);
+}
+
+#[test]
+fn test_register_resolvers_batch() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let mut entries = Vec::new(&env);
+    for min_collateral in [1_000_000i128, 2_000_000, 3_000_000] {
+        entries.push_back(ResolverRegistration {
+            resolver: Address::generate(&env),
+            collateral_token: token.clone(),
+            min_collateral,
+        });
+    }
+    client.register_resolvers_batch(&entries);
+    
+    // Every resolver is registered and announced
+    let registered: Vec<Val> = (String::from_str(&env, "resolver_registered"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let events = env.events().all();
+    for entry in entries.iter() {
+        let info = client.get_resolver_info(&entry.resolver).unwrap();
+        assert_eq!(info.collateral_token, token);
+        assert_eq!(info.min_collateral, entry.min_collateral);
+        assert!(info.is_active);
+        
+        assert!(events.iter().any(|(contract, topics, data)| {
+            let announced: (Address, i128) = data.into_val(&env);
+            contract == contract_id
+                && topics == registered
+                && announced == (entry.resolver.clone(), entry.min_collateral)
+        }));
+    }
+    
+    // One invalid entry fails the whole batch
+    let valid = Address::generate(&env);
+    let invalid = Address::generate(&env);
+    let mut entries = Vec::new(&env);
+    entries.push_back(ResolverRegistration {
+        resolver: valid.clone(),
+        collateral_token: token.clone(),
+        min_collateral: 1_000_000,
+    });
+    entries.push_back(ResolverRegistration {
+        resolver: invalid.clone(),
+        collateral_token: token.clone(),
+        min_collateral: 0,
+    });
+    let result = client.try_register_resolvers_batch(&entries);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientCollateral.into())));
+    assert_eq!(client.get_resolver_info(&valid), None);
+    assert_eq!(client.get_resolver_info(&invalid), None);
+    
+    // Oversized batches are rejected
+    let mut entries = Vec::new(&env);
+    for _ in 0..=MAX_RESOLVER_BATCH_SIZE {
+        entries.push_back(ResolverRegistration {
+            resolver: Address::generate(&env),
+            collateral_token: token.clone(),
+            min_collateral: 1_000_000,
+        });
+    }
+    let result = client.try_register_resolvers_batch(&entries);
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_chain_contract_validation() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    let stale_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    client.set_chain_contract(&11155111u64, &eth_contract);
+    assert_eq!(client.get_chain_contract(&11155111u64), Some(eth_contract.clone()));
+    assert_eq!(client.get_chain_contract(&1u64), None);
+    
+    // Registered contract is accepted
+    client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // A different contract on a registered chain is rejected
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &stale_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(result, Err(Ok(HTLCError::UnknownEthContract.into())));
+    
+    // Unregistered chains are not validated
+    client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &stale_contract,
+        &1u64,
+        &SwapOptions::default(),
+    );
+}
+
+#[test]
+fn test_resolver_heartbeat_staleness() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    assert!(client.is_resolver_live(&resolver));
+    assert!(!client.is_resolver_live(&Address::generate(&env)));
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    // Fast forward past the default 24h window without a heartbeat
+    env.ledger().with_mut(|li| {
+        li.timestamp = 86_401;
+    });
+    assert!(!client.is_resolver_live(&resolver));
+    
+    let timelock = 86_401u64 + 7200;
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            resolver: Some(resolver.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::ResolverNotActive.into())));
+    
+    // A heartbeat restores eligibility
+    client.resolver_heartbeat(&resolver);
+    assert!(client.is_resolver_live(&resolver));
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().last_active, 86_401);
+    client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            resolver: Some(resolver.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    
+    // A shorter window takes effect immediately
+    client.set_staleness_window(&60u64);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 86_401 + 61;
+    });
+    assert!(!client.is_resolver_live(&resolver));
+}
+
+#[test]
+fn test_resolver_swap_limit() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    client.set_resolver_limit(&resolver, &1_000_000i128);
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().max_swap_amount, 1_000_000);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    
+    // Under and at the limit are accepted
+    for amount in [999_999i128, 1_000_000i128] {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver: Some(resolver.clone()),
+                ..SwapOptions::default()
+            },
+        );
+    }
+    
+    // Over the limit is rejected
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_001i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            resolver: Some(resolver.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::SwapExceedsResolverLimit.into())));
+    
+    // Clearing the limit lifts the cap
+    client.set_resolver_limit(&resolver, &0i128);
+    client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_001i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            resolver: Some(resolver.clone()),
+            ..SwapOptions::default()
+        },
+    );
+}
+
+#[test]
+fn test_resolver_collateral_ratio() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Resolvers must hold half the value of their open swaps
+    assert_eq!(client.get_collateral_ratio(), 0);
+    client.set_collateral_ratio(&5_000);
+    assert_eq!(client.get_collateral_ratio(), 5_000);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &100_000i128);
+    fund_account(&env, &token, &resolver, 1_000_000);
+    client.add_collateral(&resolver, &600_000i128);
+    
+    let sender = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    let create = || client.try_create_swap(
+        &sender,
+        &Address::generate(&env),
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &Address::generate(&env),
+        &11155111u64,
+        &SwapOptions {
+            resolver: Some(resolver.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    
+    // 600,000 covers one 1,000,000 swap but not a second concurrent one
+    let first = create().unwrap().unwrap();
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().open_value, 1_000_000);
+    assert_eq!(create().err(), Some(Ok(HTLCError::InsufficientCollateral.into())));
+    
+    // Nor can the resolver withdraw below the ratio of its open swaps
+    let result = client.try_request_collateral_withdrawal(&resolver, &200_000i128);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientCollateral.into())));
+    
+    // Topping up the collateral admits the second swap
+    client.add_collateral(&resolver, &400_000i128);
+    create().unwrap().unwrap();
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().open_value, 2_000_000);
+    
+    // Settling a swap frees its share of the collateral
+    client.claim_swap(&first, &preimage, &None);
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().open_value, 1_000_000);
+    client.request_collateral_withdrawal(&resolver, &500_000i128);
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().deposited_collateral, 500_000);
+    
+    // Re-registering updates the terms but keeps the collateral and open swaps
+    client.register_resolver(&resolver, &token, &200_000i128);
+    let info = client.get_resolver_info(&resolver).unwrap();
+    assert_eq!(info.min_collateral, 200_000);
+    assert_eq!((info.deposited_collateral, info.open_swaps, info.open_value), (500_000, 1, 1_000_000));
+    
+    // The collateral token can't move out from under the deposit
+    let result = client.try_register_resolver(&resolver, &Address::generate(&env), &200_000i128);
+    assert_eq!(result, Err(Ok(HTLCError::ResolverInUse.into())));
+}
+
+#[test]
+fn test_resolver_leaderboard() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 10_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let resolver_a = Address::generate(&env);
+    let resolver_b = Address::generate(&env);
+    let resolver_c = Address::generate(&env);
+    for resolver in [&resolver_a, &resolver_b, &resolver_c] {
+        client.register_resolver(resolver, &token, &5_000_000i128);
+    }
+    
+    let resolve = |resolver: &Address, amount: i128| {
+        let swap_id = client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver: Some(resolver.clone()),
+                ..SwapOptions::default()
+            },
+        );
+        client.claim_swap(&swap_id, &preimage, &None);
+    };
+    
+    resolve(&resolver_b, 2_000_000);
+    resolve(&resolver_a, 3_000_000);
+    resolve(&resolver_c, 1_000_000);
+    
+    let top = client.get_top_resolvers(&10);
+    assert_eq!(top.len(), 3);
+    assert_eq!(top.get(0).unwrap().resolver, resolver_a);
+    assert_eq!(top.get(0).unwrap().total_volume, 3_000_000);
+    assert_eq!(top.get(0).unwrap().total_resolved, 1);
+    assert_eq!(top.get(1).unwrap().resolver, resolver_b);
+    assert_eq!(top.get(2).unwrap().resolver, resolver_c);
+    
+    // C climbs to the top and pushes B out of the top two
+    resolve(&resolver_c, 2_500_000);
+    let top = client.get_top_resolvers(&2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top.get(0).unwrap().resolver, resolver_c);
+    assert_eq!(top.get(0).unwrap().total_volume, 3_500_000);
+    assert_eq!(top.get(1).unwrap().resolver, resolver_a);
+}
+
+#[test]
+fn test_resolver_leaderboard_is_capped() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Fill the board with resolvers at volumes 1..=MAX_LEADERBOARD_SIZE
+    let mut bottom = None;
+    for volume in 1..=(MAX_LEADERBOARD_SIZE as i128 + 1) {
+        let resolver = Address::generate(&env);
+        client.register_resolver(&resolver, &token, &5_000_000i128);
+        env.as_contract(&contract_id, || {
+            let mut resolver_info = get_resolver(&env, &resolver).unwrap();
+            resolver_info.total_volume = volume;
+            set_resolver(&env, &resolver, &resolver_info);
+            update_resolver_leaderboard(&env, &resolver_info);
+        });
+        if volume == 1 {
+            bottom = Some(resolver);
+        }
+    }
+    
+    // The lowest-volume resolver was displaced by the newcomer
+    let top = client.get_top_resolvers(&MAX_LEADERBOARD_SIZE);
+    assert_eq!(top.len(), MAX_LEADERBOARD_SIZE);
+    assert_eq!(top.get(0).unwrap().total_volume, MAX_LEADERBOARD_SIZE as i128 + 1);
+    assert_eq!(top.get(MAX_LEADERBOARD_SIZE - 1).unwrap().total_volume, 2);
+    assert!(top.iter().all(|info| Some(info.resolver) != bottom));
+}
+
+#[test]
+fn test_add_collateral() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    fund_account(&env, &token, &resolver, 10_000_000);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    
+    // Top up in two deposits
+    client.add_collateral(&resolver, &4_000_000i128);
+    client.add_collateral(&resolver, &2_000_000i128);
+    
+    // Top-ups are announced with the new total
+    let expected_topics: Vec<Val> = (symbol_short!("coll_inc"), resolver.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+        .expect("collateral event not emitted");
+    let event: CollateralIncreasedEvent = data.into_val(&env);
+    assert_eq!(event, CollateralIncreasedEvent {
+        resolver: resolver.clone(),
+        amount: 2_000_000,
+        total_collateral: 6_000_000,
+    });
+    
+    let resolver_info = client.get_resolver_info(&resolver).unwrap();
+    assert_eq!(resolver_info.deposited_collateral, 6_000_000);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&resolver), 4_000_000);
+    assert_eq!(token_client.balance(&contract_id), 6_000_000);
+    
+    // Zero amounts are rejected
+    let result = client.try_add_collateral(&resolver, &0i128);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    
+    // Unknown resolvers cannot deposit
+    let stranger = Address::generate(&env);
+    let result = client.try_add_collateral(&stranger, &1_000i128);
+    assert_eq!(result, Err(Ok(HTLCError::ResolverNotFound.into())));
+    
+    // Inactive resolvers can still top up
+    env.as_contract(&contract_id, || {
+        let mut resolver_info = get_resolver(&env, &resolver).unwrap();
+        resolver_info.is_active = false;
+        set_resolver(&env, &resolver, &resolver_info);
+    });
+    client.add_collateral(&resolver, &1_000_000i128);
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().deposited_collateral, 7_000_000);
+}
+
+#[test]
+fn test_collateral_withdrawal_blocked_while_swap_open() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    fund_account(&env, &token, &resolver, 6_000_000);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    client.add_collateral(&resolver, &6_000_000i128);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            resolver: Some(resolver.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().open_swaps, 1);
+    
+    // Dropping below the minimum is blocked while the swap is open
+    let result = client.try_request_collateral_withdrawal(&resolver, &2_000_000i128);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientCollateral.into())));
+    
+    // Withdrawing the excess is allowed
+    client.request_collateral_withdrawal(&resolver, &1_000_000i128);
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().deposited_collateral, 5_000_000);
+    
+    // Once the swap settles the rest can be withdrawn
+    client.claim_swap(&swap_id, &preimage, &None);
+    let resolver_info = client.get_resolver_info(&resolver).unwrap();
+    assert_eq!(resolver_info.open_swaps, 0);
+    client.request_collateral_withdrawal(&resolver, &5_000_000i128);
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().deposited_collateral, 0);
+    assert_eq!(token::Client::new(&env, &token).balance(&resolver), 6_000_000);
+    
+    // Cannot withdraw more than was deposited
+    let result = client.try_request_collateral_withdrawal(&resolver, &1i128);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientBalance.into())));
+}
+
+#[test]
+fn test_mark_swap_failed() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Create test data
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64; // 2 hours
+    let amount = 1_000_000i128;
+    
+    // Create swap
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Verify swap exists and is pending
+    assert!(client.swap_exists(&swap_id));
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Pending);
+    assert_eq!(client.get_failure_info(&swap_id), None);
+    
+    // Mark swap as failed (admin only)
+    let failure_reason = String::from_str(&env, "Network error");
+    client.mark_swap_failed(&swap_id, &FailureCode::Other, &failure_reason);
+    
+    // The event carries the failure code
+    let expected_topics: Vec<Val> = (symbol_short!("failed"), swap_id.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+        .expect("failure event not emitted");
+    let event: SwapFailedEvent = data.into_val(&env);
+    assert_eq!(event, SwapFailedEvent {
+        swap_id: swap_id.clone(),
+        sender: sender.clone(),
+        code: FailureCode::Other,
+        reason: failure_reason.clone(),
+        resolver: None,
+        elapsed: 0,
+    });
+    
+    // So does the status update
+    let expected_topics: Vec<Val> = (symbol_short!("status"), swap_id.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+        .expect("status event not emitted");
+    let event: SwapStatusUpdatedEvent = data.into_val(&env);
+    assert_eq!(event, SwapStatusUpdatedEvent {
+        swap_id: swap_id.clone(),
+        old_status: SwapStatus::Pending,
+        new_status: SwapStatus::Failed,
+    });
+    
+    // Verify swap is marked as failed
+    let updated_swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(updated_swap.status, SwapStatus::Failed);
+    assert_eq!(client.get_failure_info(&swap_id), Some((FailureCode::Other, failure_reason.clone())));
+    
+    // A failed swap can't be failed again
+    let result = client.try_mark_swap_failed(&swap_id, &FailureCode::Other, &failure_reason);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotPending.into())));
+}
+
+#[test]
+fn test_swap_exists() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Test non-existent swap
+    let non_existent_id = String::from_str(&env, "non_existent_swap");
+    assert!(!client.swap_exists(&non_existent_id));
+    
+    // Create swap and test existence
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Verify swap exists
+    assert!(client.swap_exists(&swap_id));
+}
+
+#[test]
+fn test_cannot_mark_claimed_swap_as_failed() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Create and claim a swap
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[1u8; 32]);
+    
+    // Calculate hashlock as SHA-256 of preimage
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hash_result = env.crypto().sha256(&preimage_bytes);
+    let hashlock = BytesN::from_array(&env, &hash_result.to_array());
+    
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Claim the swap
+    client.claim_swap(&swap_id, &preimage, &None);
+    
+    // Marking a claimed swap as failed is rejected
+    let failure_reason = String::from_str(&env, "Test failure");
+    let result = client.try_mark_swap_failed(&swap_id, &FailureCode::Other, &failure_reason);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyClaimed.into())));
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Claimed);
+}
+
+#[test]
+fn test_failed_status_integration() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Create swap
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Mark as failed
+    let failure_reason = String::from_str(&env, "Cross-chain coordination failed");
+    client.mark_swap_failed(&swap_id, &FailureCode::EthLegFailed, &failure_reason);
+    
+    // Verify status
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Failed);
+    assert_eq!(client.get_failure_info(&swap_id), Some((FailureCode::EthLegFailed, failure_reason)));
+    
+    // Contract stats should remain accurate
+    let stats = client.get_contract_stats();
+    assert_eq!(stats.total_swaps_created, 1);
+    assert_eq!(stats.total_swaps_completed, 0); // Failed swaps don't count as completed
+}
+
+#[test]
+fn test_failed_swap_cannot_be_claimed() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // The Ethereum leg was reorged, so the admin fails the swap
+    let reason = String::from_str(&env, "Ethereum leg reorged");
+    client.mark_swap_failed(&swap_id, &FailureCode::EthLegFailed, &reason);
+    
+    // Knowing the secret no longer lets the recipient claim
+    let result = client.try_claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotClaimable.into())));
+    
+    // The sender can refund straight away, before the timelock
+    client.refund_swap(&swap_id, &None);
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Refunded);
+    assert_eq!(token::Client::new(&env, &token).balance(&sender), 1_000_000);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 0);
+    
+    let result = client.try_claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyRefunded.into())));
+}
+
+#[test]
+fn test_permissioned_mode_toggle() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[7u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    // Swap created while the mode is off
+    let existing_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Enable permissioned mode: unlisted sender is rejected
+    client.set_permissioned_mode(&true);
+    assert!(!client.is_allowed_sender(&sender));
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
+    
+    // Existing swap can still be claimed
+    client.claim_swap(&existing_swap_id, &preimage, &None);
+    assert_eq!(client.get_swap_details(&existing_swap_id).unwrap().status, SwapStatus::Claimed);
+    
+    // Listed sender can create swaps
+    client.add_allowed_sender(&sender);
+    assert!(client.is_allowed_sender(&sender));
+    let allowed_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_ne!(allowed_swap_id, existing_swap_id);
+    
+    // Removing the sender rejects them again
+    client.remove_allowed_sender(&sender);
+    assert!(!client.is_allowed_sender(&sender));
+    
+    // Disabling the mode reopens creation to everyone
+    client.set_permissioned_mode(&false);
+    let open_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert!(client.swap_exists(&open_swap_id));
+    assert_eq!(client.get_contract_stats().total_swaps_created, 3);
+}
+
+#[test]
+fn test_recipient_registration_mode() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    
+    let create = |recipient: &Address| {
+        client.try_create_swap(
+            &sender,
+            recipient,
+            &BytesN::from_array(&env, &[7u8; 32]),
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    
+    // Disabled by default: any recipient can be paid
+    assert!(!client.get_recipient_registration());
+    assert!(create(&recipient).is_ok());
+    
+    // Enabled: unregistered recipients are rejected
+    client.set_recipient_registration(&true);
+    assert!(client.get_recipient_registration());
+    assert!(!client.is_registered_recipient(&recipient));
+    assert_eq!(create(&recipient), Err(Ok(HTLCError::RecipientNotRegistered.into())));
+    
+    // Registered recipients are accepted
+    client.add_registered_recipient(&recipient);
+    assert!(client.is_registered_recipient(&recipient));
+    assert!(create(&recipient).is_ok());
+    
+    // Removing the recipient rejects them again
+    client.remove_registered_recipient(&recipient);
+    assert!(!client.is_registered_recipient(&recipient));
+    assert_eq!(create(&recipient), Err(Ok(HTLCError::RecipientNotRegistered.into())));
+    
+    // Disabling the mode reopens swaps to every recipient
+    client.set_recipient_registration(&false);
+    assert!(create(&recipient).is_ok());
+    assert_eq!(client.get_contract_stats().total_swaps_created, 3);
+}
+
+#[test]
+fn test_get_preimage() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // No preimage before the swap is claimed
+    assert_eq!(client.get_preimage(&swap_id), None);
+    
+    client.claim_swap(&swap_id, &preimage, &None);
+    
+    // Preimage is recoverable after claim
+    assert_eq!(client.get_preimage(&swap_id), Some(preimage));
+    
+    // Unknown swaps have no preimage
+    assert_eq!(client.get_preimage(&String::from_str(&env, "unknown")), None);
+}
+
+#[test]
+fn test_record_preimage() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    let token_client = token::Client::new(&env, &token);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // An incorrect preimage is rejected
+    let wrong = BytesN::from_array(&env, &[0u8; 32]);
+    let result = client.try_record_preimage(&swap_id, &wrong, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+    let result = client.try_record_preimage(&String::from_str(&env, "unknown"), &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotFound.into())));
+    assert_eq!(client.get_preimage(&swap_id), None);
+    
+    // Anyone can record the correct preimage before the claim
+    client.record_preimage(&swap_id, &preimage, &None);
+    let expected_topics: Vec<Val> = (String::from_str(&env, "preimage_recorded"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+        .expect("preimage event not emitted");
+    let recorded: (String, BytesN<32>) = data.into_val(&env);
+    assert_eq!(recorded, (swap_id.clone(), preimage.clone()));
+    assert_eq!(client.get_preimage(&swap_id), Some(preimage.clone()));
+    
+    // The swap and its funds are untouched
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+    assert_eq!(token_client.balance(&contract_id), 1_000_000);
+    
+    // The claim still goes through afterwards
+    client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Claimed);
+    assert_eq!(token_client.balance(&recipient), 997_000);
+    assert_eq!(client.get_preimage(&swap_id), Some(preimage));
+}
+
+#[test]
+fn test_swap_memo() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    // Memo round-trips through swap details
+    let memo = String::from_str(&env, "invoice-2024-0042");
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            memo: Some(memo.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().memo, Some(memo));
+    
+    // Memos over 64 bytes are rejected
+    let long_memo = String::from_str(&env, &"x".repeat(65));
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            memo: Some(long_memo),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::MemoTooLong.into())));
+}
+
+#[test]
+fn test_swap_quote() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let quote_token = Address::generate(&env);
+    
+    let create = |quote_token: Option<Address>, quote_amount: Option<i128>| {
+        client.try_create_swap(
+            &sender,
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                quote_token,
+                quote_amount,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // Quote fields round-trip through the creation event and swap details
+    let swap_id = create(Some(quote_token.clone()), Some(2_500_000)).unwrap().unwrap();
+    let created: Vec<Val> = (String::from_str(&env, "swap_created"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &created)
+        .expect("creation event not emitted");
+    let data: Vec<Val> = data.into_val(&env);
+    let announced_token: Option<Address> = data.get(14).unwrap().into_val(&env);
+    let announced_amount: Option<i128> = data.get(15).unwrap().into_val(&env);
+    assert_eq!(announced_token, Some(quote_token.clone()));
+    assert_eq!(announced_amount, Some(2_500_000));
+    
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.amount, 1_000_000);
+    assert_eq!(swap.quote_token, Some(quote_token.clone()));
+    assert_eq!(swap.quote_amount, Some(2_500_000));
+    
+    // Swaps without a quote leave both fields empty
+    let swap_id = create(None, None).unwrap().unwrap();
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.quote_token, None);
+    assert_eq!(swap.quote_amount, None);
+    
+    // The fields are set together, with a positive amount
+    assert_eq!(create(Some(quote_token.clone()), None), Err(Ok(HTLCError::InvalidAmount.into())));
+    assert_eq!(create(None, Some(2_500_000)), Err(Ok(HTLCError::InvalidAmount.into())));
+    assert_eq!(create(Some(quote_token), Some(0)), Err(Ok(HTLCError::InvalidAmount.into())));
+}
+
+#[test]
+fn test_salted_commitment_claim() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    // Commit to sha256(preimage || salt)
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+    let mut commitment = Bytes::from_array(&env, &preimage.to_array());
+    commitment.append(&Bytes::from_array(&env, &salt.to_array()));
+    let hashlock: BytesN<32> = env.crypto().sha256(&commitment).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // The preimage alone does not open a salted commitment
+    let result = client.try_claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+    
+    // Nor does a different salt
+    let wrong_salt = BytesN::from_array(&env, &[8u8; 32]);
+    let result = client.try_claim_swap(&swap_id, &preimage, &Some(wrong_salt));
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+    
+    // The matching salt claims and is revealed alongside the preimage
+    assert!(client.verify_preimage(&swap_id, &preimage, &Some(salt.clone())));
+    client.claim_swap(&swap_id, &preimage, &Some(salt.clone()));
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Claimed);
+    assert_eq!(swap.salt, Some(salt));
+}
+
+#[test]
+fn test_truncated_hashlock_claim() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let digest = env.crypto().sha256(&preimage_bytes).to_array();
+    
+    // Keep a 20-byte commitment, zero-padded to 32 bytes
+    let mut truncated = [0u8; 32];
+    truncated[..20].copy_from_slice(&digest[..20]);
+    let mut tampered = truncated;
+    tampered[19] ^= 1;
+    
+    let mut swap_ids = Vec::new(&env);
+    for hashlock in [truncated, tampered] {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &BytesN::from_array(&env, &hashlock),
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                hashlock_len: Some(20u32),
+                ..SwapOptions::default()
+            },
+        ));
+    }
+    
+    // The matching 20-byte prefix claims
+    client.claim_swap(&swap_ids.get(0).unwrap(), &preimage, &None);
+    assert_eq!(client.get_swap_details(&swap_ids.get(0).unwrap()).unwrap().status, SwapStatus::Claimed);
+    
+    // A tampered prefix does not
+    let result = client.try_claim_swap(&swap_ids.get(1).unwrap(), &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+    
+    // Prefixes shorter than 20 bytes are rejected
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &BytesN::from_array(&env, &truncated),
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            hashlock_len: Some(19u32),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::InvalidHashlockLength.into())));
+}
+
+#[test]
+fn test_verify_preimage() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Correct preimage matches without changing the swap
+    assert!(client.verify_preimage(&swap_id, &preimage, &None));
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+    
+    // Wrong preimage does not match
+    let wrong_preimage = BytesN::from_array(&env, &[7u8; 32]);
+    assert!(!client.verify_preimage(&swap_id, &wrong_preimage, &None));
+    
+    // Unknown swaps never match
+    assert!(!client.verify_preimage(&String::from_str(&env, "unknown"), &preimage, &None));
+}
+
+#[test]
+fn test_diagnose_claim() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    let mut secret = [0u8; 32];
+    for (i, byte) in secret.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let preimage = BytesN::from_array(&env, &secret);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &secret)).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // The right secret hashes to the stored hashlock
+    let diagnosis = client.diagnose_claim(&swap_id, &preimage, &None);
+    assert!(env.events().all().is_empty());
+    assert_eq!(diagnosis, ClaimDiagnosis {
+        computed_hash: hashlock.clone(),
+        hashlock: hashlock.clone(),
+        hashlock_len: 32,
+        matches: true,
+    });
+    
+    // A byte-reversed secret shows both hashes side by side
+    secret.reverse();
+    let reversed = BytesN::from_array(&env, &secret);
+    let diagnosis = client.diagnose_claim(&swap_id, &reversed, &None);
+    assert!(!diagnosis.matches);
+    assert_eq!(diagnosis.hashlock, hashlock);
+    let reversed_hash: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &secret)).into();
+    assert_eq!(diagnosis.computed_hash, reversed_hash);
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+    
+    assert_eq!(
+        client.try_diagnose_claim(&String::from_str(&env, "unknown"), &preimage, &None),
+        Err(Ok(HTLCError::SwapNotFound.into()))
+    );
+}
+
+#[test]
+fn test_blocked_address_cannot_create_swap() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    // Blocked recipient is rejected
+    client.block_address(&recipient);
+    assert!(client.is_address_blocked(&recipient));
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(result, Err(Ok(HTLCError::AddressBlocked.into())));
+    
+    // Blocked resolvers cannot be registered
+    let resolver = Address::generate(&env);
+    client.block_address(&resolver);
+    let result = client.try_register_resolver(&resolver, &token, &5_000_000i128);
+    assert_eq!(result, Err(Ok(HTLCError::AddressBlocked.into())));
+    
+    // Unblocking restores access
+    client.unblock_address(&recipient);
+    assert!(!client.is_address_blocked(&recipient));
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert!(client.swap_exists(&swap_id));
+}
+
+#[test]
+fn test_blocked_recipient_cannot_claim() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    client.block_address(&recipient);
+    let result = client.try_claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AddressBlocked.into())));
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+}
+
+#[test]
+fn test_blocked_sender_can_still_refund() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    client.block_address(&sender);
+    
+    // Fast forward past timelock
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 1;
+    });
+    
+    // Funds return to origin even though the sender is blocked
+    client.refund_swap(&swap_id, &None);
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Refunded);
+    assert_eq!(token::Client::new(&env, &token).balance(&sender), 1_000_000);
+}
+
+#[test]
+fn test_permissioned_creation_enabled() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    client.set_permissioned_creation(&true);
+    
+    let listed = Address::generate(&env);
+    let unknown = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &listed, 1_000_000);
+    fund_account(&env, &token, &unknown, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    // Unknown sender is rejected
+    let result = client.try_create_swap(
+        &unknown,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(result, Err(Ok(HTLCError::CreatorNotAllowed.into())));
+    
+    // Listed sender is accepted
+    client.add_allowed_creator(&listed);
+    assert!(client.is_allowed_creator(&listed));
+    let swap_id = client.create_swap(
+        &listed,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert!(client.swap_exists(&swap_id));
+    
+    // Removed creators are rejected again
+    client.remove_allowed_creator(&listed);
+    assert!(!client.is_allowed_creator(&listed));
+}
+
+#[test]
+fn test_permissioned_creation_disabled() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    client.set_permissioned_creation(&false);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    // Any sender can create swaps when the flag is off
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert!(client.swap_exists(&swap_id));
+    assert!(!client.is_allowed_creator(&sender));
+}
+
+#[test]
+fn test_create_swap_auth_bound_to_arguments() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    
+    // Authorization signed for a smaller amount doesn't cover this call
+    env.mock_auths(&[MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "create_swap",
+            args: (
+                recipient.clone(),
+                token.clone(),
+                1i128,
+                hashlock.clone(),
+                timelock,
+                eth_contract.clone(),
+                11155111u64,
+                SwapOptions::default(),
+            ).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert!(result.is_err());
+    
+    // Nor does one a relayer replays with refunds rerouted to itself
+    let signed_terms = (
+        recipient.clone(),
+        token.clone(),
+        amount,
+        hashlock.clone(),
+        timelock,
+        eth_contract.clone(),
+        11155111u64,
+        SwapOptions::default(),
+    );
+    env.mock_auths(&[MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "create_swap",
+            args: signed_terms.clone().into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            refund_to: Some(Address::generate(&env)),
+            ..SwapOptions::default()
+        },
+    );
+    assert!(result.is_err());
+    
+    // Authorization matching the swap terms succeeds
+    env.mock_auths(&[MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "create_swap",
+            args: signed_terms.into_val(&env),
+            sub_invokes: &[MockAuthInvoke {
+                contract: &token,
+                fn_name: "transfer",
+                args: (sender.clone(), contract_id.clone(), amount).into_val(&env),
+                sub_invokes: &[],
+            }],
+        },
+    }]);
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert!(client.swap_exists(&swap_id));
+}
+
+#[test]
+fn test_claim_and_refund_auth_bound_to_swap_id() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
+    
+    let first_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    let second_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Claim authorization for the first swap can't claim the second
+    env.mock_auths(&[MockAuth {
+        address: &recipient,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "claim_swap",
+            args: (first_swap_id.clone(), preimage.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_claim_swap(&second_swap_id, &preimage, &None).is_err());
+    
+    // Fast forward past timelock
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 1;
+    });
+    
+    // Refund authorization for the first swap can't refund the second
+    let refund_auth = [MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "refund_swap",
+            args: (first_swap_id.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }];
+    env.mock_auths(&refund_auth);
+    assert!(client.try_refund_swap(&second_swap_id, &None).is_err());
+    assert_eq!(client.get_swap_details(&second_swap_id).unwrap().status, SwapStatus::Pending);
+    
+    // Matching authorization refunds the first swap
+    env.mock_auths(&refund_auth);
+    client.refund_swap(&first_swap_id, &None);
+    assert_eq!(client.get_swap_details(&first_swap_id).unwrap().status, SwapStatus::Refunded);
+}
+
+#[test]
+fn test_recompute_stats_corrects_drift() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let mut swap_ids = Vec::new(&env);
+    for _ in 0..3 {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        ));
+    }
+    
+    client.claim_swap(&swap_ids.get(0).unwrap(), &preimage, &None);
+    
+    // Deliberately corrupt the counters
+    env.as_contract(&contract_id, || {
+        set_total_swaps_completed(&env, 99);
+        set_status_count(&env, &SwapStatus::Failed, 7);
+    });
+    assert_eq!(client.get_contract_stats().total_swaps_completed, 99);
+    
+    // The first page counts two swaps and leaves the counters alone
+    assert_eq!(client.recompute_stats(&2), None);
+    assert_eq!(client.get_contract_stats().total_swaps_completed, 99);
+    
+    // A counted swap failing mid-count moves to its new status
+    client.mark_swap_failed(&swap_ids.get(1).unwrap(), &FailureCode::Timeout, &String::from_str(&env, "Timeout"));
+    
+    // The last page replaces the counters: one claimed, one failed, one pending
+    let stats = client.recompute_stats(&2).unwrap();
+    assert_eq!(stats.total_swaps_created, 3);
+    assert_eq!(stats.total_swaps_completed, 1);
+    assert_eq!(stats.total_swaps_refunded, 0);
+    assert_eq!(stats.total_swaps_failed, 1);
+    assert_eq!(stats.active_swaps, 1);
+    assert_eq!(client.get_contract_stats(), stats);
+    assert_eq!(client.get_status_counts().failed, 1);
+    
+    // Oversized pages are rejected
+    let result = client.try_recompute_stats(&(MAX_STATS_BATCH_SIZE + 1));
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_fee_collected_event() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract with a 0.5% fee
+    client.initialize(&admin, &fee_recipient, &50, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &2_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    client.claim_swap(&swap_id, &preimage, &None);
+    
+    let expected_topics: Vec<Val> = (symbol_short!("fee_coll"), swap_id.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+        .expect("fee event not emitted");
+    let event: FeeCollectedEvent = data.into_val(&env);
+    
+    assert_eq!(event, FeeCollectedEvent {
+        swap_id,
+        token: token.clone(),
+        fee_amount: 10_000,
+        fee_recipient: fee_recipient.clone(),
+    });
+    assert_eq!(token::Client::new(&env, &token).balance(&fee_recipient), 10_000);
+}
+
+#[test]
+fn test_claim_falls_back_to_claimable_balance() {
+    let (env, admin, fee_recipient, _) = create_test_env();
+    
+    // Asset whose issuer can revoke authorization
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = asset.address();
+    
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // Recipient can no longer receive the asset
+    let asset_admin = token::StellarAssetClient::new(&env, &token);
+    asset_admin.set_authorized(&recipient, &false);
+    
+    client.claim_swap(&swap_id, &preimage, &None);
+    
+    // Claim is recorded and the payout credited internally
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Claimed);
+    assert_eq!(swap.preimage, Some(preimage));
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(client.get_claimable_balance(&recipient, &token), 997_000);
+    
+    // Once the recipient can receive again the balance is withdrawable
+    asset_admin.set_authorized(&recipient, &true);
+    assert_eq!(client.withdraw_claimable(&recipient, &token), 997_000);
+    assert_eq!(token_client.balance(&recipient), 997_000);
+    assert_eq!(client.get_claimable_balance(&recipient, &token), 0);
+    
+    let result = client.try_withdraw_claimable(&recipient, &token);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientBalance.into())));
+}
+
+#[test]
+fn test_status_counts_track_transitions() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 4_000_000);
+    
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
+    
+    let mut swap_ids = Vec::new(&env);
+    for _ in 0..4 {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        ));
+    }
+    assert_eq!(client.get_status_counts(), StatusCounts {
+        pending: 4,
+        active: 0,
+        claimed: 0,
+        refunded: 0,
+        failed: 0,
+    });
+    
+    // Pending -> Claimed and Pending -> Failed
+    client.claim_swap(&swap_ids.get(0).unwrap(), &preimage, &None);
+    client.mark_swap_failed(&swap_ids.get(1).unwrap(), &FailureCode::Timeout, &String::from_str(&env, "Timeout"));
+    assert_eq!(client.get_status_counts(), StatusCounts {
+        pending: 2,
+        active: 0,
+        claimed: 1,
+        refunded: 0,
+        failed: 1,
+    });
+    
+    // Fast forward past timelock
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock + 1;
+    });
+    
+    // Pending -> Refunded and Failed -> Refunded
+    client.refund_swap(&swap_ids.get(2).unwrap(), &None);
+    client.refund_swap(&swap_ids.get(1).unwrap(), &None);
+    assert_eq!(client.get_status_counts(), StatusCounts {
+        pending: 1,
+        active: 0,
+        claimed: 1,
+        refunded: 2,
+        failed: 0,
+    });
+}
+
+#[test]
+fn test_swap_rate_normalizes_decimals() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let counter_token = BytesN::from_array(&env, &[9u8; 20]);
+    
+    // 100 units of a 7-decimal asset for 0.05 units of an 18-decimal asset
+    let eth_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            counter_amount: Some(50_000_000_000_000_000i128),
+            counter_token: Some(counter_token.clone()),
+            counter_decimals: Some(18u32),
+            ..SwapOptions::default()
+        },
+    );
+    
+    // Rate of 2000 scaled by 1e7
+    assert_eq!(client.get_swap_rate(&eth_swap_id), Some(20_000_000_000));
+    let swap = client.get_swap_details(&eth_swap_id).unwrap();
+    assert_eq!(swap.counter_amount, Some(50_000_000_000_000_000));
+    assert_eq!(swap.counter_token, Some(counter_token.clone()));
+    assert_eq!(swap.counter_decimals, Some(18));
+    assert_eq!(swap.stellar_decimals, 7);
+    assert_eq!(swap.evm_decimals, 18);
+    
+    // 100 units of a 7-decimal asset for 50 units of a 6-decimal asset
+    let usdc_swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &1_000_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            counter_amount: Some(50_000_000i128),
+            counter_token: Some(counter_token),
+            counter_decimals: Some(6u32),
+            ..SwapOptions::default()
+        },
+    );
+    
+    // Rate of 2 scaled by 1e7
+    assert_eq!(client.get_swap_rate(&usdc_swap_id), Some(20_000_000));
+}
+
+#[test]
+fn test_swap_rate_validation() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let timelock = 7200u64;
+    let amount = 1_000_000i128;
+    let counter_token = BytesN::from_array(&env, &[9u8; 20]);
+    
+    // Counter amount must be positive
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            counter_amount: Some(0i128),
+            counter_token: Some(counter_token.clone()),
+            counter_decimals: Some(18u32),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    
+    // Counter fields must be provided together
+    let result = client.try_create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            counter_amount: Some(1_000i128),
+            counter_decimals: Some(18u32),
+            ..SwapOptions::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    
+    // Swaps without a counter asset have no rate
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &timelock,
+        &token,
+        &amount,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(client.get_swap_rate(&swap_id), None);
+    
+    // Decimals default to 7 on Stellar and 18 on the EVM side
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.stellar_decimals, 7);
+    assert_eq!(swap.evm_decimals, 18);
+    assert_eq!(client.get_swap_rate(&String::from_str(&env, "missing")), None);
+}
+
+#[test]
+fn test_convert_amount() {
+    let (env, _, _, _) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Up-scaling from 7 to 18 decimals
+    assert_eq!(client.convert_amount(&12_345_678i128, &7, &18), 1_234_567_800_000_000_000);
+    
+    // Down-scaling truncates toward zero
+    assert_eq!(client.convert_amount(&1_234_567_899_999_999_999i128, &18, &7), 12_345_678);
+    assert_eq!(client.convert_amount(&99_999_999_999i128, &18, &7), 0);
+    
+    // Equal decimals are unchanged
+    assert_eq!(client.convert_amount(&1_000i128, &7, &7), 1_000);
+    
+    // Overflowing results are rejected
+    let result = client.try_convert_amount(&i128::MAX, &7, &18);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    let result = client.try_convert_amount(&1i128, &0, &39);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+}
+
+#[test]
+fn test_get_all_swaps_paginated() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 50_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    let mut created = Vec::new(&env);
+    for _ in 0..50 {
+        created.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        ));
+    }
+    
+    // Page through in chunks of 20
+    let mut paged = Vec::new(&env);
+    let mut start = 0u32;
+    loop {
+        let page = client.get_all_swaps(&start, &20);
+        if page.is_empty() {
+            break;
+        }
+        assert!(page.len() <= 20);
+        paged.append(&page);
+        start += page.len();
+    }
+    assert_eq!(start, 50);
+    assert_eq!(paged, created);
+    
+    // Pages are bounded
+    let result = client.try_get_all_swaps(&0, &(MAX_PAGE_SIZE + 1));
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_create_swap_idempotent() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
+    
+    let create = |amount: i128| {
+        client.try_create_swap_idempotent(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    
+    let swap_id = create(1_000_000).unwrap().unwrap();
+    
+    // An exact retry returns the same swap without moving funds again
+    assert_eq!(create(1_000_000).unwrap().unwrap(), swap_id);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&sender), 2_000_000);
+    assert_eq!(token_client.balance(&contract_id), 1_000_000);
+    assert_eq!(client.get_contract_stats().total_swaps_created, 1);
+    
+    // A near-duplicate with a different amount fails
+    assert_eq!(create(999_999), Err(Ok(HTLCError::SwapAlreadyExists.into())));
+    
+    // A retry after the swap settled fails
+    client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(create(1_000_000), Err(Ok(HTLCError::SwapAlreadyExists.into())));
+    assert_eq!(token_client.balance(&sender), 2_000_000);
+}
+
+#[test]
+fn test_check_invariants_reports_corruption() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    assert!(client.check_invariants().is_empty());
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    client.claim_swap(&swap_id, &preimage, &None);
+    assert!(client.check_invariants().is_empty());
+    
+    // Deliberately corrupt the completed counter
+    env.as_contract(&contract_id, || {
+        set_total_swaps_completed(&env, 5);
+    });
+    
+    let violations = client.check_invariants();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations.get(0).unwrap(), String::from_str(&env, "settled_within_created"));
+}
+
+#[test]
+fn test_health_check() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    let tokens = soroban_sdk::vec![&env, token.clone()];
+    let healthy = HealthReport {
+        healthy: true,
+        status_counts_match: true,
+        open_count_matches: true,
+        counters_non_negative: true,
+        locked_covers_open_swaps: true,
+        solvent: true,
+    };
+    
+    // One settled and one open swap keep the books consistent
+    client.claim_swap(&create(), &preimage, &None);
+    create();
+    assert_eq!(client.health_check(&tokens), healthy);
+    
+    // A lost locked balance no longer covers the open swap
+    env.as_contract(&contract_id, || {
+        release_locked_balance(&env, &token, 400_000);
+    });
+    assert_eq!(client.health_check(&tokens), HealthReport {
+        healthy: false,
+        locked_covers_open_swaps: false,
+        ..healthy.clone()
+    });
+    
+    // Over-tracked funds the contract doesn't hold make it insolvent, and a
+    // drifted status count breaks the swap totals
+    env.as_contract(&contract_id, || {
+        add_locked_balance(&env, &token, 800_000);
+        set_status_count(&env, &SwapStatus::Pending, 2);
+    });
+    assert_eq!(client.health_check(&tokens), HealthReport {
+        healthy: false,
+        status_counts_match: false,
+        open_count_matches: false,
+        solvent: false,
+        ..healthy
+    });
+    
+    // Each call checks a bounded number of tokens
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_HEALTH_CHECK_TOKENS {
+        too_many.push_back(token.clone());
+    }
+    assert_eq!(client.try_health_check(&too_many), Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_swap_metadata_not_read_on_hot_path() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let memo = String::from_str(&env, "invoice-0000000000000000000000000000000000000000000000000000001");
+    
+    // Create a swap carrying the full set of cold metadata
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions {
+            counter_amount: Some(500_000_000_000_000_000i128),
+            counter_token: Some(BytesN::from_array(&env, &[7u8; 20])),
+            counter_decimals: Some(18u32),
+            memo: Some(memo.clone()),
+            ..SwapOptions::default()
+        },
+    );
+    
+    // Views still see the stitched record
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.memo, Some(memo.clone()));
+    assert_eq!(swap.eth_contract, eth_contract);
+    assert_eq!(swap.counter_decimals, Some(18));
+    let full_read_bytes = env.cost_estimate().resources().read_bytes;
+    
+    // The hot record alone is a fraction of the full swap
+    assert!(client.swap_exists(&swap_id));
+    let core_read_bytes = env.cost_estimate().resources().read_bytes;
+    assert!(core_read_bytes < full_read_bytes);
+    assert!(full_read_bytes - core_read_bytes > memo.len());
+    
+    // Claims only touch the hot record and leave the metadata intact
+    client.claim_swap(&swap_id, &preimage, &None);
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Claimed);
+    assert_eq!(swap.memo, Some(memo));
+    assert_eq!(swap.created_at, 0);
+}
+
+#[test]
+fn test_sweep_stray_tokens() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    // Lock funds in a swap
+    client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(client.get_locked_balance(&token), 1_000_000);
+    
+    // Tokens sent directly to the contract are not locked
+    let stray_sender = Address::generate(&env);
+    fund_account(&env, &token, &stray_sender, 250_000);
+    let token_client = token::Client::new(&env, &token);
+    token_client.transfer(&stray_sender, &contract_id, &250_000);
+    assert_eq!(token_client.balance(&contract_id), 1_250_000);
+    
+    // Sweeping into the locked funds fails
+    assert_eq!(
+        client.try_sweep_stray_tokens(&token, &250_001),
+        Err(Ok(HTLCError::InsufficientBalance.into()))
+    );
+    assert_eq!(
+        client.try_sweep_stray_tokens(&token, &0),
+        Err(Ok(HTLCError::InvalidAmount.into()))
+    );
+    
+    // Exactly the stray amount can be swept
+    client.sweep_stray_tokens(&token, &250_000);
+    assert_eq!(token_client.balance(&admin), 250_000);
+    assert_eq!(token_client.balance(&contract_id), 1_000_000);
+    assert_eq!(client.get_locked_balance(&token), 1_000_000);
+    
+    // Nothing is left to sweep
+    assert_eq!(
+        client.try_sweep_stray_tokens(&token, &1),
+        Err(Ok(HTLCError::InsufficientBalance.into()))
+    );
+}
+
+#[test]
+fn test_total_locked_per_token() {
+    let (env, admin, fee_recipient, token_a) = create_test_env();
+    let token_b = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    fund_account(&env, &token_a, &sender, 3_006_000);
+    fund_account(&env, &token_b, &sender, 500_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    let create = |token: &Address, amount: i128, fee_payer: FeePayer| {
+        client.create_swap(
+            &sender,
+            &Address::generate(&env),
+            &hashlock,
+            &7200u64,
+            token,
+            &amount,
+            &Address::generate(&env),
+            &11155111u64,
+            &SwapOptions {
+                fee_payer,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // Two fills of one order in token A, one swap in token B
+    let fill_a = create(&token_a, 1_000_000, FeePayer::Recipient);
+    let fill_b = create(&token_a, 2_000_000, FeePayer::Sender);
+    let swap_b = create(&token_b, 500_000, FeePayer::Recipient);
+    assert_eq!(client.get_total_locked(&token_a), 3_000_000);
+    assert_eq!(client.get_total_locked(&token_b), 500_000);
+    
+    // The prepaid fee is locked but not counted as principal
+    assert_eq!(client.get_locked_balance(&token_a), 3_006_000);
+    
+    // Claiming one fill releases only its own amount
+    client.claim_swap(&fill_a, &preimage, &None);
+    assert_eq!(client.get_total_locked(&token_a), 2_000_000);
+    assert_eq!(client.get_total_locked(&token_b), 500_000);
+    
+    // Refunds release the rest
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&fill_b, &None);
+    client.refund_swap(&swap_b, &None);
+    assert_eq!(client.get_total_locked(&token_a), 0);
+    assert_eq!(client.get_total_locked(&token_b), 0);
+    assert_eq!(token::Client::new(&env, &token_a).balance(&contract_id), 0);
+}
+
+#[test]
+fn test_resolver_active_swaps() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 10_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    assert_eq!(client.get_resolver_active_swaps(&resolver).len(), 0);
+    
+    let create = |resolver: Option<Address>| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // Attach the resolver to two swaps; a swap without a resolver isn't listed
+    let first = create(Some(resolver.clone()));
+    let second = create(Some(resolver.clone()));
+    create(None);
+    let active = client.get_resolver_active_swaps(&resolver);
+    assert_eq!(active.len(), 2);
+    assert_eq!(active.get(0).unwrap(), first);
+    assert_eq!(active.get(1).unwrap(), second);
+    
+    // Claiming one leaves only the other listed
+    client.claim_swap(&first, &preimage, &None);
+    let active = client.get_resolver_active_swaps(&resolver);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap(), second);
+    
+    // Refunding the last one clears the list
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&second, &None);
+    assert_eq!(client.get_resolver_active_swaps(&resolver).len(), 0);
+}
+
+#[test]
+fn test_claim_and_refund_error_paths() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let timelock = 7200u64;
+    
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    let claimed_id = create();
+    let refunded_id = create();
+    
+    // Unknown swaps
+    let unknown_id = String::from_str(&env, "unknown");
+    let result = client.try_claim_swap(&unknown_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotFound.into())));
+    let result = client.try_refund_swap(&unknown_id, &None);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotFound.into())));
+    
+    // Claim with a wrong preimage
+    let wrong_preimage = BytesN::from_array(&env, &[7u8; 32]);
+    let result = client.try_claim_swap(&claimed_id, &wrong_preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+    
+    // Refund before expiry
+    let result = client.try_refund_swap(&refunded_id, &None);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockNotExpired.into())));
+    
+    // Double claim, and refund of a claimed swap
+    client.claim_swap(&claimed_id, &preimage, &None);
+    let result = client.try_claim_swap(&claimed_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyClaimed.into())));
+    
+    env.ledger().with_mut(|li| {
+        li.timestamp = timelock;
+    });
+    let result = client.try_refund_swap(&claimed_id, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyClaimed.into())));
+    
+    // Claim after refund, and double refund
+    client.refund_swap(&refunded_id, &None);
+    let result = client.try_claim_swap(&refunded_id, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyRefunded.into())));
+    let result = client.try_refund_swap(&refunded_id, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyRefunded.into())));
+}
+
+#[test]
+fn test_create_swap_rejects_invalid_terms() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+    });
+    
+    let create = |timelock: u64, amount: i128| {
+        client.try_create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &timelock,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    
+    // Timelock at or below the minimum duration
+    let result = create(1_000 + MIN_TIMELOCK_DURATION, 1_000_000);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidTimelock.into())));
+    
+    // Timelock above the maximum duration
+    let result = create(1_000 + MAX_TIMELOCK_DURATION + 1, 1_000_000);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidTimelock.into())));
+    
+    // Non-positive amounts
+    let result = create(7200, 0);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    let result = create(7200, -1);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    
+    // Nothing was created
+    assert_eq!(client.get_contract_stats().total_swaps_created, 0);
+    assert_eq!(token::Client::new(&env, &token).balance(&sender), 1_000_000);
+    
+    // The boundaries themselves are accepted
+    assert!(create(1_000 + MIN_TIMELOCK_DURATION + 1, 500_000).is_ok());
+    assert!(create(1_000 + MAX_TIMELOCK_DURATION, 500_000).is_ok());
+}
+
+#[test]
+fn test_admin_calls_require_admin_auth() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Admin calls before initialization
+    let result = client.try_update_protocol_fee(&50);
+    assert_eq!(result, Err(Ok(HTLCError::NotInitialized.into())));
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // Drop the mocked authorizations so no admin signature is present
+    env.set_auths(&[]);
+    
+    let resolver = Address::generate(&env);
+    assert!(client.try_update_protocol_fee(&50).is_err());
+    assert!(client.try_set_permissioned_mode(&true).is_err());
+    assert!(client.try_block_address(&resolver).is_err());
+    assert!(client.try_register_resolver(&resolver, &token, &5_000_000i128).is_err());
+    assert!(client.try_sweep_stray_tokens(&token, &1i128).is_err());
+    
+    // Nothing changed
+    assert_eq!(client.get_contract_stats().protocol_fee_bps, 30);
+    assert!(!client.is_address_blocked(&resolver));
+    assert_eq!(client.get_resolver_info(&resolver), None);
+    
+    // The admin's authorization lets the call through
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "update_protocol_fee",
+            args: (50u32,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.update_protocol_fee(&50);
+    assert_eq!(client.get_contract_stats().protocol_fee_bps, 50);
+}
+
+#[test]
+fn test_get_swaps_created_between() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 50_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    // Two swaps at each of 1000, 2000, ..., 5000
+    let mut created = Vec::new(&env);
+    for timestamp in [1_000u64, 2_000, 3_000, 4_000, 5_000] {
+        env.ledger().with_mut(|li| {
+            li.timestamp = timestamp;
+        });
+        for _ in 0..2 {
+            created.push_back(client.create_swap(
+                &sender,
+                &recipient,
+                &hashlock,
+                &(timestamp + 7200),
+                &token,
+                &1_000_000i128,
+                &eth_contract,
+                &11155111u64,
+                &SwapOptions::default(),
+            ));
+        }
+    }
+    
+    // The window is inclusive on both ends
+    let window = client.get_swaps_created_between(&2_000, &4_000, &0, &MAX_PAGE_SIZE);
+    assert_eq!(window, created.slice(2..8));
+    
+    // Bounds between creation times
+    let window = client.get_swaps_created_between(&1_500, &3_500, &0, &MAX_PAGE_SIZE);
+    assert_eq!(window, created.slice(2..6));
+    
+    // Paging within the window
+    let page = client.get_swaps_created_between(&2_000, &4_000, &0, &4);
+    assert_eq!(page, created.slice(2..6));
+    let page = client.get_swaps_created_between(&2_000, &4_000, &4, &4);
+    assert_eq!(page, created.slice(6..8));
+    let page = client.get_swaps_created_between(&2_000, &4_000, &6, &4);
+    assert!(page.is_empty());
+    
+    // Empty and inverted windows
+    assert!(client.get_swaps_created_between(&5_001, &9_000, &0, &10).is_empty());
+    assert!(client.get_swaps_created_between(&4_000, &2_000, &0, &10).is_empty());
+    
+    // Pages are bounded
+    let result = client.try_get_swaps_created_between(&0, &9_000, &0, &(MAX_PAGE_SIZE + 1));
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_resolver_claim_nonces() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 10_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    assert_eq!(client.get_resolver_nonce(&resolver), 0);
+    
+    let create = |resolver: Option<Address>| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    let first = create(Some(resolver.clone()));
+    let second = create(Some(resolver.clone()));
+    let third = create(Some(resolver.clone()));
+    
+    // Sequential nonces succeed and pay the recipient
+    client.claim_swap_as_resolver(&first, &preimage, &None, &0);
+    assert_eq!(client.get_resolver_nonce(&resolver), 1);
+    client.claim_swap_as_resolver(&second, &preimage, &None, &1);
+    assert_eq!(client.get_resolver_nonce(&resolver), 2);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 1_994_000);
+    
+    // A replayed or skipped nonce fails
+    let result = client.try_claim_swap_as_resolver(&third, &preimage, &None, &1);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidResolverNonce.into())));
+    let result = client.try_claim_swap_as_resolver(&third, &preimage, &None, &3);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidResolverNonce.into())));
+    assert_eq!(client.get_swap_details(&third).unwrap().status, SwapStatus::Pending);
+    assert_eq!(client.get_resolver_nonce(&resolver), 2);
+    
+    // Swaps without a resolver can't be claimed through the resolver path
+    let unassigned = create(None);
+    let result = client.try_claim_swap_as_resolver(&unassigned, &preimage, &None, &2);
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
+    
+    // The resolver's authorization covers the nonce
+    env.mock_auths(&[MockAuth {
+        address: &resolver,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "claim_swap_as_resolver",
+            args: (third.clone(), preimage.clone(), 2u64).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.claim_swap_as_resolver(&third, &preimage, &None, &2);
+    assert_eq!(client.get_resolver_nonce(&resolver), 3);
+}
+
+#[test]
+fn test_auto_confirm_after() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    client.set_auto_confirm_after(&600);
+    
+    let mut swap_ids = Vec::new(&env);
+    for _ in 0..2 {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        ));
+    }
+    let early = swap_ids.get(0).unwrap();
+    let confirmed = swap_ids.get(1).unwrap();
+    
+    // Before the delay the swap is still pending and can't be claimed
+    env.ledger().with_mut(|li| li.timestamp = 599);
+    let result = client.try_claim_swap(&early, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotConfirmed.into())));
+    assert_eq!(client.get_swap_details(&early).unwrap().status, SwapStatus::Pending);
+    
+    // Once the delay has passed the claim confirms and settles the swap
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    client.claim_swap(&confirmed, &preimage, &None);
+    assert_eq!(client.get_swap_details(&confirmed).unwrap().status, SwapStatus::Claimed);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 997_000);
+    let counts = client.get_status_counts();
+    assert_eq!(counts.pending, 1);
+    assert_eq!(counts.active, 0);
+    assert_eq!(counts.claimed, 1);
+}
+
+#[test]
+fn test_renounce_admin() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let mut swap_ids = Vec::new(&env);
+    for _ in 0..2 {
+        swap_ids.push_back(client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        ));
+    }
+    
+    // A request left unconfirmed past the window has to be made again
+    assert!(!client.renounce_admin());
+    env.ledger().with_mut(|li| li.timestamp = RENOUNCE_CONFIRMATION_WINDOW + 1);
+    assert!(!client.renounce_admin());
+    assert!(!client.get_contract_stats().admin_renounced);
+    
+    // Confirming within the window clears the admin
+    env.ledger().with_mut(|li| li.timestamp += RENOUNCE_CONFIRMATION_WINDOW);
+    assert!(client.renounce_admin());
+    let stats = client.get_contract_stats();
+    assert_eq!(stats.admin, None);
+    assert!(stats.admin_renounced);
+    
+    // Every admin entrypoint is disabled for good
+    let renounced = Some(Ok(HTLCError::AdminRenounced.into()));
+    let resolver = Address::generate(&env);
+    let swap_id = swap_ids.get(0).unwrap();
+    assert_eq!(client.try_renounce_admin().err(), renounced);
+    assert_eq!(client.try_register_resolver(&resolver, &token, &5_000_000i128).err(), renounced);
+    assert_eq!(client.try_register_resolvers_batch(&Vec::new(&env)).err(), renounced);
+    assert_eq!(client.try_unregister_resolver(&resolver).err(), renounced);
+    assert_eq!(client.try_set_max_resolvers(&1).err(), renounced);
+    assert_eq!(client.try_set_staleness_window(&60).err(), renounced);
+    assert_eq!(client.try_set_claim_cutoff_buffer(&60).err(), renounced);
+    assert_eq!(client.try_set_auto_confirm_after(&60).err(), renounced);
+    assert_eq!(client.try_set_abandonment_policy(&60, &10).err(), renounced);
+    assert_eq!(client.try_set_refund_bounty(&10).err(), renounced);
+    assert_eq!(client.try_set_resolver_reward_rate(&10).err(), renounced);
+    assert_eq!(client.try_set_resolver_limit(&resolver, &1i128).err(), renounced);
+    assert_eq!(client.try_update_protocol_fee(&50).err(), renounced);
+    assert_eq!(client.try_set_min_fee(&token, &1i128).err(), renounced);
+    assert_eq!(client.try_set_fee_splits(&Vec::new(&env)).err(), renounced);
+    assert_eq!(client.try_update_fee_recipient(&resolver).err(), renounced);
+    assert_eq!(client.try_set_permissioned_mode(&true).err(), renounced);
+    assert_eq!(client.try_add_allowed_sender(&sender).err(), renounced);
+    assert_eq!(client.try_remove_allowed_sender(&sender).err(), renounced);
+    assert_eq!(client.try_set_recipient_registration(&true).err(), renounced);
+    assert_eq!(client.try_add_registered_recipient(&sender).err(), renounced);
+    assert_eq!(client.try_remove_registered_recipient(&sender).err(), renounced);
+    assert_eq!(client.try_set_permissioned_creation(&true).err(), renounced);
+    assert_eq!(client.try_add_allowed_creator(&sender).err(), renounced);
+    assert_eq!(client.try_remove_allowed_creator(&sender).err(), renounced);
+    assert_eq!(client.try_block_address(&resolver).err(), renounced);
+    assert_eq!(client.try_unblock_address(&resolver).err(), renounced);
+    assert_eq!(client.try_add_fee_exempt(&resolver).err(), renounced);
+    assert_eq!(client.try_remove_fee_exempt(&resolver).err(), renounced);
+    assert_eq!(client.try_set_fee_policy(&None).err(), renounced);
+    assert_eq!(client.try_set_price_oracle(&None, &0).err(), renounced);
+    assert_eq!(client.try_set_counterpart_oracle(&None).err(), renounced);
+    assert_eq!(client.try_set_chain_contract(&1u64, &eth_contract).err(), renounced);
+    assert_eq!(client.try_set_chain_finality_delay(&1u64, &600).err(), renounced);
+    assert_eq!(client.try_set_eth_attester(&BytesN::from_array(&env, &[1u8; 20])).err(), renounced);
+    assert_eq!(client.try_sweep_stray_tokens(&token, &1i128).err(), renounced);
+    assert_eq!(
+        client.try_mark_swap_failed(&swap_id, &FailureCode::Other, &String::from_str(&env, "x")).err(),
+        renounced
+    );
+    assert_eq!(client.try_relink_counterpart(&swap_id, &BytesN::from_array(&env, &[1u8; 32])).err(), renounced);
+    assert_eq!(client.try_recompute_stats(&MAX_STATS_BATCH_SIZE).err(), renounced);
+    assert_eq!(client.try_confirm_initialization().err(), renounced);
+    assert_eq!(client.try_set_collateral_ratio(&5_000).err(), renounced);
+    
+    // Nobody can take the contract over by initializing it again
+    let result = client.try_initialize(&resolver, &resolver, &0, &None);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyInitialized.into())));
+    
+    // The swap lifecycle keeps working
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    client.claim_swap(&swap_id, &preimage, &None);
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&swap_ids.get(1).unwrap(), &None);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 997_000);
+    assert_eq!(token::Client::new(&env, &token).balance(&sender), 1_000_000);
+    assert_eq!(client.get_contract_stats().total_swaps_completed, 1);
+}
+
+#[test]
+fn test_public_claim_window() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let create = |public_unlock: Option<u64>| {
+        client.try_create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                public_unlock,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // The public window must open before the timelock
+    assert_eq!(create(Some(7200)), Err(Ok(HTLCError::InvalidTimelock.into())));
+    let public = create(Some(3600)).unwrap().unwrap();
+    let private = create(None).unwrap().unwrap();
+    
+    // Drop the mocked authorizations so the caller is a third party
+    env.set_auths(&[]);
+    
+    // Before the window only the recipient may claim
+    env.ledger().with_mut(|li| li.timestamp = 3599);
+    assert!(client.try_claim_swap(&public, &preimage, &None).is_err());
+    assert_eq!(client.get_swap_details(&public).unwrap().status, SwapStatus::Pending);
+    
+    // Once it opens anyone may claim, and the funds still go to the recipient
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.claim_swap(&public, &preimage, &None);
+    assert_eq!(client.get_swap_details(&public).unwrap().status, SwapStatus::Claimed);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 997_000);
+    
+    // Swaps without a public window keep requiring the recipient
+    assert!(client.try_claim_swap(&private, &preimage, &None).is_err());
+    assert_eq!(client.get_swap_details(&private).unwrap().status, SwapStatus::Pending);
+}
+
+#[test]
+fn test_preauthorized_claim() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    let preauthorized = create();
+    let other = create();
+    
+    // The recipient consents once, up front
+    client.preauthorize_claim(&preauthorized);
+    assert!(client.is_claim_preauthorized(&preauthorized));
+    assert!(!client.is_claim_preauthorized(&other));
+    
+    // Drop the mocked authorizations so the caller is a relayer
+    env.set_auths(&[]);
+    
+    // A preimage that doesn't match is still rejected
+    let wrong = BytesN::from_array(&env, &[0u8; 32]);
+    let result = client.try_claim_swap(&preauthorized, &wrong, &None);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+    
+    // The relayer claims the preauthorized swap; funds go to the recipient
+    client.claim_swap(&preauthorized, &preimage, &None);
+    assert_eq!(client.get_swap_details(&preauthorized).unwrap().status, SwapStatus::Claimed);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 997_000);
+    
+    // Without preauthorization the relayer is rejected
+    assert!(client.try_claim_swap(&other, &preimage, &None).is_err());
+    assert_eq!(client.get_swap_details(&other).unwrap().status, SwapStatus::Pending);
+    
+    // Nor can the relayer preauthorize on the recipient's behalf
+    assert!(client.try_preauthorize_claim(&other).is_err());
+    assert!(!client.is_claim_preauthorized(&other));
+}
+
+#[test]
+fn test_claim_deadline() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    let create = |claim_deadline: Option<u64>| {
+        client.try_create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                claim_deadline,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // The deadline must fall after creation and no later than the timelock
+    assert_eq!(create(Some(7201)), Err(Ok(HTLCError::InvalidTimelock.into())));
+    assert_eq!(create(Some(0)), Err(Ok(HTLCError::InvalidTimelock.into())));
+    
+    // The deadline is announced and shown in the swap details
+    let claimed = create(Some(3600)).unwrap().unwrap();
+    let created: Vec<Val> = (String::from_str(&env, "swap_created"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &created)
+        .expect("creation event not emitted");
+    let data: Vec<Val> = data.into_val(&env);
+    let announced: Option<u64> = data.get(10).unwrap().into_val(&env);
+    assert_eq!(announced, Some(3600));
+    assert_eq!(client.get_swap_details(&claimed).unwrap().claim_deadline, Some(3600));
+    
+    let stale = create(Some(3600)).unwrap().unwrap();
+    let open_ended = create(None).unwrap().unwrap();
+    
+    // Claims are accepted up to the deadline
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.claim_swap(&claimed, &preimage, &None);
+    let result = client.try_refund_swap(&stale, &None);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockNotExpired.into())));
+    
+    // After it the swap can't be claimed but refunds before the timelock
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    let result = client.try_claim_swap(&stale, &preimage, &None);
+    assert_eq!(result, Err(Ok(HTLCError::ClaimWindowClosed.into())));
+    client.refund_swap(&stale, &None);
+    assert_eq!(client.get_swap_details(&stale).unwrap().status, SwapStatus::Refunded);
+    assert_eq!(token::Client::new(&env, &token).balance(&sender), 1_000_000);
+    
+    // Swaps without a deadline still wait for the timelock
+    let result = client.try_refund_swap(&open_ended, &None);
+    assert_eq!(result, Err(Ok(HTLCError::TimelockNotExpired.into())));
+    client.claim_swap(&open_ended, &preimage, &None);
+}
+
+#[test]
+fn test_user_swap_summaries() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 6_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    
+    // Three swaps with distinct recipients, amounts and timelocks
+    for (amount, timelock) in [(1_000_000i128, 7200u64), (2_000_000, 8000), (3_000_000, 9000)] {
+        client.create_swap(
+            &sender,
+            &Address::generate(&env),
+            &hashlock,
+            &timelock,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        );
+    }
+    let swap_ids = client.get_user_swaps(&sender);
+    client.claim_swap(&swap_ids.get(1).unwrap(), &preimage, &None);
+    
+    // Every summary matches its full record
+    let summaries = client.get_user_swap_summaries(&sender, &0, &10);
+    assert_eq!(summaries.len(), 3);
+    for (swap_id, summary) in swap_ids.iter().zip(summaries.iter()) {
+        let swap = client.get_swap_details(&swap_id).unwrap();
+        assert_eq!(summary, SwapSummary {
+            id: swap.id,
+            status: swap.status,
+            amount: swap.amount,
+            timelock: swap.timelock,
+            recipient: swap.recipient,
+        });
+    }
+    assert_eq!(summaries.get(1).unwrap().status, SwapStatus::Claimed);
+    
+    // Pages follow the user's swap order
+    let page = client.get_user_swap_summaries(&sender, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap(), summaries.get(1).unwrap());
+    assert_eq!(client.get_user_swap_summaries(&sender, &3, &10).len(), 0);
+    assert_eq!(client.get_user_swap_summaries(&Address::generate(&env), &0, &10).len(), 0);
+    
+    let result = client.try_get_user_swap_summaries(&sender, &0, &(MAX_PAGE_SIZE + 1));
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_refund_to_alternate_address() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    // An exchange funds swaps on behalf of its user
+    let exchange = Address::generate(&env);
+    let user = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &exchange, 3_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    let token_client = token::Client::new(&env, &token);
+    
+    let create = |refund_to: Option<Address>| {
+        client.create_swap(
+            &exchange,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                refund_to,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    let by_exchange = create(Some(user.clone()));
+    let by_user = create(Some(user.clone()));
+    let default = create(None);
+    assert_eq!(client.get_swap_details(&by_exchange).unwrap().refund_to, user);
+    assert_eq!(client.get_swap_details(&default).unwrap().refund_to, exchange);
+    
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    
+    // The exchange's refund lands with the user and names both addresses
+    client.refund_swap(&by_exchange, &None);
+    let refunded: Vec<Val> = (String::from_str(&env, "swap_refunded"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &refunded)
+        .expect("refund event not emitted");
+    let data: (String, Address, Address) = data.into_val(&env);
+    assert_eq!(data, (by_exchange.clone(), exchange.clone(), user.clone()));
+    assert_eq!(token_client.balance(&user), 1_000_000);
+    assert_eq!(token_client.balance(&exchange), 0);
+    
+    // The user can trigger the refund with only its own authorization
+    env.mock_auths(&[MockAuth {
+        address: &user,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "refund_swap",
+            args: (by_user.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.refund_swap(&by_user, &Some(user.clone()));
+    assert_eq!(token_client.balance(&user), 2_000_000);
+    
+    // Nobody else can, and swaps without a refund address pay the sender
+    env.mock_all_auths();
+    let result = client.try_refund_swap(&default, &Some(user.clone()));
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
+    client.refund_swap(&default, &None);
+    assert_eq!(token_client.balance(&exchange), 1_000_000);
+}
+
+#[test]
+fn test_fee_exempt_partners() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let partner = Address::generate(&env);
+    let regular = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &partner, 2_000_000);
+    fund_account(&env, &token, &regular, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let preimage_bytes = Bytes::from_array(&env, &preimage.to_array());
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage_bytes).into();
+    let token_client = token::Client::new(&env, &token);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    
+    let create = |sender: &Address, resolver: Option<Address>| {
+        client.create_swap(
+            sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    client.add_fee_exempt(&partner);
+    client.add_fee_exempt(&resolver);
+    assert!(client.is_fee_exempt(&partner));
+    assert!(!client.is_fee_exempt(&regular));
+    
+    // An exempt sender pays no fee
+    client.claim_swap(&create(&partner, None), &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), 1_000_000);
+    assert_eq!(token_client.balance(&fee_recipient), 0);
+    
+    // A regular sender pays the fee
+    client.claim_swap(&create(&regular, None), &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), 1_997_000);
+    assert_eq!(token_client.balance(&fee_recipient), 3_000);
+    
+    // An exempt resolver waives the fee for a regular sender
+    client.claim_swap(&create(&regular, Some(resolver.clone())), &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), 2_997_000);
+    assert_eq!(token_client.balance(&fee_recipient), 3_000);
+    
+    // Removing the exemption restores the fee
+    client.remove_fee_exempt(&partner);
+    assert!(!client.is_fee_exempt(&partner));
+    client.claim_swap(&create(&partner, None), &preimage, &None);
+    assert_eq!(token_client.balance(&fee_recipient), 6_000);
+}
+
+#[test]
+fn test_resolver_default_events() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let create = |resolver: Option<Address>| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    let assigned = create(Some(resolver.clone()));
+    let unassigned = create(None);
+    let failed = create(Some(resolver.clone()));
+    
+    let defaulted: Vec<Val> = (symbol_short!("res_dflt"), resolver.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let default_event = |env: &Env| {
+        env.events().all().iter()
+            .find(|(contract, topics, _)| contract == &contract_id && topics == &defaulted)
+            .map(|(_, _, data)| -> ResolverDefaultedEvent { data.into_val(env) })
+    };
+    
+    // A refunded swap names its resolver and counts as a default
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&assigned, &None);
+    let refunded: Vec<Val> = (symbol_short!("refunded"), assigned.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &refunded)
+        .expect("refund event not emitted");
+    let event: FundsRefundedEvent = data.into_val(&env);
+    assert_eq!(event, FundsRefundedEvent {
+        swap_id: assigned.clone(),
+        sender: sender.clone(),
+        amount: 1_000_000,
+        resolver: Some(resolver.clone()),
+        elapsed: 7100,
+    });
+    assert_eq!(default_event(&env), Some(ResolverDefaultedEvent {
+        swap_id: assigned.clone(),
+        resolver: resolver.clone(),
+        outcome: SwapStatus::Refunded,
+        elapsed: 7100,
+    }));
+    
+    // Swaps without a resolver never report a default
+    client.refund_swap(&unassigned, &None);
+    assert_eq!(default_event(&env), None);
+    
+    // A failed swap with a resolver reports one too
+    client.mark_swap_failed(&failed, &FailureCode::ResolverDefault, &String::from_str(&env, "no fill"));
+    assert_eq!(default_event(&env), Some(ResolverDefaultedEvent {
+        swap_id: failed.clone(),
+        resolver: resolver.clone(),
+        outcome: SwapStatus::Failed,
+        elapsed: 7100,
+    }));
+}
+
+#[test]
+fn test_order_group_progress() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 10_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    let order = BytesN::from_array(&env, &[9u8; 32]);
+    
+    let create = |amount: i128, order_group: Option<BytesN<32>>| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                order_group,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // One order filled by three swaps, plus an unrelated swap
+    let first = create(1_000_000, Some(order.clone()));
+    let second = create(2_000_000, Some(order.clone()));
+    create(500_000, None);
+    let third = create(3_000_000, Some(order.clone()));
+    
+    let mut expected = Vec::new(&env);
+    expected.push_back(first.clone());
+    expected.push_back(second.clone());
+    expected.push_back(third.clone());
+    assert_eq!(client.get_group_swaps(&order), expected);
+    assert_eq!(client.get_swap_details(&second).unwrap().order_group, Some(order.clone()));
+    assert_eq!(client.get_group_progress(&order), GroupProgress {
+        swaps: 3,
+        filled_swaps: 0,
+        total_amount: 6_000_000,
+        filled_amount: 0,
+    });
+    
+    // Claims fill the order; refunds leave it partially filled
+    client.claim_swap(&first, &preimage, &None);
+    client.claim_swap(&third, &preimage, &None);
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&second, &None);
+    assert_eq!(client.get_group_progress(&order), GroupProgress {
+        swaps: 3,
+        filled_swaps: 2,
+        total_amount: 6_000_000,
+        filled_amount: 4_000_000,
+    });
+    
+    // Unknown groups are empty
+    let unknown = BytesN::from_array(&env, &[0u8; 32]);
+    assert!(client.get_group_swaps(&unknown).is_empty());
+    assert_eq!(client.get_group_progress(&unknown).swaps, 0);
+}
+
+#[test]
+fn test_resolver_swaps_pagination() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 10_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    let create = |resolver: Option<Address>| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    let assigned = [
+        create(Some(resolver.clone())),
+        create(Some(resolver.clone())),
+        create(Some(resolver.clone())),
+        create(Some(resolver.clone())),
+    ];
+    create(None);
+    assert_eq!(client.get_resolver_open_count(&resolver), 4);
+    
+    // Pages cover the assigned swaps in order
+    let first_page = client.get_resolver_swaps(&resolver, &0, &3);
+    assert_eq!(first_page.len(), 3);
+    assert_eq!(first_page.get(0).unwrap(), assigned[0]);
+    let last_page = client.get_resolver_swaps(&resolver, &3, &3);
+    assert_eq!(last_page.len(), 1);
+    assert_eq!(last_page.get(0).unwrap(), assigned[3]);
+    assert!(client.get_resolver_swaps(&resolver, &10, &3).is_empty());
+    let result = client.try_get_resolver_swaps(&resolver, &0, &(MAX_PAGE_SIZE + 1));
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+    
+    // Completed swaps drop out of the list
+    client.claim_swap(&assigned[0], &preimage, &None);
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&assigned[2], &None);
+    assert_eq!(client.get_resolver_open_count(&resolver), 2);
+    let open = client.get_resolver_swaps(&resolver, &0, &10);
+    assert_eq!(open.len(), 2);
+    assert_eq!(open.get(0).unwrap(), assigned[1]);
+    assert_eq!(open.get(1).unwrap(), assigned[3]);
+}
+
+#[test]
+fn test_reassign_resolver() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let offline = Address::generate(&env);
+    let backup = Address::generate(&env);
+    client.register_resolver(&offline, &token, &5_000_000i128);
+    client.register_resolver(&backup, &token, &5_000_000i128);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver: Some(offline.clone()),
+                ..SwapOptions::default()
+            },
+        )
+    };
+    let pending = create();
+    let failed = create();
+    client.mark_swap_failed(&failed, &FailureCode::ResolverDefault, &String::from_str(&env, "offline"));
+    
+    // The sender moves the pending swap to the backup resolver
+    env.mock_auths(&[MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "reassign_resolver",
+            args: (pending.clone(), backup.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.reassign_resolver(&pending, &backup);
+    env.mock_all_auths();
+    assert_eq!(client.get_swap_details(&pending).unwrap().resolver, Some(backup.clone()));
+    assert_eq!(client.get_resolver_open_count(&offline), 1);
+    assert_eq!(client.get_resolver_info(&offline).unwrap().open_swaps, 1);
+    assert_eq!(client.get_resolver_swaps(&backup, &0, &10).get(0).unwrap(), pending.clone());
+    assert_eq!(client.get_resolver_info(&backup).unwrap().open_swaps, 1);
+    
+    // Unregistered resolvers can't take the swap
+    let result = client.try_reassign_resolver(&pending, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(HTLCError::ResolverNotActive.into())));
+    
+    // Swaps that left the pending state keep their resolver
+    let result = client.try_reassign_resolver(&failed, &backup);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotPending.into())));
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    client.refund_swap(&pending, &None);
+    let result = client.try_reassign_resolver(&pending, &offline);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotPending.into())));
+    assert_eq!(client.get_resolver_open_count(&backup), 0);
+}
+
+#[test]
+fn test_storage_ttl_bumps() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    
+    // The swap and every entry backing its funds and indexes get the full TTL
+    env.as_contract(&contract_id, || {
+        let keys: [Val; 7] = [
+            storage::SwapKey::Swap(swap_id.clone()).into_val(&env),
+            storage::SwapKey::SwapMeta(swap_id.clone()).into_val(&env),
+            storage::SwapKey::SwapByIndex(1).into_val(&env),
+            storage::SwapKey::CreatedAtByIndex(1).into_val(&env),
+            storage::UserKey::UserSwaps(sender.clone()).into_val(&env),
+            storage::TokenKey::LockedBalance(token.clone()).into_val(&env),
+            storage::TokenKey::TotalLocked(token.clone()).into_val(&env),
+        ];
+        for key in keys {
+            assert!(env.storage().persistent().get_ttl(&key) >= PERSISTENT_TTL_EXTEND_TO - 1);
+        }
+    });
+    
+    let policy = client.get_ttl_policy();
+    assert_eq!(policy.instance_extend_to, INSTANCE_TTL_EXTEND_TO);
+    assert_eq!(policy.persistent_extend_to, PERSISTENT_TTL_EXTEND_TO);
+    assert!(policy.persistent_extend_to > policy.instance_extend_to);
+    
+    // A keeper bumps the instance roughly every few weeks; the swap lives
+    // on its own TTL from creation
+    for _ in 0..3 {
+        client.bump_instance_ttl();
+        let instance_ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+        assert!(instance_ttl >= INSTANCE_TTL_THRESHOLD);
+        env.ledger().with_mut(|li| li.sequence_number += 25 * DAY_IN_LEDGERS);
+        assert_eq!(client.get_contract_stats().total_swaps_created, 1);
+        assert_eq!(client.get_contract_stats().admin, Some(admin.clone()));
+    }
+    
+    // 75 days on, well past the minimum entry lifetime, the swap and the
+    // balances and indexes behind it are still live
+    assert!(75 * DAY_IN_LEDGERS > env.ledger().get().min_persistent_entry_ttl);
     let swap = client.get_swap_details(&swap_id).unwrap();
-    assert_eq!(swap.status, SwapStatus::Failed);
+    assert_eq!(swap.status, SwapStatus::Pending);
+    assert_eq!(swap.amount, 1_000_000);
+    assert_eq!(client.get_all_swaps(&0, &10), soroban_sdk::vec![&env, swap_id.clone()]);
+    assert_eq!(client.get_user_swaps(&sender), soroban_sdk::vec![&env, swap_id.clone()]);
+    assert_eq!(client.get_locked_balance(&token), 1_000_000);
+}
+
+#[test]
+fn test_swap_watchers() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
     
-    // Contract stats should remain accurate
-    let stats = client.get_contract_stats();
-    assert_eq!(stats.total_swaps_created, 1);
-    assert_eq!(stats.total_swaps_completed, 0); // Failed swaps don't count as completed
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    
+    let create = |hashlock: u8| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &BytesN::from_array(&env, &[hashlock; 32]),
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    let watched = create(1);
+    let crowded = create(2);
+    
+    let wallet = Address::generate(&env);
+    let other_wallet = Address::generate(&env);
+    client.watch_swap(&watched, &wallet);
+    client.watch_swap(&watched, &other_wallet);
+    client.watch_swap(&watched, &wallet);
+    assert_eq!(client.get_swap_watchers(&watched).len(), 2);
+    
+    let unknown = String::from_str(&env, "unknown");
+    assert_eq!(
+        client.try_watch_swap(&unknown, &wallet),
+        Err(Ok(HTLCError::SwapNotFound.into()))
+    );
+    
+    // Each status change reaches the remaining watchers under their own topic
+    client.unwatch_swap(&watched, &other_wallet);
+    assert_eq!(client.get_swap_watchers(&watched).len(), 1);
+    let notifications = |env: &Env, watcher: &Address| {
+        let topics: Vec<Val> = (symbol_short!("swap_stat"), watcher.clone(), EVENT_SCHEMA_VERSION).into_val(env);
+        let mut found = Vec::new(env);
+        for (contract, event_topics, data) in env.events().all().iter() {
+            if contract == contract_id && event_topics == topics {
+                let event: SwapStatusChangedEvent = data.into_val(env);
+                found.push_back(event);
+            }
+        }
+        found
+    };
+    
+    client.mark_swap_failed(&watched, &FailureCode::Other, &String::from_str(&env, "stuck"));
+    let failed = notifications(&env, &wallet);
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed.get(0).unwrap(), SwapStatusChangedEvent {
+        swap_id: watched.clone(),
+        from: SwapStatus::Pending,
+        to: SwapStatus::Failed,
+    });
+    assert!(notifications(&env, &other_wallet).is_empty());
+    
+    client.refund_swap(&watched, &None);
+    let refunded = notifications(&env, &wallet);
+    assert_eq!(refunded.len(), 1);
+    assert_eq!(refunded.get(0).unwrap().from, SwapStatus::Failed);
+    assert_eq!(refunded.get(0).unwrap().to, SwapStatus::Refunded);
+    
+    // A swap takes at most MAX_SWAP_WATCHERS watchers
+    for _ in 0..MAX_SWAP_WATCHERS {
+        client.watch_swap(&crowded, &Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_watch_swap(&crowded, &wallet),
+        Err(Ok(HTLCError::TooManyWatchers.into()))
+    );
+    let first = client.get_swap_watchers(&crowded).get(0).unwrap();
+    client.unwatch_swap(&crowded, &first);
+    client.watch_swap(&crowded, &wallet);
+    assert_eq!(client.get_swap_watchers(&crowded).len(), MAX_SWAP_WATCHERS);
+}
+
+#[test]
+fn test_link_counterpart() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    let oracle = Address::generate(&env);
+    client.set_counterpart_oracle(&Some(oracle.clone()));
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver: Some(resolver.clone()),
+                ..SwapOptions::default()
+            },
+        )
+    };
+    let first = create();
+    let second = create();
+    let escrow = BytesN::from_array(&env, &[7u8; 32]);
+    let other_escrow = BytesN::from_array(&env, &[8u8; 32]);
+    
+    // Only the resolver or the oracle can vouch for a link
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_link_counterpart(&first, &escrow, &stranger),
+        Err(Ok(HTLCError::Unauthorized.into()))
+    );
+    
+    client.link_counterpart(&first, &escrow, &resolver);
+    let linked: Vec<Val> = (symbol_short!("linked"), first.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &linked)
+        .expect("link event not emitted");
+    let event: CounterpartLinkedEvent = data.into_val(&env);
+    assert_eq!(event, CounterpartLinkedEvent {
+        swap_id: first.clone(),
+        counterpart_id: escrow.clone(),
+        proof_source: resolver.clone(),
+    });
+    assert_eq!(client.get_swap_counterpart(&first), Some(escrow.clone()));
+    assert_eq!(client.get_swap_by_counterpart(&escrow), Some(first.clone()));
+    
+    // A link is one-time and an escrow belongs to a single swap
+    assert_eq!(
+        client.try_link_counterpart(&first, &other_escrow, &oracle),
+        Err(Ok(HTLCError::CounterpartAlreadyLinked.into()))
+    );
+    assert_eq!(
+        client.try_link_counterpart(&second, &escrow, &oracle),
+        Err(Ok(HTLCError::CounterpartAlreadyLinked.into()))
+    );
+    client.link_counterpart(&second, &other_escrow, &oracle);
+    assert_eq!(client.get_swap_by_counterpart(&other_escrow), Some(second.clone()));
+    
+    // The admin can move a link, freeing the previous escrow
+    let new_escrow = BytesN::from_array(&env, &[9u8; 32]);
+    client.relink_counterpart(&first, &new_escrow);
+    assert_eq!(client.get_swap_counterpart(&first), Some(new_escrow.clone()));
+    assert_eq!(client.get_swap_by_counterpart(&new_escrow), Some(first.clone()));
+    assert_eq!(client.get_swap_by_counterpart(&escrow), None);
+    assert_eq!(
+        client.try_relink_counterpart(&first, &other_escrow),
+        Err(Ok(HTLCError::CounterpartAlreadyLinked.into()))
+    );
+}
+
+#[test]
+fn test_resolver_reward_accrual() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    let token_client = token::Client::new(&env, &token);
+    
+    // Initialize contract with a 0.3% fee, a third of which rewards resolvers
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    client.set_resolver_reward_rate(&10);
+    assert_eq!(client.get_resolver_reward_rate(), 10);
+    assert_eq!(
+        client.try_set_resolver_reward_rate(&(MAX_PROTOCOL_FEE_BPS + 1)),
+        Err(Ok(HTLCError::InvalidFee.into()))
+    );
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 4_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    let create = |amount: i128, resolver: Option<Address>| {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &amount,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions {
+                resolver,
+                ..SwapOptions::default()
+            },
+        )
+    };
+    
+    // Two resolver swaps accrue rewards; a swap without a resolver does not
+    for swap_id in [
+        create(1_000_000, Some(resolver.clone())),
+        create(2_000_000, Some(resolver.clone())),
+        create(1_000_000, None),
+    ] {
+        client.claim_swap(&swap_id, &preimage, &None);
+    }
+    assert_eq!(client.get_resolver_info(&resolver).unwrap().total_volume, 3_000_000);
+    assert_eq!(client.get_reward_pool(&token), 3_000);
+    assert_eq!(client.get_resolver_rewards(&resolver, &token), 3_000);
+    assert_eq!(token_client.balance(&fee_recipient), 12_000 - 3_000);
+    assert_eq!(client.get_locked_balance(&token), 3_000);
+    
+    // The payout empties the pool and resets the accrued reward
+    assert_eq!(client.claim_resolver_rewards(&resolver, &token), 3_000);
+    assert_eq!(token_client.balance(&resolver), 3_000);
+    assert_eq!(client.get_resolver_rewards(&resolver, &token), 0);
+    assert_eq!(client.get_reward_pool(&token), 0);
+    assert_eq!(client.get_locked_balance(&token), 0);
+    assert_eq!(
+        client.try_claim_resolver_rewards(&resolver, &token),
+        Err(Ok(HTLCError::InsufficientBalance.into()))
+    );
+}
+
+#[test]
+fn test_resolver_reward_share() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    let token_client = token::Client::new(&env, &token);
+    
+    // Initialize contract with a 0.3% fee; the reward can't exceed it
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    assert_eq!(
+        client.try_set_resolver_reward_rate(&31),
+        Err(Ok(HTLCError::InvalidFee.into()))
+    );
+    
+    let resolver = Address::generate(&env);
+    client.register_resolver(&resolver, &token, &5_000_000i128);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    fund_account(&env, &token, &sender, 3_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    
+    // (reward rate, resolver reward, fee recipient share) of a 3,000 fee
+    let mut accrued = 0;
+    for (reward_bps, reward, fee_share) in [(0u32, 0i128, 3_000i128), (15, 1_500, 1_500), (30, 3_000, 0)] {
+        client.set_resolver_reward_rate(&reward_bps);
+        let swap_id = client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &7200u64,
+            &token,
+            &1_000_000i128,
+            &Address::generate(&env),
+            &11155111u64,
+            &SwapOptions {
+                resolver: Some(resolver.clone()),
+                ..SwapOptions::default()
+            },
+        );
+        let fee_balance = token_client.balance(&fee_recipient);
+        client.claim_swap(&swap_id, &preimage, &None);
+        
+        let expected_topics: Vec<Val> = (String::from_str(&env, "resolver_rewarded"), EVENT_SCHEMA_VERSION).into_val(&env);
+        let event = env.events().all().iter()
+            .find(|(contract, topics, _)| contract == &contract_id && topics == &expected_topics)
+            .map(|(_, _, data)| -> (String, Address, Address, i128) { data.into_val(&env) });
+        assert_eq!(event, (reward > 0).then(|| (swap_id.clone(), resolver.clone(), token.clone(), reward)));
+        
+        accrued += reward;
+        assert_eq!(client.get_swap_details(&swap_id).unwrap().resolver_reward, reward);
+        assert_eq!(token_client.balance(&fee_recipient) - fee_balance, fee_share);
+        assert_eq!(client.get_resolver_rewards(&resolver, &token), accrued);
+    }
+    
+    assert_eq!(client.get_reward_pool(&token), 4_500);
+    assert_eq!(client.claim_resolver_rewards(&resolver, &token), 4_500);
+}
+
+#[test]
+fn test_max_resolvers() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    client.set_max_resolvers(&2);
+    assert_eq!(client.get_max_resolvers(), 2);
+    
+    // Registrations fill the cap; re-registering keeps the same slot
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    client.register_resolver(&first, &token, &1_000i128);
+    client.register_resolver(&second, &token, &1_000i128);
+    client.register_resolver(&second, &token, &2_000i128);
+    assert_eq!(client.get_resolver_count(), 2);
+    assert_eq!(
+        client.try_register_resolver(&third, &token, &1_000i128),
+        Err(Ok(HTLCError::ResolverLimitReached.into()))
+    );
+    
+    // A resolver holding collateral can't be unregistered
+    fund_account(&env, &token, &first, 1_000);
+    client.add_collateral(&first, &1_000);
+    assert_eq!(
+        client.try_unregister_resolver(&first),
+        Err(Ok(HTLCError::ResolverInUse.into()))
+    );
+    
+    // Unregistering frees a slot for the next resolver
+    client.unregister_resolver(&second);
+    assert!(client.get_resolver_info(&second).is_none());
+    assert_eq!(client.get_resolver_count(), 1);
+    client.register_resolver(&third, &token, &1_000i128);
+    assert_eq!(client.get_resolver_count(), 2);
+    
+    // Zero lifts the cap
+    client.set_max_resolvers(&0);
+    client.register_resolver(&second, &token, &1_000i128);
+    assert_eq!(client.get_resolver_count(), 3);
+}
+
+#[test]
+fn test_chain_finality_delay() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    client.set_chain_finality_delay(&11155111u64, &600);
+    assert_eq!(client.get_chain_finality_delay(&11155111u64), 600);
+    assert_eq!(client.get_chain_finality_delay(&1u64), 0);
+    assert_eq!(
+        client.try_set_chain_finality_delay(&1u64, &MIN_TIMELOCK_DURATION),
+        Err(Ok(HTLCError::InvalidTimelock.into()))
+    );
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 2_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let create = || {
+        client.create_swap(
+            &sender,
+            &recipient,
+            &hashlock,
+            &8200u64,
+            &token,
+            &1_000_000i128,
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        )
+    };
+    let claimed = create();
+    let created: Vec<Val> = (String::from_str(&env, "swap_created"), EVENT_SCHEMA_VERSION).into_val(&env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &contract_id && topics == &created)
+        .expect("creation event not emitted");
+    let data: Vec<Val> = data.into_val(&env);
+    let announced: u64 = data.get(13).unwrap().into_val(&env);
+    assert_eq!(announced, 600);
+    let refunded = create();
+    
+    // Claims wait out the delay after creation
+    env.ledger().with_mut(|li| li.timestamp = 1_599);
+    assert_eq!(
+        client.try_claim_swap(&claimed, &preimage, &None),
+        Err(Ok(HTLCError::FinalityDelayActive.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_601);
+    client.claim_swap(&claimed, &preimage, &None);
+    assert_eq!(client.get_swap_details(&claimed).unwrap().status, SwapStatus::Claimed);
+    
+    // Refunds still open at the timelock
+    env.ledger().with_mut(|li| li.timestamp = 8_200);
+    client.refund_swap(&refunded, &None);
+    assert_eq!(client.get_swap_details(&refunded).unwrap().status, SwapStatus::Refunded);
+}
+
+#[test]
+fn test_swap_view() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &8200u64,
+        &token,
+        &1_000_000i128,
+        &eth_contract,
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    assert_eq!(client.get_swap_view(&String::from_str(&env, "unknown")), None);
+    
+    // An hour in, the swap is claimable net of the 0.3% fee
+    env.ledger().with_mut(|li| li.timestamp = 4_600);
+    let view = client.get_swap_view(&swap_id).unwrap();
+    assert_eq!(view.swap, client.get_swap_details(&swap_id).unwrap());
+    assert_eq!(view.protocol_fee, 3_000);
+    assert_eq!(view.net_amount, 997_000);
+    assert_eq!(view.seconds_to_timelock, 3_600);
+    assert!(view.is_claimable);
+    assert!(!view.is_refundable);
+    
+    // The fee follows the current settings until the claim
+    client.update_protocol_fee(&50);
+    let view = client.get_swap_view(&swap_id).unwrap();
+    assert_eq!((view.protocol_fee, view.net_amount), (5_000, 995_000));
+    
+    // Inside the cutoff buffer neither side can settle
+    env.ledger().with_mut(|li| li.timestamp = 8_000);
+    let view = client.get_swap_view(&swap_id).unwrap();
+    assert_eq!(view.seconds_to_timelock, 200);
+    assert!(!view.is_claimable);
+    assert!(!view.is_refundable);
+    
+    // Past the timelock only the refund remains
+    env.ledger().with_mut(|li| li.timestamp = 9_000);
+    let view = client.get_swap_view(&swap_id).unwrap();
+    assert_eq!(view.seconds_to_timelock, 0);
+    assert!(!view.is_claimable);
+    assert!(view.is_refundable);
+    
+    client.refund_swap(&swap_id, &None);
+    assert!(!client.get_swap_view(&swap_id).unwrap().is_refundable);
+}
+
+#[test]
+fn test_swaps_by_index() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &sender, 10_000_000);
+    
+    let mut swap_ids = Vec::new(&env);
+    for i in 1..=10u8 {
+        let swap_id = client.create_swap(
+            &sender,
+            &recipient,
+            &BytesN::from_array(&env, &[i; 32]),
+            &7200u64,
+            &token,
+            &(i as i128 * 100_000),
+            &eth_contract,
+            &11155111u64,
+            &SwapOptions::default(),
+        );
+        swap_ids.push_back(swap_id);
+    }
+    
+    // Indexes are the swap counter at creation, starting at 1
+    assert_eq!(client.get_swap_id_by_index(&1), swap_ids.get(0));
+    assert_eq!(client.get_swap_id_by_index(&10), swap_ids.get(9));
+    assert_eq!(client.get_swap_id_by_index(&0), None);
+    assert_eq!(client.get_swap_id_by_index(&11), None);
+    
+    // Walking the whole range summarizes every swap in order
+    let all = client.get_swaps_range(&1, &10);
+    assert_eq!(all.len(), 10);
+    for (i, summary) in all.iter().enumerate() {
+        assert_eq!(summary.id, swap_ids.get(i as u32).unwrap());
+        assert_eq!(summary.amount, (i as i128 + 1) * 100_000);
+        assert_eq!(summary.status, SwapStatus::Pending);
+    }
+    let middle = client.get_swaps_range(&4, &6);
+    assert_eq!(middle.len(), 3);
+    assert_eq!(middle.get(0).unwrap().id, swap_ids.get(3).unwrap());
+    
+    // Out-of-bounds indexes are skipped
+    assert_eq!(client.get_swaps_range(&8, &20).len(), 3);
+    assert_eq!(client.get_swaps_range(&0, &2).len(), 2);
+    assert!(client.get_swaps_range(&11, &20).is_empty());
+    assert!(client.get_swaps_range(&5, &4).is_empty());
+    assert!(client.get_swaps_range(&u64::MAX, &u64::MAX).is_empty());
+    assert_eq!(client.get_swaps_range(&1, &MAX_RANGE_SIZE).len(), 10);
+    assert_eq!(
+        client.try_get_swaps_range(&1, &(MAX_RANGE_SIZE + 1)),
+        Err(Ok(HTLCError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_swap_templates() {
+    let (env, admin, fee_recipient, token) = create_test_env();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    
+    // Initialize contract
+    client.initialize(&admin, &fee_recipient, &30, &None);
+    
+    let maker = Address::generate(&env);
+    let other = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+    fund_account(&env, &token, &maker, 2_000_000);
+    fund_account(&env, &token, &other, 1_000_000);
+    let hashlock = BytesN::from_array(&env, &[1u8; 32]);
+    
+    let template = SwapTemplate {
+        token: token.clone(),
+        eth_chain_id: 11155111,
+        eth_contract: eth_contract.clone(),
+        resolver: None,
+        timelock_offset: 7200,
+        fee_payer: FeePayer::Sender,
+        is_public: false,
+    };
+    
+    // Offsets that can't yield a valid timelock are rejected
+    let result = client.try_create_template(&maker, &SwapTemplate { timelock_offset: 60, ..template.clone() });
+    assert_eq!(result, Err(Ok(HTLCError::InvalidTimelock.into())));
+    
+    let private_id = client.create_template(&maker, &template);
+    let public_id = client.create_template(&maker, &SwapTemplate { is_public: true, ..template.clone() });
+    assert_ne!(private_id, public_id);
+    assert_eq!(client.get_template(&private_id), Some(TemplateInfo { owner: maker.clone(), template: template.clone() }));
+    
+    // The owner fills in only the per-swap fields
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let swap_id = client.create_swap_from_template(&maker, &private_id, &recipient, &hashlock, &1_000_000i128);
+    let swap = client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.sender, maker);
+    assert_eq!(swap.recipient, recipient);
+    assert_eq!(swap.token, token);
+    assert_eq!(swap.amount, 1_000_000);
+    assert_eq!(swap.timelock, 8_200);
+    assert_eq!(swap.eth_contract, eth_contract);
+    assert_eq!(swap.eth_chain_id, 11155111);
+    assert_eq!(swap.fee_payer, FeePayer::Sender);
+    assert_eq!(swap.prepaid_fee, 3_000);
+    
+    // Someone else's private template can't be used
+    let result = client.try_create_swap_from_template(&other, &private_id, &recipient, &hashlock, &900_000i128);
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
+    
+    // A public template can
+    let swap_id = client.create_swap_from_template(&other, &public_id, &recipient, &hashlock, &900_000i128);
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().sender, other);
+    
+    let result = client.try_create_swap_from_template(&maker, &99, &recipient, &hashlock, &1i128);
+    assert_eq!(result, Err(Ok(HTLCError::TemplateNotFound.into())));
 }
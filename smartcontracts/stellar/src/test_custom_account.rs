@@ -0,0 +1,187 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use crate::test_fixtures::{self, Fixture, SwapTerms, SWAP_AMOUNT};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl,
+    crypto::Hash,
+    symbol_short,
+    testutils::Address as _,
+    xdr::{
+        InvokeContractArgs, ScAddress, ScSymbol, ScVal, SorobanAddressCredentials,
+        SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+        SorobanCredentials, VecM,
+    },
+    Address, BytesN, Env, TryFromVal, Val,
+};
+
+/// Minimal custom account used as a swap recipient
+///
+/// Authorizes any payload when the signature equals the key it was
+/// constructed with.
+#[contract]
+pub struct TestCustomAccount;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TestAccountError {
+    BadSignature = 1,
+}
+
+#[contractimpl]
+impl TestCustomAccount {
+    pub fn __constructor(env: Env, key: BytesN<32>) {
+        env.storage().instance().set(&symbol_short!("key"), &key);
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for TestCustomAccount {
+    type Signature = BytesN<32>;
+    type Error = TestAccountError;
+
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        _signature_payload: Hash<32>,
+        signature: BytesN<32>,
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), TestAccountError> {
+        let key: BytesN<32> = env.storage().instance().get(&symbol_short!("key")).unwrap();
+        if signature != key {
+            return Err(TestAccountError::BadSignature);
+        }
+        Ok(())
+    }
+}
+
+fn to_sc_val<T: IntoVal<Env, Val>>(env: &Env, value: T) -> ScVal {
+    ScVal::try_from_val(env, &value.into_val(env)).unwrap()
+}
+
+/// Build a signed authorization entry for `claim_swap` on behalf of `account`
+fn claim_auth_entry(
+    env: &Env,
+    account: &Address,
+    contract_id: &Address,
+    swap_id: &String,
+    preimage: &BytesN<32>,
+    signature: &BytesN<32>,
+) -> SorobanAuthorizationEntry {
+    SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::from(account),
+            nonce: 1,
+            signature_expiration_ledger: env.ledger().sequence() + 100,
+            signature: to_sc_val(env, signature.clone()),
+        }),
+        root_invocation: SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                contract_address: ScAddress::from(contract_id),
+                function_name: ScSymbol("claim_swap".try_into().unwrap()),
+                args: std::vec![
+                    to_sc_val(env, swap_id.clone()),
+                    to_sc_val(env, preimage.clone()),
+                ]
+                .try_into()
+                .unwrap(),
+            }),
+            sub_invocations: VecM::default(),
+        },
+    }
+}
+
+struct CustomAccountSetup {
+    fixture: Fixture,
+    account: Address,
+    account_key: BytesN<32>,
+}
+
+fn setup() -> CustomAccountSetup {
+    let fixture = test_fixtures::setup();
+    let account_key = BytesN::from_array(&fixture.env, &[9u8; 32]);
+    let account = fixture.env.register(TestCustomAccount, (account_key.clone(),));
+
+    CustomAccountSetup { fixture, account, account_key }
+}
+
+/// Create a default swap from a freshly funded sender to `recipient`
+fn create_swap_to(s: &Fixture, recipient: &Address) -> String {
+    let sender = Address::generate(&s.env);
+    s.mint(&sender, SWAP_AMOUNT);
+    s.create_swap(&SwapTerms { sender, recipient: recipient.clone(), ..s.swap() })
+}
+
+#[test]
+fn test_custom_account_recipient_claims_with_valid_signature() {
+    let CustomAccountSetup { fixture: s, account, account_key } = setup();
+    let env = &s.env;
+    let preimage = s.preimage();
+    let swap_id = create_swap_to(&s, &account);
+
+    // Claim with real auth: the recipient contract's __check_auth is invoked
+    env.set_auths(&[claim_auth_entry(
+        env,
+        &account,
+        &s.contract_id,
+        &swap_id,
+        &preimage,
+        &account_key,
+    )]);
+    s.client.claim_swap(&swap_id, &preimage, &None);
+
+    let swap = s.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Claimed);
+    assert_eq!(s.token_client().balance(&account), 997_000);
+}
+
+#[test]
+fn test_custom_account_recipient_rejects_invalid_signature() {
+    let CustomAccountSetup { fixture: s, account, .. } = setup();
+    let env = &s.env;
+    let preimage = s.preimage();
+    let swap_id = create_swap_to(&s, &account);
+
+    let wrong_key = BytesN::from_array(env, &[1u8; 32]);
+    env.set_auths(&[claim_auth_entry(
+        env,
+        &account,
+        &s.contract_id,
+        &swap_id,
+        &preimage,
+        &wrong_key,
+    )]);
+    assert!(s.client.try_claim_swap(&swap_id, &preimage, &None).is_err());
+
+    let swap = s.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Pending);
+}
+
+#[test]
+fn test_custom_account_authorization_cannot_be_replayed_across_swaps() {
+    let CustomAccountSetup { fixture: s, account, account_key } = setup();
+    let env = &s.env;
+    let preimage = s.preimage();
+    let first_swap_id = create_swap_to(&s, &account);
+
+    // Same hashlock and recipient, but a distinct swap
+    let second_swap_id = create_swap_to(&s, &account);
+    assert_ne!(first_swap_id, second_swap_id);
+
+    // A valid signature scoped to the first swap does not authorize the second
+    env.set_auths(&[claim_auth_entry(
+        env,
+        &account,
+        &s.contract_id,
+        &first_swap_id,
+        &preimage,
+        &account_key,
+    )]);
+    assert!(s.client.try_claim_swap(&second_swap_id, &preimage, &None).is_err());
+
+    let swap = s.client.get_swap_details(&second_swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Pending);
+}
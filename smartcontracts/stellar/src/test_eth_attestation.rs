@@ -0,0 +1,366 @@
+#![cfg(test)]
+
+use super::*;
+use crate::test_fixtures::{self, Fixture, SWAP_AMOUNT};
+use k256::ecdsa::SigningKey;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal,
+};
+
+struct AttestationSetup {
+    env: Env,
+    client: StellarHTLCClient<'static>,
+    swap_id: String,
+    preimage: BytesN<32>,
+    attester_key: SigningKey,
+}
+
+fn eth_address(env: &Env, key: &SigningKey) -> BytesN<20> {
+    let point = key.verifying_key().to_encoded_point(false);
+    let hash = env.crypto().keccak256(&Bytes::from_slice(env, &point.as_bytes()[1..])).to_array();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    BytesN::from_array(env, &address)
+}
+
+fn setup() -> AttestationSetup {
+    let s = test_fixtures::setup();
+    let attester_key = SigningKey::from_slice(&[5u8; 32]).unwrap();
+    s.client.set_eth_attester(&eth_address(&s.env, &attester_key));
+
+    s.mint(&s.sender, SWAP_AMOUNT);
+    let swap_id = s.create_swap(&s.swap());
+    let preimage = s.preimage();
+
+    let Fixture { env, client, .. } = s;
+    AttestationSetup { env, client, swap_id, preimage, attester_key }
+}
+
+/// Sign an attestation payload, returning (payload, signature, recovery_id)
+fn sign(env: &Env, key: &SigningKey, payload: &EthAttestationPayload) -> (Bytes, BytesN<64>, u32) {
+    let payload = payload.clone().to_xdr(env);
+    let digest = env.crypto().keccak256(&payload).to_array();
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+    let signature: [u8; 64] = signature.to_bytes().into();
+    (payload, BytesN::from_array(env, &signature), recovery_id.to_byte() as u32)
+}
+
+#[test]
+fn test_funded_attestation_activates_swap() {
+    let setup = setup();
+    let env = &setup.env;
+    let eth_tx_hash = BytesN::from_array(env, &[3u8; 32]);
+
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: eth_tx_hash.clone(),
+    });
+    setup.client.submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+
+    let swap = setup.client.get_swap_details(&setup.swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Active);
+    let attestation = setup.client.get_eth_attestation(&setup.swap_id).unwrap();
+    assert_eq!(attestation.event, CounterpartEvent::Funded);
+    assert_eq!(attestation.eth_tx_hash, eth_tx_hash);
+    assert_eq!(attestation.attester, eth_address(env, &setup.attester_key));
+    assert_eq!(setup.client.get_status_counts().active, 1);
+
+    // A confirmed swap can no longer be reassigned
+    let resolver = Address::generate(env);
+    setup.client.register_resolver(&resolver, &swap.token, &5_000_000i128);
+    let result = setup.client.try_reassign_resolver(&setup.swap_id, &resolver);
+    assert_eq!(result, Err(Ok(HTLCError::SwapNotPending.into())));
+}
+
+#[test]
+fn test_claimed_attestation_records_preimage() {
+    let setup = setup();
+    let env = &setup.env;
+
+    // A preimage that doesn't match the hashlock is rejected
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Claimed,
+        preimage: Some(BytesN::from_array(env, &[0u8; 32])),
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Claimed,
+        preimage: Some(setup.preimage.clone()),
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    setup.client.submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+
+    let attestation = setup.client.get_eth_attestation(&setup.swap_id).unwrap();
+    assert_eq!(attestation.event, CounterpartEvent::Claimed);
+    assert_eq!(attestation.preimage, Some(setup.preimage.clone()));
+}
+
+#[test]
+fn test_attestation_from_unknown_signer_rejected() {
+    let setup = setup();
+    let env = &setup.env;
+    let impostor = SigningKey::from_slice(&[6u8; 32]).unwrap();
+
+    let (payload, signature, recovery_id) = sign(env, &impostor, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAttestation.into())));
+
+    let swap = setup.client.get_swap_details(&setup.swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Pending);
+    assert_eq!(setup.client.get_eth_attestation(&setup.swap_id), None);
+}
+
+#[test]
+fn test_attestation_for_other_swap_rejected() {
+    let setup = setup();
+    let env = &setup.env;
+
+    // Valid signature, but the payload names a different swap
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: String::from_str(env, "other_swap"),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAttestation.into())));
+}
+
+#[test]
+fn test_attestation_for_other_contract_rejected() {
+    let setup = setup();
+    let env = &setup.env;
+
+    // Valid signature, but addressed to another deployment
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: Address::generate(env),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAttestation.into())));
+}
+
+#[test]
+fn test_stale_attestations_rejected() {
+    let setup = setup();
+    let env = &setup.env;
+
+    let (funded, funded_signature, funded_recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    setup.client.submit_eth_attestation(&setup.swap_id, &funded, &funded_signature, &funded_recovery_id);
+
+    // Resubmitting the same attestation is a replay
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &funded, &funded_signature, &funded_recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAttestation.into())));
+
+    let (refunded, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Refunded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[4u8; 32]),
+    });
+    setup.client.submit_eth_attestation(&setup.swap_id, &refunded, &signature, &recovery_id);
+
+    // The earlier funded observation can't overwrite the refund
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &funded, &funded_signature, &funded_recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAttestation.into())));
+    let attestation = setup.client.get_eth_attestation(&setup.swap_id).unwrap();
+    assert_eq!(attestation.event, CounterpartEvent::Refunded);
+}
+
+#[test]
+fn test_refunded_attestation_allows_early_refund() {
+    let setup = setup();
+    let env = &setup.env;
+    let swap = setup.client.get_swap_details(&setup.swap_id).unwrap();
+
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Refunded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    setup.client.submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+
+    // Refundable well before the timelock
+    assert!(env.ledger().timestamp() < swap.timelock);
+    setup.client.refund_swap(&setup.swap_id, &None);
+    let swap = setup.client.get_swap_details(&setup.swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Refunded);
+    assert_eq!(token::Client::new(env, &swap.token).balance(&swap.refund_to), 1_000_000);
+
+    // Nothing more can be attested on a settled swap
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[4u8; 32]),
+    });
+    let result = setup.client.try_submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+    assert_eq!(result, Err(Ok(HTLCError::AlreadyRefunded.into())));
+}
+
+#[test]
+fn test_cancel_swaps_batch() {
+    let setup = setup();
+    let env = &setup.env;
+    let active = setup.client.get_swap_details(&setup.swap_id).unwrap();
+    let sender = active.sender.clone();
+    let token_client = token::Client::new(env, &active.token);
+
+    // Confirm the setup swap on Ethereum so it is no longer cancellable
+    let (payload, signature, recovery_id) = sign(env, &setup.attester_key, &EthAttestationPayload {
+        contract: setup.client.address.clone(),
+        swap_id: setup.swap_id.clone(),
+        event: CounterpartEvent::Funded,
+        preimage: None,
+        eth_tx_hash: BytesN::from_array(env, &[3u8; 32]),
+    });
+    setup.client.submit_eth_attestation(&setup.swap_id, &payload, &signature, &recovery_id);
+
+    // Two fresh pending swaps from the same sender, and one from another
+    token::StellarAssetClient::new(env, &active.token).mint(&sender, &300_000);
+    let other_sender = Address::generate(env);
+    token::StellarAssetClient::new(env, &active.token).mint(&other_sender, &100_000);
+    let mut pending = [String::from_str(env, ""), String::from_str(env, ""), String::from_str(env, "")];
+    for (i, owner) in [&sender, &sender, &other_sender].into_iter().enumerate() {
+        pending[i] = setup.client.create_swap(
+            owner,
+            &Address::generate(env),
+            &BytesN::from_array(env, &[i as u8 + 1; 32]),
+            &7200u64,
+            &active.token,
+            &100_000i128,
+            &Address::generate(env),
+            &11155111u64,
+            &SwapOptions::default(),
+        );
+    }
+    assert_eq!(token_client.balance(&sender), 100_000);
+
+    let swap_ids = soroban_sdk::vec![
+        env,
+        pending[0].clone(),
+        setup.swap_id.clone(),
+        String::from_str(env, "missing"),
+        pending[2].clone(),
+        pending[1].clone(),
+    ];
+    let outcomes = setup.client.cancel_swaps_batch(&sender, &swap_ids);
+    assert_eq!(outcomes, soroban_sdk::vec![
+        env,
+        CancelOutcome::Cancelled,
+        CancelOutcome::NotCancellable,
+        CancelOutcome::NotFound,
+        CancelOutcome::NotSender,
+        CancelOutcome::Cancelled,
+    ]);
+
+    assert_eq!(token_client.balance(&sender), 300_000);
+    assert_eq!(setup.client.get_swap_details(&pending[0]).unwrap().status, SwapStatus::Refunded);
+    assert_eq!(setup.client.get_swap_details(&pending[1]).unwrap().status, SwapStatus::Refunded);
+    assert_eq!(setup.client.get_swap_details(&pending[2]).unwrap().status, SwapStatus::Pending);
+    assert_eq!(setup.client.get_swap_details(&setup.swap_id).unwrap().status, SwapStatus::Active);
+
+    // Already-cancelled swaps are skipped on a second pass
+    let outcomes = setup.client.cancel_swaps_batch(&sender, &soroban_sdk::vec![env, pending[0].clone()]);
+    assert_eq!(outcomes, soroban_sdk::vec![env, CancelOutcome::NotCancellable]);
+
+    let mut too_many = Vec::new(env);
+    for _ in 0..=MAX_CANCEL_BATCH_SIZE {
+        too_many.push_back(pending[1].clone());
+    }
+    let result = setup.client.try_cancel_swaps_batch(&sender, &too_many);
+    assert_eq!(result, Err(Ok(HTLCError::BatchTooLarge.into())));
+}
+
+#[test]
+fn test_cancel_needs_cooldown_and_counterparty() {
+    let setup = setup();
+    let env = &setup.env;
+    let swap = setup.client.get_swap_details(&setup.swap_id).unwrap();
+    let sender = swap.sender.clone();
+    let recipient = Address::generate(env);
+    token::StellarAssetClient::new(env, &swap.token).mint(&sender, &100_000);
+    let swap_id = setup.client.create_swap(
+        &sender,
+        &recipient,
+        &BytesN::from_array(env, &[9u8; 32]),
+        &(env.ledger().timestamp() + 7200),
+        &swap.token,
+        &100_000i128,
+        &Address::generate(env),
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+    let swap_ids = soroban_sdk::vec![env, swap_id.clone()];
+
+    // Swaps younger than the cooldown stay locked
+    setup.client.set_cancel_cooldown(&600);
+    assert_eq!(setup.client.get_cancel_cooldown(), 600);
+    let outcomes = setup.client.cancel_swaps_batch(&sender, &swap_ids);
+    assert_eq!(outcomes, soroban_sdk::vec![env, CancelOutcome::OnCooldown]);
+    env.ledger().with_mut(|li| li.timestamp += 600);
+
+    // The sender alone can't pull a swap the recipient may have matched
+    let sender_auth = MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &setup.client.address,
+            fn_name: "cancel_swaps_batch",
+            args: (sender.clone(), swap_ids.clone()).into_val(env),
+            sub_invokes: &[],
+        },
+    };
+    env.mock_auths(core::slice::from_ref(&sender_auth));
+    assert!(setup.client.try_cancel_swaps_batch(&sender, &swap_ids).is_err());
+    assert_eq!(setup.client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+
+    // With the recipient's consent the swap is cancelled
+    env.mock_auths(&[
+        sender_auth,
+        MockAuth {
+            address: &recipient,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "cancel_swaps_batch",
+                args: (swap_id.clone(),).into_val(env),
+                sub_invokes: &[],
+            },
+        },
+    ]);
+    let outcomes = setup.client.cancel_swaps_batch(&sender, &swap_ids);
+    assert_eq!(outcomes, soroban_sdk::vec![env, CancelOutcome::Cancelled]);
+    assert_eq!(setup.client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Refunded);
+}
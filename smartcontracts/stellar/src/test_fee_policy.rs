@@ -0,0 +1,394 @@
+#![cfg(test)]
+
+use super::*;
+use crate::test_fixtures::{self, Fixture, SwapTerms};
+use broken_fee_policy::BrokenFeePolicy;
+use flat_fee_policy::FlatFeePolicy;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, IntoVal, Val,
+};
+
+/// Sample fee policy quoting a configurable flat fee
+mod flat_fee_policy {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct FlatFeePolicy;
+
+    #[contractimpl]
+    impl FlatFeePolicy {
+        pub fn __constructor(env: Env, fee: i128) {
+            env.storage().instance().set(&symbol_short!("fee"), &fee);
+        }
+
+        pub fn get_fee(env: Env, _token: Address, _amount: i128, _sender: Address) -> i128 {
+            env.storage().instance().get(&symbol_short!("fee")).unwrap()
+        }
+    }
+}
+
+/// Fee policy that always fails
+mod broken_fee_policy {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct BrokenFeePolicy;
+
+    #[contractimpl]
+    impl BrokenFeePolicy {
+        pub fn get_fee(_env: Env, _token: Address, _amount: i128, _sender: Address) -> i128 {
+            panic!("policy unavailable");
+        }
+    }
+}
+
+fn setup() -> Fixture {
+    test_fixtures::setup()
+}
+
+/// Create and claim a 1,000,000 swap, returning the fee collected
+fn claim_and_collect_fee(setup: &Fixture) -> i128 {
+    claim_amount_and_collect_fee(setup, 1_000_000)
+}
+
+/// Create and claim a swap of `amount`, returning the fee collected
+fn claim_amount_and_collect_fee(setup: &Fixture, amount: i128) -> i128 {
+    let env = &setup.env;
+    let sender = Address::generate(env);
+    let recipient = Address::generate(env);
+    setup.mint(&sender, amount);
+
+    let token_client = setup.token_client();
+    let fees_before = fee_balances(setup);
+
+    let swap_id = setup.create_swap(&SwapTerms { sender, recipient: recipient.clone(), amount, ..setup.swap() });
+    setup.client.claim_swap(&swap_id, &setup.preimage(), &None);
+
+    // Everything withheld from the recipient went to the fee recipients
+    let fee = amount - token_client.balance(&recipient);
+    assert_eq!(fee_balances(setup), fees_before + fee);
+    fee
+}
+
+/// Sum the balances of every configured fee recipient
+fn fee_balances(setup: &Fixture) -> i128 {
+    let token_client = setup.token_client();
+    setup.client.get_fee_splits().iter().map(|split| token_client.balance(&split.recipient)).sum()
+}
+
+#[test]
+fn test_fee_policy_quotes_fee() {
+    let setup = setup();
+    let policy = setup.env.register(FlatFeePolicy, (1_234i128,));
+
+    // Internal 0.3% fee without a policy
+    assert_eq!(setup.client.get_fee_policy(), None);
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+
+    // Policy quote replaces the basis-point fee
+    setup.client.set_fee_policy(&Some(policy.clone()));
+    assert_eq!(setup.client.get_fee_policy(), Some(policy));
+    assert_eq!(claim_and_collect_fee(&setup), 1_234);
+
+    // Clearing the policy restores the internal fee
+    setup.client.set_fee_policy(&None);
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+}
+
+#[test]
+fn test_fee_policy_quote_is_capped() {
+    let setup = setup();
+    let policy = setup.env.register(FlatFeePolicy, (900_000i128,));
+    setup.client.set_fee_policy(&Some(policy));
+
+    // Never more than 5% of the amount
+    assert_eq!(claim_and_collect_fee(&setup), 50_000);
+}
+
+#[test]
+fn test_failing_fee_policy_falls_back_to_bps() {
+    let setup = setup();
+    let policy = setup.env.register(BrokenFeePolicy, ());
+    setup.client.set_fee_policy(&Some(policy));
+
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+}
+
+#[test]
+fn test_min_fee_floor() {
+    let setup = setup();
+
+    // 0.3% of 100 rounds down to zero
+    assert_eq!(claim_amount_and_collect_fee(&setup, 100), 0);
+
+    // The floor applies when the basis-point fee is below it
+    setup.client.set_min_fee(&setup.token, &2);
+    assert_eq!(setup.client.get_min_fee(&setup.token), 2);
+    assert_eq!(claim_amount_and_collect_fee(&setup, 100), 2);
+
+    // A larger basis-point fee wins over the floor
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+
+    // The floor never takes more than 5% of the amount
+    setup.client.set_min_fee(&setup.token, &10);
+    assert_eq!(claim_amount_and_collect_fee(&setup, 100), 5);
+
+    // Floors are per token
+    let other_token = setup.env.register_stellar_asset_contract_v2(Address::generate(&setup.env)).address();
+    assert_eq!(setup.client.get_min_fee(&other_token), 0);
+
+    // Negative floors are rejected and zero disables the floor
+    let result = setup.client.try_set_min_fee(&setup.token, &-1);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidFee.into())));
+    setup.client.set_min_fee(&setup.token, &0);
+    assert_eq!(claim_amount_and_collect_fee(&setup, 100), 0);
+}
+
+/// Build fee splits from `(recipient, share_bps)` pairs
+fn fee_splits(env: &Env, shares: &[(&Address, u32)]) -> Vec<FeeSplit> {
+    let mut splits = Vec::new(env);
+    for (recipient, share_bps) in shares {
+        splits.push_back(FeeSplit { recipient: (*recipient).clone(), share_bps: *share_bps });
+    }
+    splits
+}
+
+#[test]
+fn test_two_way_fee_split() {
+    let setup = setup();
+    let operations = Address::generate(&setup.env);
+    let token_client = setup.token_client();
+
+    let splits = fee_splits(&setup.env, &[(&setup.fee_recipient, 6_000), (&operations, 4_000)]);
+    setup.client.set_fee_splits(&splits);
+    assert_eq!(setup.client.get_fee_splits(), splits);
+
+    // 3,000 splits exactly along the shares
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+    assert_eq!(token_client.balance(&setup.fee_recipient), 1_800);
+    assert_eq!(token_client.balance(&operations), 1_200);
+
+    // An odd fee of 3 leaves a remainder for the first recipient
+    setup.client.set_fee_splits(&fee_splits(&setup.env, &[(&setup.fee_recipient, 5_000), (&operations, 5_000)]));
+    assert_eq!(claim_amount_and_collect_fee(&setup, 1_000), 3);
+    assert_eq!(token_client.balance(&setup.fee_recipient), 1_802);
+    assert_eq!(token_client.balance(&operations), 1_201);
+
+    // The single-recipient setter replaces the splits
+    setup.client.update_fee_recipient(&operations);
+    assert_eq!(setup.client.get_fee_splits(), fee_splits(&setup.env, &[(&operations, 10_000)]));
+    assert_eq!(setup.client.get_contract_stats().fee_recipient, Some(operations.clone()));
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+    assert_eq!(token_client.balance(&setup.fee_recipient), 1_802);
+    assert_eq!(token_client.balance(&operations), 4_201);
+}
+
+#[test]
+fn test_three_way_fee_split() {
+    let setup = setup();
+    let operations = Address::generate(&setup.env);
+    let insurance = Address::generate(&setup.env);
+    let token_client = setup.token_client();
+
+    setup.client.set_fee_splits(&fee_splits(
+        &setup.env,
+        &[(&setup.fee_recipient, 3_334), (&operations, 3_333), (&insurance, 3_333)],
+    ));
+
+    // A fee of 30 pays 10, 9 and 9, with the remainder of 2 to the first
+    assert_eq!(claim_amount_and_collect_fee(&setup, 10_000), 30);
+    assert_eq!(token_client.balance(&setup.fee_recipient), 12);
+    assert_eq!(token_client.balance(&operations), 9);
+    assert_eq!(token_client.balance(&insurance), 9);
+
+    // A fee of 3,000 is paid out in full
+    assert_eq!(claim_and_collect_fee(&setup), 3_000);
+    assert_eq!(token_client.balance(&setup.fee_recipient), 12 + 1_002);
+    assert_eq!(token_client.balance(&operations), 9 + 999);
+    assert_eq!(token_client.balance(&insurance), 9 + 999);
+}
+
+#[test]
+fn test_invalid_fee_splits_rejected() {
+    let setup = setup();
+    let env = &setup.env;
+    let operations = Address::generate(env);
+    let invalid = Err(Ok(HTLCError::InvalidFee.into()));
+
+    // Shares must sum to exactly 10000 bps
+    let result = setup.client.try_set_fee_splits(&fee_splits(env, &[(&setup.fee_recipient, 6_000), (&operations, 3_000)]));
+    assert_eq!(result, invalid);
+    let result = setup.client.try_set_fee_splits(&fee_splits(env, &[(&setup.fee_recipient, 6_000), (&operations, 5_000)]));
+    assert_eq!(result, invalid);
+
+    // Empty splits and zero shares are rejected
+    assert_eq!(setup.client.try_set_fee_splits(&Vec::new(env)), invalid);
+    let result = setup.client.try_set_fee_splits(&fee_splits(env, &[(&setup.fee_recipient, 10_000), (&operations, 0)]));
+    assert_eq!(result, invalid);
+
+    // So is an unbounded list of recipients
+    let mut splits = Vec::new(env);
+    for i in 0..=MAX_FEE_SPLITS {
+        let share_bps = if i == 0 { 10_000 - MAX_FEE_SPLITS } else { 1 };
+        splits.push_back(FeeSplit { recipient: Address::generate(env), share_bps });
+    }
+    assert_eq!(setup.client.try_set_fee_splits(&splits), invalid);
+
+    // The original single recipient is untouched
+    assert_eq!(setup.client.get_fee_splits(), fee_splits(env, &[(&setup.fee_recipient, 10_000)]));
+}
+
+/// Create a 1,000,000 swap with the given fee payer, returning its ID and
+/// the sender, recipient and preimage
+fn create_with_fee_payer(setup: &Fixture, fee_payer: FeePayer) -> (String, Address, Address, BytesN<32>) {
+    create_with_terms(setup, fee_payer, None)
+}
+
+/// Create a 1,000,000 swap with the given fee payer and minimum net payout
+fn create_with_terms(
+    setup: &Fixture,
+    fee_payer: FeePayer,
+    min_net_amount: Option<i128>,
+) -> (String, Address, Address, BytesN<32>) {
+    let env = &setup.env;
+    let sender = Address::generate(env);
+    let recipient = Address::generate(env);
+    setup.mint(&sender, 1_010_000);
+
+    let swap_id = setup.create_swap(&SwapTerms {
+        sender: sender.clone(),
+        recipient: recipient.clone(),
+        options: SwapOptions { fee_payer, min_net_amount, ..SwapOptions::default() },
+        ..setup.swap()
+    });
+    (swap_id, sender, recipient, setup.preimage())
+}
+
+#[test]
+fn test_sender_pays_fee_on_top() {
+    let setup = setup();
+    let env = &setup.env;
+    let token_client = setup.token_client();
+    let contract = setup.client.address.clone();
+
+    let quote = setup.client.quote_swap(&setup.token, &1_000_000, &Address::generate(env), &None, &FeePayer::Sender);
+    assert_eq!(quote, SwapQuote { fee: 3_000, sender_total: 1_003_000, recipient_payout: 1_000_000 });
+
+    // The fee is locked on top of the amount and announced at creation
+    let (swap_id, sender, recipient, preimage) = create_with_fee_payer(&setup, FeePayer::Sender);
+    let created: Vec<Val> = (String::from_str(env, "swap_created"), EVENT_SCHEMA_VERSION).into_val(env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(_, topics, _)| topics == &created)
+        .expect("creation event not emitted");
+    let data: Vec<Val> = data.into_val(env);
+    let announced: FeePayer = data.get(12).unwrap().into_val(env);
+    assert_eq!(announced, FeePayer::Sender);
+    assert_eq!(token_client.balance(&sender), 1_010_000 - quote.sender_total);
+    assert_eq!(token_client.balance(&contract), quote.sender_total);
+    assert_eq!(setup.client.get_locked_balance(&setup.token), quote.sender_total);
+
+    let swap = setup.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!((swap.fee_payer, swap.prepaid_fee), (FeePayer::Sender, 3_000));
+
+    // The recipient receives the full amount
+    setup.client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), quote.recipient_payout);
+    assert_eq!(token_client.balance(&setup.fee_recipient), quote.fee);
+    assert_eq!(token_client.balance(&contract), 0);
+    assert_eq!(setup.client.get_locked_balance(&setup.token), 0);
+}
+
+#[test]
+fn test_recipient_pays_fee_from_payout() {
+    let setup = setup();
+    let env = &setup.env;
+    let token_client = setup.token_client();
+    let contract = setup.client.address.clone();
+
+    let quote = setup.client.quote_swap(&setup.token, &1_000_000, &Address::generate(env), &None, &FeePayer::Recipient);
+    assert_eq!(quote, SwapQuote { fee: 3_000, sender_total: 1_000_000, recipient_payout: 997_000 });
+
+    // Only the amount is locked at creation
+    let (swap_id, sender, recipient, preimage) = create_with_fee_payer(&setup, FeePayer::Recipient);
+    assert_eq!(token_client.balance(&sender), 1_010_000 - quote.sender_total);
+    assert_eq!(token_client.balance(&contract), quote.sender_total);
+
+    let swap = setup.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!((swap.fee_payer, swap.prepaid_fee), (FeePayer::Recipient, 0));
+
+    // The fee is deducted from the payout
+    setup.client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), quote.recipient_payout);
+    assert_eq!(token_client.balance(&setup.fee_recipient), quote.fee);
+    assert_eq!(token_client.balance(&contract), 0);
+}
+
+#[test]
+fn test_prepaid_fee_returned_on_refund() {
+    let setup = setup();
+    let env = &setup.env;
+    let token_client = setup.token_client();
+
+    let (swap_id, sender, _, _) = create_with_fee_payer(&setup, FeePayer::Sender);
+    assert_eq!(token_client.balance(&sender), 7_000);
+
+    // An unclaimed swap charges no fee, so the sender gets everything back
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    setup.client.refund_swap(&swap_id, &None);
+    assert_eq!(token_client.balance(&sender), 1_010_000);
+    assert_eq!(token_client.balance(&setup.fee_recipient), 0);
+    assert_eq!(token_client.balance(&setup.client.address), 0);
+    assert_eq!(setup.client.get_locked_balance(&setup.token), 0);
+}
+
+#[test]
+fn test_min_net_payout_guard() {
+    let setup = setup();
+    let env = &setup.env;
+    let token_client = setup.token_client();
+
+    // At the current fee the payout meets the minimum and is announced
+    let (swap_id, _, recipient, preimage) = create_with_terms(&setup, FeePayer::Recipient, Some(997_000));
+    setup.client.claim_swap(&swap_id, &preimage, &None);
+    let claimed: Vec<Val> = (String::from_str(env, "swap_claimed"), EVENT_SCHEMA_VERSION).into_val(env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(_, topics, _)| topics == &claimed)
+        .expect("claim event not emitted");
+    let data: Vec<Val> = data.into_val(env);
+    let payout: i128 = data.last().unwrap().into_val(env);
+    assert_eq!(payout, 997_000);
+    assert_eq!(token_client.balance(&recipient), 997_000);
+
+    // A fee raised after creation would short the recipient, so the claim fails
+    let (swap_id, _, recipient, preimage) = create_with_terms(&setup, FeePayer::Recipient, Some(997_000));
+    setup.client.update_protocol_fee(&50);
+    assert_eq!(
+        setup.client.try_claim_swap(&swap_id, &preimage, &None),
+        Err(Ok(HTLCError::PayoutBelowMinimum.into()))
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(setup.client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+
+    // A fee policy quote is held to the same minimum
+    let policy = env.register(FlatFeePolicy, (2_000i128,));
+    setup.client.set_fee_policy(&Some(policy));
+    setup.client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), 998_000);
+
+    // A sender-paid fee leaves the full amount for the recipient
+    let (swap_id, _, recipient, preimage) = create_with_terms(&setup, FeePayer::Sender, Some(1_000_000));
+    setup.client.claim_swap(&swap_id, &preimage, &None);
+    assert_eq!(token_client.balance(&recipient), 1_000_000);
+
+    // The minimum must be positive and within the amount
+    for min_net_amount in [0i128, 1_000_001] {
+        let result = setup.try_create_swap(&SwapTerms {
+            sender: Address::generate(env),
+            hashlock: BytesN::from_array(env, &[1u8; 32]),
+            options: SwapOptions { min_net_amount: Some(min_net_amount), ..SwapOptions::default() },
+            ..setup.swap()
+        });
+        assert_eq!(result, Err(HTLCError::InvalidAmount.into()));
+    }
+}
@@ -0,0 +1,166 @@
+#![cfg(test)]
+//! Shared setup for the contract tests
+//!
+//! `setup` registers and initializes the contract against a Stellar asset
+//! token with every auth mocked. `Fixture::swap` returns default swap terms
+//! that tests override with struct update syntax.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, StellarAssetContract},
+    token, Address, Bytes, BytesN, Env,
+};
+
+/// Protocol fee the contract is initialized with, in basis points
+pub const FEE_BPS: u32 = 30;
+
+/// Preimage of the default swap's hashlock
+pub const PREIMAGE: [u8; 32] = [42u8; 32];
+
+/// Seconds from now until the default swap's timelock
+pub const SWAP_DURATION: u64 = 7200;
+
+/// Amount locked by the default swap
+pub const SWAP_AMOUNT: i128 = 1_000_000;
+
+/// Sepolia, the chain ID every test swap points at
+pub const ETH_CHAIN_ID: u64 = 11155111;
+
+pub fn create_test_env() -> (Env, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+    (env, admin, fee_recipient, token)
+}
+
+pub fn fund_account(env: &Env, token: &Address, account: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, token).mint(account, &amount);
+}
+
+pub fn hashlock_of(env: &Env, preimage: &BytesN<32>) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from_array(env, &preimage.to_array())).into()
+}
+
+/// Initialized contract, its Stellar asset token and the usual parties
+pub struct Fixture {
+    pub env: Env,
+    pub contract_id: Address,
+    pub client: StellarHTLCClient<'static>,
+    pub fee_recipient: Address,
+    pub asset: StellarAssetContract,
+    pub token: Address,
+    pub sender: Address,
+    pub recipient: Address,
+    pub eth_contract: Address,
+}
+
+/// Terms of a swap created through `Fixture::create_swap`
+pub struct SwapTerms {
+    pub sender: Address,
+    pub recipient: Address,
+    pub hashlock: BytesN<32>,
+    pub timelock: u64,
+    pub amount: i128,
+    pub options: SwapOptions,
+}
+
+/// Register and initialize the contract with a fresh Stellar asset token
+///
+/// No account is funded; tests mint what they lock.
+pub fn setup() -> Fixture {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = asset.address();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &FEE_BPS, &None);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let eth_contract = Address::generate(&env);
+
+    Fixture {
+        env,
+        contract_id,
+        client,
+        fee_recipient,
+        asset,
+        token,
+        sender,
+        recipient,
+        eth_contract,
+    }
+}
+
+impl Fixture {
+    pub fn token_client(&self) -> token::Client<'_> {
+        token::Client::new(&self.env, &self.token)
+    }
+
+    pub fn asset_admin(&self) -> token::StellarAssetClient<'_> {
+        token::StellarAssetClient::new(&self.env, &self.token)
+    }
+
+    pub fn mint(&self, to: &Address, amount: i128) {
+        fund_account(&self.env, &self.token, to, amount);
+    }
+
+    pub fn preimage(&self) -> BytesN<32> {
+        BytesN::from_array(&self.env, &PREIMAGE)
+    }
+
+    /// Default terms: `SWAP_AMOUNT` from `sender` to `recipient` under the
+    /// hashlock of `PREIMAGE`, refundable `SWAP_DURATION` seconds from now
+    pub fn swap(&self) -> SwapTerms {
+        SwapTerms {
+            sender: self.sender.clone(),
+            recipient: self.recipient.clone(),
+            hashlock: hashlock_of(&self.env, &self.preimage()),
+            timelock: self.env.ledger().timestamp() + SWAP_DURATION,
+            amount: SWAP_AMOUNT,
+            options: SwapOptions::default(),
+        }
+    }
+
+    pub fn create_swap(&self, terms: &SwapTerms) -> String {
+        self.client.create_swap(
+            &terms.sender,
+            &terms.recipient,
+            &terms.hashlock,
+            &terms.timelock,
+            &self.token,
+            &terms.amount,
+            &self.eth_contract,
+            &ETH_CHAIN_ID,
+            &terms.options,
+        )
+    }
+
+    /// Create a swap, returning the contract error if creation fails
+    pub fn try_create_swap(&self, terms: &SwapTerms) -> Result<String, soroban_sdk::Error> {
+        let result = self.client.try_create_swap(
+            &terms.sender,
+            &terms.recipient,
+            &terms.hashlock,
+            &terms.timelock,
+            &self.token,
+            &terms.amount,
+            &self.eth_contract,
+            &ETH_CHAIN_ID,
+            &terms.options,
+        );
+        match result {
+            Ok(Ok(swap_id)) => Ok(swap_id),
+            Err(Ok(error)) => Err(error),
+            _ => panic!("unexpected create_swap result"),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+#![cfg(test)]
+
+use super::*;
+use crate::test_fixtures::{self, Fixture, SwapTerms};
+use broken_price_oracle::BrokenPriceOracle;
+use fixed_price_oracle::FixedPriceOracle;
+use soroban_sdk::{testutils::Address as _, Address, BytesN};
+
+/// Sample oracle quoting a configurable price for every token
+mod fixed_price_oracle {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct FixedPriceOracle;
+
+    #[contractimpl]
+    impl FixedPriceOracle {
+        pub fn __constructor(env: Env, price: i128) {
+            env.storage().instance().set(&symbol_short!("price"), &price);
+        }
+
+        pub fn price(env: Env, _token: Address) -> i128 {
+            env.storage().instance().get(&symbol_short!("price")).unwrap()
+        }
+    }
+}
+
+/// Oracle that always fails
+mod broken_price_oracle {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct BrokenPriceOracle;
+
+    #[contractimpl]
+    impl BrokenPriceOracle {
+        pub fn price(_env: Env, _token: Address) -> i128 {
+            panic!("oracle unavailable");
+        }
+    }
+}
+
+fn setup() -> Fixture {
+    let s = test_fixtures::setup();
+    s.mint(&s.sender, 10_000_000);
+    s
+}
+
+/// Try to lock 1,000,000 (0.1 of a 7-decimal token) against an optional
+/// amount of a 6-decimal counter asset
+fn try_create(setup: &Fixture, counter_amount: Option<i128>) -> Result<String, soroban_sdk::Error> {
+    let env = &setup.env;
+    let (counter_token, counter_decimals) = match counter_amount {
+        Some(_) => (Some(BytesN::from_array(env, &[7u8; 20])), Some(6u32)),
+        None => (None, None),
+    };
+    setup.try_create_swap(&SwapTerms {
+        recipient: Address::generate(env),
+        hashlock: BytesN::from_array(env, &[1u8; 32]),
+        options: SwapOptions { counter_amount, counter_token, counter_decimals, ..SwapOptions::default() },
+        ..setup.swap()
+    })
+}
+
+#[test]
+fn test_swaps_priced_within_deviation_accepted() {
+    let setup = setup();
+    // 0.1 token for 0.05 units of the counter asset is a rate of 2.0
+    let oracle = setup.env.register(FixedPriceOracle, (20_000_000i128,));
+    setup.client.set_price_oracle(&Some(oracle.clone()), &100);
+    assert_eq!(setup.client.get_price_oracle(), Some((oracle, 100)));
+
+    let swap_id = try_create(&setup, Some(50_000)).unwrap();
+    assert_eq!(setup.client.get_swap_rate(&swap_id), Some(20_000_000));
+
+    // Up to 1% off the oracle price either way
+    assert!(try_create(&setup, Some(49_505)).is_ok());
+    assert!(try_create(&setup, Some(50_500)).is_ok());
+
+    // Swaps without a counter amount have nothing to check
+    assert!(try_create(&setup, None).is_ok());
+}
+
+#[test]
+fn test_mispriced_swaps_rejected() {
+    let setup = setup();
+    let oracle = setup.env.register(FixedPriceOracle, (20_000_000i128,));
+    setup.client.set_price_oracle(&Some(oracle), &100);
+
+    // A fat-fingered counter amount lands far outside the range
+    assert_eq!(try_create(&setup, Some(500_000)), Err(HTLCError::PriceDeviation.into()));
+    assert_eq!(try_create(&setup, Some(5_000)), Err(HTLCError::PriceDeviation.into()));
+    assert_eq!(try_create(&setup, Some(49_000)), Err(HTLCError::PriceDeviation.into()));
+    assert_eq!(setup.client.get_contract_stats().total_swaps_created, 0);
+
+    // Clearing the oracle lifts the check
+    setup.client.set_price_oracle(&None, &0);
+    assert_eq!(setup.client.get_price_oracle(), None);
+    assert!(try_create(&setup, Some(500_000)).is_ok());
+}
+
+#[test]
+fn test_failing_oracle_rejects_priced_swaps() {
+    let setup = setup();
+    let oracle = setup.env.register(BrokenPriceOracle, ());
+    setup.client.set_price_oracle(&Some(oracle), &100);
+
+    assert_eq!(try_create(&setup, Some(50_000)), Err(HTLCError::PriceDeviation.into()));
+    assert!(try_create(&setup, None).is_ok());
+}
+
+#[test]
+fn test_invalid_max_deviation_rejected() {
+    let setup = setup();
+    let oracle = setup.env.register(FixedPriceOracle, (20_000_000i128,));
+
+    let result = setup.client.try_set_price_oracle(&Some(oracle), &10_001);
+    assert_eq!(result, Err(Ok(HTLCError::InvalidAmount.into())));
+    assert_eq!(setup.client.get_price_oracle(), None);
+}
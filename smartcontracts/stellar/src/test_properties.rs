@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use crate::test_fixtures::{self, hashlock_of, Fixture, SwapTerms, FEE_BPS};
+use proptest::prelude::*;
+use soroban_sdk::{testutils::Ledger, BytesN};
+
+/// Ledger time at which every property starts
+const START_TIME: u64 = 1_000;
+
+/// Build a fresh contract and Stellar asset token at `START_TIME`
+///
+/// Addresses come from the test environment's deterministic generator, so
+/// every case starts from the same state apart from its generated inputs.
+fn setup() -> Fixture {
+    let s = test_fixtures::setup();
+    s.env.ledger().with_mut(|li| li.timestamp = START_TIME);
+    s
+}
+
+fn create_swap(s: &Fixture, hashlock: &BytesN<32>, timelock: u64, amount: i128) -> String {
+    s.mint(&s.sender, amount);
+    s.create_swap(&SwapTerms { hashlock: hashlock.clone(), timelock, amount, ..s.swap() })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_only_committed_preimage_claims(
+        secret in any::<[u8; 32]>(),
+        guess in any::<[u8; 32]>(),
+        amount in 1i128..=i128::MAX,
+    ) {
+        prop_assume!(secret != guess);
+        let s = setup();
+        let preimage = BytesN::from_array(&s.env, &secret);
+        let swap_id = create_swap(&s, &hashlock_of(&s.env, &preimage), START_TIME + 7200, amount);
+
+        // A wrong preimage never claims
+        let wrong = BytesN::from_array(&s.env, &guess);
+        let result = s.client.try_claim_swap(&swap_id, &wrong, &None);
+        prop_assert_eq!(result, Err(Ok(HTLCError::InvalidPreimage.into())));
+        prop_assert!(!s.client.verify_preimage(&swap_id, &wrong, &None));
+
+        // The committed preimage claims before expiry, net of the exact fee
+        prop_assert!(s.client.try_claim_swap(&swap_id, &preimage, &None).is_ok());
+        let token_client = s.token_client();
+        let fee = token_client.balance(&s.fee_recipient);
+        prop_assert_eq!(fee, amount / 10_000 * FEE_BPS as i128 + amount % 10_000 * FEE_BPS as i128 / 10_000);
+        prop_assert_eq!(token_client.balance(&s.recipient), amount - fee);
+        prop_assert_eq!(token_client.balance(&s.contract_id), 0);
+    }
+
+    #[test]
+    fn prop_claim_and_refund_windows_never_overlap(
+        duration in (MIN_TIMELOCK_DURATION + 1)..=MAX_TIMELOCK_DURATION,
+        elapsed in 0u64..=(2 * MAX_TIMELOCK_DURATION),
+    ) {
+        let s = setup();
+        let preimage = BytesN::from_array(&s.env, &[42u8; 32]);
+        let hashlock = hashlock_of(&s.env, &preimage);
+        let timelock = START_TIME + duration;
+        let claim_id = create_swap(&s, &hashlock, timelock, 1_000_000);
+        let refund_id = create_swap(&s, &hashlock, timelock, 1_000_000);
+
+        let now = START_TIME + elapsed;
+        s.env.ledger().with_mut(|li| li.timestamp = now);
+        let claimed = s.client.try_claim_swap(&claim_id, &preimage, &None).is_ok();
+        let refunded = s.client.try_refund_swap(&refund_id, &None).is_ok();
+
+        // Refunds only after expiry, claims only before the cutoff buffer
+        prop_assert!(!(claimed && refunded));
+        prop_assert_eq!(refunded, now >= timelock);
+        prop_assert_eq!(claimed, now <= timelock - DEFAULT_CLAIM_CUTOFF_BUFFER);
+    }
+
+    #[test]
+    fn prop_contract_balance_matches_locked_amounts(
+        amounts in prop::collection::vec(1i128..1_000_000_000_000i128, 1..8),
+        actions in prop::collection::vec(0u8..3, 8),
+    ) {
+        let s = setup();
+        let preimage = BytesN::from_array(&s.env, &[42u8; 32]);
+        let hashlock = hashlock_of(&s.env, &preimage);
+        let timelock = START_TIME + 7200;
+        let token_client = s.token_client();
+
+        let mut outstanding = 0i128;
+        let mut swap_ids = std::vec::Vec::new();
+        for amount in amounts.iter() {
+            swap_ids.push(create_swap(&s, &hashlock, timelock, *amount));
+            outstanding += amount;
+            prop_assert_eq!(token_client.balance(&s.contract_id), outstanding);
+        }
+
+        // Claims settle before expiry; action 0 leaves a swap open
+        for (swap_id, (amount, action)) in swap_ids.iter().zip(amounts.iter().zip(actions.iter())) {
+            if *action == 1 {
+                s.client.claim_swap(swap_id, &preimage, &None);
+                outstanding -= amount;
+            }
+            prop_assert_eq!(token_client.balance(&s.contract_id), outstanding);
+        }
+
+        // Refunds settle after expiry
+        s.env.ledger().with_mut(|li| li.timestamp = timelock);
+        for (swap_id, (amount, action)) in swap_ids.iter().zip(amounts.iter().zip(actions.iter())) {
+            if *action == 2 {
+                s.client.refund_swap(swap_id, &None);
+                outstanding -= amount;
+            }
+            prop_assert_eq!(token_client.balance(&s.contract_id), outstanding);
+        }
+        prop_assert_eq!(s.client.get_locked_balance(&s.token), outstanding);
+    }
+}
@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::Address as _,
+    Address, Bytes, BytesN, Env,
+};
+
+#[contracttype]
+enum MockTokenKey {
+    Balance(Address),
+    Target,
+    Reentered,
+}
+
+/// Token that tries to claim the swap again from inside its `transfer`
+#[contract]
+pub struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().instance().set(&MockTokenKey::Balance(to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().instance().get(&MockTokenKey::Balance(id)).unwrap_or(0)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    /// Arm the token to reenter `claim_swap` on the next transfer
+    pub fn arm(env: Env, htlc: Address, swap_id: String, preimage: BytesN<32>) {
+        env.storage().instance().set(&MockTokenKey::Target, &(htlc, swap_id, preimage));
+    }
+
+    /// Whether a reentrant claim went through
+    pub fn reentered(env: Env) -> bool {
+        env.storage().instance().get(&MockTokenKey::Reentered).unwrap_or(false)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage().instance().set(&MockTokenKey::Balance(from), &(from_balance - amount));
+        Self::mint(env.clone(), to, amount);
+
+        let target: Option<(Address, String, BytesN<32>)> = env.storage().instance().get(&MockTokenKey::Target);
+        if let Some((htlc, swap_id, preimage)) = target {
+            env.storage().instance().remove(&MockTokenKey::Target);
+            let result = StellarHTLCClient::new(&env, &htlc).try_claim_swap(&swap_id, &preimage, &None);
+            env.storage().instance().set(&MockTokenKey::Reentered, &result.is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_reentrant_claim_blocked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let token = env.register(ReentrantToken, ());
+    let token_client = ReentrantTokenClient::new(&env, &token);
+
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &fee_recipient, &0, &None);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token_client.mint(&sender, &1_000_000);
+    let preimage = BytesN::from_array(&env, &[42u8; 32]);
+    let hashlock: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &preimage.to_array())).into();
+
+    let swap_id = client.create_swap(
+        &sender,
+        &recipient,
+        &hashlock,
+        &7200u64,
+        &token,
+        &1_000_000i128,
+        &Address::generate(&env),
+        &11155111u64,
+        &SwapOptions::default(),
+    );
+
+    // The payout transfer tries to claim the same swap a second time
+    token_client.arm(&contract_id, &swap_id, &preimage);
+    client.claim_swap(&swap_id, &preimage, &None);
+
+    assert!(!token_client.reentered());
+    assert_eq!(token_client.balance(&recipient), 1_000_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(client.get_locked_balance(&token), 0);
+    assert_eq!(client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Claimed);
+    assert_eq!(client.get_contract_stats().total_swaps_completed, 1);
+}
+
+#[test]
+fn test_guard_rejects_calls_mid_operation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let contract_id = env.register(StellarHTLC, ());
+    let client = StellarHTLCClient::new(&env, &contract_id);
+    client.initialize(&admin, &Address::generate(&env), &30, &None);
+
+    // While an operation is calling out, fund-moving entrypoints fail
+    env.as_contract(&contract_id, || storage::set_in_progress(&env, true));
+    let result = client.try_sweep_stray_tokens(&token, &1);
+    assert_eq!(result, Err(Ok(HTLCError::ReentrantCall.into())));
+
+    env.as_contract(&contract_id, || storage::set_in_progress(&env, false));
+    let result = client.try_sweep_stray_tokens(&token, &1);
+    assert_eq!(result, Err(Ok(HTLCError::InsufficientBalance.into())));
+}
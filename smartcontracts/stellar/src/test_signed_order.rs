@@ -0,0 +1,155 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use crate::test_fixtures::{self, hashlock_of, Fixture, ETH_CHAIN_ID, PREIMAGE};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token,
+    xdr::ToXdr,
+    Address, BytesN, Env,
+};
+
+struct SignedOrderSetup {
+    env: Env,
+    client: StellarHTLCClient<'static>,
+    token: Address,
+    maker: Address,
+    signing_key: SigningKey,
+}
+
+fn setup() -> SignedOrderSetup {
+    let s = test_fixtures::setup();
+
+    // Maker registers a signing key and approves the contract once
+    let maker = s.sender.clone();
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&s.env, &signing_key.verifying_key().to_bytes());
+    s.client.register_maker_key(&maker, &public_key);
+    s.mint(&maker, 3_000_000);
+    s.token_client().approve(&maker, &s.contract_id, &3_000_000, &1000);
+
+    let Fixture { env, client, token, .. } = s;
+    SignedOrderSetup { env, client, token, maker, signing_key }
+}
+
+fn order_terms(setup: &SignedOrderSetup, nonce: u64) -> OrderTerms {
+    let env = &setup.env;
+    OrderTerms {
+        contract: setup.client.address.clone(),
+        maker: setup.maker.clone(),
+        maker_public_key: BytesN::from_array(env, &setup.signing_key.verifying_key().to_bytes()),
+        recipient: Address::generate(env),
+        token: setup.token.clone(),
+        amount: 1_000_000,
+        hashlock: hashlock_of(env, &BytesN::from_array(env, &PREIMAGE)),
+        timelock: 7200,
+        expiry: 600,
+        nonce,
+        eth_contract: Address::generate(env),
+        eth_chain_id: ETH_CHAIN_ID,
+    }
+}
+
+fn sign(setup: &SignedOrderSetup, terms: &OrderTerms) -> SignedOrder {
+    let env = &setup.env;
+    let message: std::vec::Vec<u8> = terms.clone().to_xdr(env).iter().collect();
+    let signature = setup.signing_key.sign(&message).to_bytes();
+    SignedOrder {
+        terms: terms.clone(),
+        signature: BytesN::from_array(env, &signature),
+    }
+}
+
+#[test]
+fn test_fill_signed_order() {
+    let setup = setup();
+    let terms = order_terms(&setup, 1);
+
+    let swap_id = setup.client.fill_signed_order(&sign(&setup, &terms));
+
+    let swap = setup.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.sender, setup.maker);
+    assert_eq!(swap.recipient, terms.recipient);
+    assert_eq!(swap.amount, terms.amount);
+    assert_eq!(swap.hashlock, terms.hashlock);
+    assert_eq!(token::Client::new(&setup.env, &setup.token).balance(&setup.maker), 2_000_000);
+}
+
+#[test]
+fn test_fill_signed_order_rejects_tampered_terms() {
+    let setup = setup();
+    let terms = order_terms(&setup, 1);
+    let mut order = sign(&setup, &terms);
+
+    // Resolver raises the amount after the maker signed
+    order.terms.amount = 3_000_000;
+
+    assert!(setup.client.try_fill_signed_order(&order).is_err());
+    assert_eq!(token::Client::new(&setup.env, &setup.token).balance(&setup.maker), 3_000_000);
+}
+
+#[test]
+fn test_fill_signed_order_rejects_expired_order() {
+    let setup = setup();
+    let terms = order_terms(&setup, 1);
+    let order = sign(&setup, &terms);
+
+    setup.env.ledger().with_mut(|li| {
+        li.timestamp = terms.expiry + 1;
+    });
+
+    let result = setup.client.try_fill_signed_order(&order);
+    assert_eq!(result, Err(Ok(HTLCError::OrderExpired.into())));
+}
+
+#[test]
+fn test_fill_signed_order_rejects_nonce_reuse() {
+    let setup = setup();
+    let order = sign(&setup, &order_terms(&setup, 1));
+    setup.client.fill_signed_order(&order);
+
+    // Replaying the order or signing new terms with the same nonce both fail
+    let result = setup.client.try_fill_signed_order(&order);
+    assert_eq!(result, Err(Ok(HTLCError::NonceAlreadyUsed.into())));
+
+    let reused = sign(&setup, &order_terms(&setup, 1));
+    let result = setup.client.try_fill_signed_order(&reused);
+    assert_eq!(result, Err(Ok(HTLCError::NonceAlreadyUsed.into())));
+
+    // A fresh nonce fills
+    setup.client.fill_signed_order(&sign(&setup, &order_terms(&setup, 2)));
+}
+
+#[test]
+fn test_fill_signed_order_rejects_other_contract() {
+    let setup = setup();
+    let mut terms = order_terms(&setup, 1);
+
+    // Validly signed, but for another HTLC deployment
+    terms.contract = Address::generate(&setup.env);
+    let order = sign(&setup, &terms);
+
+    let result = setup.client.try_fill_signed_order(&order);
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
+    assert_eq!(token::Client::new(&setup.env, &setup.token).balance(&setup.maker), 3_000_000);
+}
+
+#[test]
+fn test_fill_signed_order_rejects_unregistered_key() {
+    let setup = setup();
+    let mut terms = order_terms(&setup, 1);
+
+    // Order signed by a key that isn't registered for the maker
+    let other_key = SigningKey::from_bytes(&[8u8; 32]);
+    terms.maker_public_key = BytesN::from_array(&setup.env, &other_key.verifying_key().to_bytes());
+    let message: std::vec::Vec<u8> = terms.clone().to_xdr(&setup.env).iter().collect();
+    let order = SignedOrder {
+        terms,
+        signature: BytesN::from_array(&setup.env, &other_key.sign(&message).to_bytes()),
+    };
+
+    let result = setup.client.try_fill_signed_order(&order);
+    assert_eq!(result, Err(Ok(HTLCError::Unauthorized.into())));
+}
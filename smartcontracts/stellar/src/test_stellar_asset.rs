@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use super::*;
+use crate::test_fixtures::{self, Fixture};
+use soroban_sdk::{
+    testutils::{Events, IssuerFlags, Ledger},
+    symbol_short, IntoVal, Val, Vec,
+};
+
+/// Register a Stellar Asset Contract whose issuer can revoke authorization
+/// and fund the sender from the issuer
+fn setup() -> Fixture {
+    let s = test_fixtures::setup();
+    s.asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    s.mint(&s.sender, 1_000_000);
+    s
+}
+
+/// Assert the balances of the sender, the contract, the recipient and the
+/// fee recipient, in that order
+fn assert_balances(s: &Fixture, expected: [i128; 4]) {
+    assert_eq!(s.token_client().balance(&s.sender), expected[0]);
+    assert_eq!(s.token_client().balance(&s.contract_id), expected[1]);
+    assert_eq!(s.token_client().balance(&s.recipient), expected[2]);
+    assert_eq!(s.token_client().balance(&s.fee_recipient), expected[3]);
+}
+
+#[test]
+fn test_create_and_claim_cycle() {
+    let s = setup();
+    let preimage = s.preimage();
+    assert_balances(&s, [1_000_000, 0, 0, 0]);
+
+    let swap_id = s.create_swap(&s.swap());
+    assert_balances(&s, [0, 1_000_000, 0, 0]);
+
+    s.client.claim_swap(&swap_id, &preimage, &None);
+    assert_balances(&s, [0, 0, 997_000, 3_000]);
+    assert_eq!(s.client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Claimed);
+}
+
+#[test]
+fn test_create_and_refund_cycle() {
+    let s = setup();
+
+    let swap_id = s.create_swap(&s.swap());
+    assert_balances(&s, [0, 1_000_000, 0, 0]);
+
+    s.env.ledger().with_mut(|li| li.timestamp = 7200);
+    s.client.refund_swap(&swap_id, &None);
+    assert_balances(&s, [1_000_000, 0, 0, 0]);
+    assert_eq!(s.client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Refunded);
+}
+
+#[test]
+fn test_unauthorized_sender_cannot_create() {
+    let s = setup();
+    s.asset_admin().set_authorized(&s.sender, &false);
+
+    let result = s.try_create_swap(&s.swap());
+    assert_eq!(result, Err(HTLCError::TokenTransferFailed.into()));
+    assert_balances(&s, [1_000_000, 0, 0, 0]);
+    assert_eq!(s.client.get_contract_stats().total_swaps_created, 0);
+}
+
+#[test]
+fn test_unauthorized_recipient_withdrawal_fails_cleanly() {
+    let s = setup();
+    let preimage = s.preimage();
+    let swap_id = s.create_swap(&s.swap());
+
+    // The claim settles into a claimable balance
+    s.asset_admin().set_authorized(&s.recipient, &false);
+    s.client.claim_swap(&swap_id, &preimage, &None);
+    assert_balances(&s, [0, 997_000, 0, 3_000]);
+    assert_eq!(s.client.get_claimable_balance(&s.recipient, &s.token), 997_000);
+
+    // Withdrawing without authorization fails and keeps the credit
+    let result = s.client.try_withdraw_claimable(&s.recipient, &s.token);
+    assert_eq!(result, Err(Ok(HTLCError::TokenTransferFailed.into())));
+    assert_eq!(s.client.get_claimable_balance(&s.recipient, &s.token), 997_000);
+    assert_eq!(s.client.get_locked_balance(&s.token), 997_000);
+    assert_balances(&s, [0, 997_000, 0, 3_000]);
+
+    s.asset_admin().set_authorized(&s.recipient, &true);
+    s.client.withdraw_claimable(&s.recipient, &s.token);
+    assert_balances(&s, [0, 0, 997_000, 3_000]);
+}
+
+#[test]
+fn test_claim_to_unauthorized_trustline_keeps_preimage() {
+    let s = setup();
+    let preimage = s.preimage();
+    let swap_id = s.create_swap(&s.swap());
+
+    // New holders of an auth-required asset start unauthorized; only the
+    // fee recipient has been authorized
+    s.asset.issuer().set_flag(IssuerFlags::RequiredFlag);
+    s.asset_admin().set_authorized(&s.fee_recipient, &true);
+
+    // The claim goes through and the payout is held for the recipient,
+    // with the token's error on the fallback event
+    s.client.claim_swap(&swap_id, &preimage, &None);
+    let env = &s.env;
+    let expected_topics: Vec<Val> = (symbol_short!("claim_fb"), swap_id.clone(), EVENT_SCHEMA_VERSION).into_val(env);
+    let (_, _, data) = env.events().all().iter()
+        .find(|(contract, topics, _)| contract == &s.contract_id && topics == &expected_topics)
+        .expect("fallback event not emitted");
+    let event: ClaimFallbackEvent = data.into_val(env);
+    assert_eq!(event.amount, 997_000);
+    assert_ne!(event.error_code, 0);
+
+    // The revealed preimage stays on record
+    let swap = s.client.get_swap_details(&swap_id).unwrap();
+    assert_eq!(swap.status, SwapStatus::Claimed);
+    assert_eq!(swap.preimage, Some(preimage));
+    assert_eq!(s.client.get_claimable_balance(&s.recipient, &s.token), 997_000);
+    assert_balances(&s, [0, 997_000, 0, 3_000]);
+
+    // Once the trustline is authorized the recipient withdraws
+    s.asset_admin().set_authorized(&s.recipient, &true);
+    s.client.withdraw_claimable(&s.recipient, &s.token);
+    assert_balances(&s, [0, 0, 997_000, 3_000]);
+}
+
+#[test]
+fn test_unauthorized_sender_refund_fails_cleanly() {
+    let s = setup();
+    let swap_id = s.create_swap(&s.swap());
+
+    // Refunding to a deauthorized sender fails without touching the swap
+    s.env.ledger().with_mut(|li| li.timestamp = 7200);
+    s.asset_admin().set_authorized(&s.sender, &false);
+    let result = s.client.try_refund_swap(&swap_id, &None);
+    assert_eq!(result, Err(Ok(HTLCError::TokenTransferFailed.into())));
+    assert_eq!(s.client.get_swap_details(&swap_id).unwrap().status, SwapStatus::Pending);
+    assert_balances(&s, [0, 1_000_000, 0, 0]);
+
+    // Once reauthorized the refund goes through
+    s.asset_admin().set_authorized(&s.sender, &true);
+    s.client.refund_swap(&swap_id, &None);
+    assert_balances(&s, [1_000_000, 0, 0, 0]);
+}
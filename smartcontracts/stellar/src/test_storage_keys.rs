@@ -0,0 +1,142 @@
+#![cfg(test)]
+
+use super::*;
+use super::storage::{ChainKey, ConfigKey, ResolverKey, SwapKey, TokenKey, UserKey};
+use soroban_sdk::{
+    testutils::Address as _,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
+};
+
+/// One key of every variant of every storage domain
+fn all_keys(env: &Env) -> Vec<Val> {
+    let address = Address::generate(env);
+    let swap_id = String::from_str(env, "swap_1");
+    let hash = BytesN::from_array(env, &[1u8; 32]);
+
+    let config: [Val; 32] = [
+        ConfigKey::Admin.into_val(env),
+        ConfigKey::FeeSplits.into_val(env),
+        ConfigKey::ProtocolFeeBps.into_val(env),
+        ConfigKey::FeePolicy.into_val(env),
+        ConfigKey::PriceOracle.into_val(env),
+        ConfigKey::MaxPriceDeviationBps.into_val(env),
+        ConfigKey::EthAttester.into_val(env),
+        ConfigKey::CounterpartOracle.into_val(env),
+        ConfigKey::SwapCounter.into_val(env),
+        ConfigKey::TemplateCounter.into_val(env),
+        ConfigKey::ResolverRewardBps.into_val(env),
+        ConfigKey::TotalSwapsCreated.into_val(env),
+        ConfigKey::TotalSwapsCompleted.into_val(env),
+        ConfigKey::StatusCount(SwapStatus::Pending).into_val(env),
+        ConfigKey::PermissionedMode.into_val(env),
+        ConfigKey::RecipientRegistrationRequired.into_val(env),
+        ConfigKey::PermissionedCreation.into_val(env),
+        ConfigKey::StalenessWindow.into_val(env),
+        ConfigKey::ResolverCount.into_val(env),
+        ConfigKey::MaxResolvers.into_val(env),
+        ConfigKey::CollateralRatioBps.into_val(env),
+        ConfigKey::AbandonmentPeriod.into_val(env),
+        ConfigKey::AbandonmentBountyBps.into_val(env),
+        ConfigKey::RefundBountyBps.into_val(env),
+        ConfigKey::ClaimCutoffBuffer.into_val(env),
+        ConfigKey::CancelCooldown.into_val(env),
+        ConfigKey::StatsRecount.into_val(env),
+        ConfigKey::AutoConfirmAfter.into_val(env),
+        ConfigKey::RenounceRequestedAt.into_val(env),
+        ConfigKey::AdminRenounced.into_val(env),
+        ConfigKey::InitializationDeadline.into_val(env),
+        ConfigKey::InProgress.into_val(env),
+    ];
+    let swap: [Val; 14] = [
+        SwapKey::Swap(swap_id.clone()).into_val(env),
+        SwapKey::SwapMeta(swap_id.clone()).into_val(env),
+        SwapKey::EthAttestation(swap_id.clone()).into_val(env),
+        SwapKey::RevealedPreimage(swap_id.clone()).into_val(env),
+        SwapKey::ClaimPreauthorized(swap_id.clone()).into_val(env),
+        SwapKey::SwapFailure(swap_id.clone()).into_val(env),
+        SwapKey::SwapCounterpart(swap_id.clone()).into_val(env),
+        SwapKey::CounterpartSwap(hash.clone()).into_val(env),
+        SwapKey::Template(1).into_val(env),
+        SwapKey::GroupSwaps(hash.clone()).into_val(env),
+        SwapKey::SwapWatchers(swap_id.clone()).into_val(env),
+        SwapKey::SwapByIndex(1).into_val(env),
+        SwapKey::CreatedAtByIndex(1).into_val(env),
+        SwapKey::IdempotentSwap(address.clone(), hash.clone()).into_val(env),
+    ];
+    let resolver: [Val; 5] = [
+        ResolverKey::Resolver(address.clone()).into_val(env),
+        ResolverKey::ResolverSwaps(address.clone()).into_val(env),
+        ResolverKey::ResolverNonce(address.clone()).into_val(env),
+        ResolverKey::ResolverReward(address.clone(), address.clone()).into_val(env),
+        ResolverKey::ResolverLeaderboard.into_val(env),
+    ];
+    let user: [Val; 9] = [
+        UserKey::UserSwaps(address.clone()).into_val(env),
+        UserKey::MakerKey(address.clone()).into_val(env),
+        UserKey::UsedNonce(address.clone(), 1).into_val(env),
+        UserKey::ClaimableBalance(address.clone(), address.clone()).into_val(env),
+        UserKey::AllowedSender(address.clone()).into_val(env),
+        UserKey::RegisteredRecipient(address.clone()).into_val(env),
+        UserKey::BlockedAddress(address.clone()).into_val(env),
+        UserKey::FeeExempt(address.clone()).into_val(env),
+        UserKey::AllowedCreator(address.clone()).into_val(env),
+    ];
+    let token: [Val; 4] = [
+        TokenKey::MinFee(address.clone()).into_val(env),
+        TokenKey::LockedBalance(address.clone()).into_val(env),
+        TokenKey::TotalLocked(address.clone()).into_val(env),
+        TokenKey::RewardPool(address.clone()).into_val(env),
+    ];
+    let chain: [Val; 2] = [
+        ChainKey::ChainConfig(1).into_val(env),
+        ChainKey::ChainFinalityDelay(1).into_val(env),
+    ];
+
+    let mut keys = Vec::new(env);
+    for domain in [&config[..], &swap[..], &resolver[..], &user[..], &token[..], &chain[..]] {
+        for key in domain {
+            keys.push_back(*key);
+        }
+    }
+    keys
+}
+
+#[test]
+fn test_storage_keys_never_collide() {
+    let env = Env::default();
+    let keys = all_keys(&env);
+
+    // Every key serializes differently
+    let mut encoded: Map<Bytes, u32> = Map::new(&env);
+    for (i, key) in keys.iter().enumerate() {
+        encoded.set(key.to_xdr(&env), i as u32);
+    }
+    assert_eq!(encoded.len(), keys.len());
+
+    // Variant names, which lead each encoding, are unique across domains,
+    // so keys stay apart whatever their payloads
+    let mut names: Map<Symbol, u32> = Map::new(&env);
+    for (i, key) in keys.iter().enumerate() {
+        let parts: Vec<Val> = key.into_val(&env);
+        let name: Symbol = parts.get(0).unwrap().into_val(&env);
+        names.set(name, i as u32);
+    }
+    assert_eq!(names.len(), keys.len());
+}
+
+#[test]
+fn test_storage_key_encoding_is_stable() {
+    let env = Env::default();
+
+    // Keys encode as their variant name and payload, independent of the
+    // enum they live in, so entries written before the split still resolve
+    let admin: Val = ConfigKey::Admin.into_val(&env);
+    let expected: Val = (Symbol::new(&env, "Admin"),).into_val(&env);
+    assert_eq!(admin.to_xdr(&env), expected.to_xdr(&env));
+
+    let swap_id = String::from_str(&env, "swap_1");
+    let swap: Val = SwapKey::Swap(swap_id.clone()).into_val(&env);
+    let expected: Val = (Symbol::new(&env, "Swap"), swap_id).into_val(&env);
+    assert_eq!(swap.to_xdr(&env), expected.to_xdr(&env));
+}
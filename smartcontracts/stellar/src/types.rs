@@ -6,9 +6,100 @@ pub const MIN_TIMELOCK_DURATION: u64 = 3600;
 /// Maximum timelock duration (7 days in seconds) 
 pub const MAX_TIMELOCK_DURATION: u64 = 604800;
 
+/// Default time before the timelock when claims stop being accepted (5 minutes in seconds)
+pub const DEFAULT_CLAIM_CUTOFF_BUFFER: u64 = 300;
+
+/// Time within which a renounce request must be confirmed (1 hour in seconds)
+pub const RENOUNCE_CONFIRMATION_WINDOW: u64 = 3600;
+
 /// Maximum protocol fee (5% in basis points)
 pub const MAX_PROTOCOL_FEE_BPS: u32 = 500;
 
+/// Smallest amount a swap may lock, in the token's smallest unit
+pub const MIN_SWAP_AMOUNT: i128 = 1;
+
+/// Maximum number of swaps recounted by a single `recompute_stats` call
+pub const MAX_STATS_BATCH_SIZE: u32 = 100;
+
+/// Maximum number of tokens checked by a single `health_check` call
+pub const MAX_HEALTH_CHECK_TOKENS: u32 = 20;
+
+/// Maximum number of recipients the protocol fee is split between
+pub const MAX_FEE_SPLITS: u32 = 10;
+
+/// Maximum number of resolvers registered by a single batch
+pub const MAX_RESOLVER_BATCH_SIZE: u32 = 20;
+
+/// Maximum number of swaps cancelled by a single batch
+pub const MAX_CANCEL_BATCH_SIZE: u32 = 20;
+
+/// Maximum decimals accepted for an EVM counter asset
+pub const MAX_COUNTER_DECIMALS: u32 = 38;
+
+/// Default time after the timelock before anyone may refund a swap (30 days in seconds)
+pub const DEFAULT_ABANDONMENT_PERIOD: u64 = 2_592_000;
+
+/// Default bounty paid for refunding an abandoned swap (0.1% in basis points)
+pub const DEFAULT_ABANDONMENT_BOUNTY_BPS: u32 = 10;
+
+/// Maximum bounty paid for refunding an abandoned swap (1% in basis points)
+pub const MAX_ABANDONMENT_BOUNTY_BPS: u32 = 100;
+
+/// Maximum bounty paid to a keeper refunding an expired swap (1% in basis points)
+pub const MAX_REFUND_BOUNTY_BPS: u32 = 100;
+
+/// Maximum number of swaps sharing one order group
+pub const MAX_GROUP_SWAPS: u32 = 100;
+
+/// Maximum number of addresses watching one swap
+pub const MAX_SWAP_WATCHERS: u32 = 10;
+
+/// Maximum number of swap IDs returned by a single page of `get_all_swaps`
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of swaps returned by a single `get_swaps_range` call
+pub const MAX_RANGE_SIZE: u64 = 50;
+
+/// Maximum number of resolvers tracked on the volume leaderboard
+pub const MAX_LEADERBOARD_SIZE: u32 = 50;
+
+/// Default time a resolver stays live without a heartbeat (24 hours in seconds)
+pub const DEFAULT_STALENESS_WINDOW: u64 = 86400;
+
+/// EVM decimals assumed when a swap doesn't record a counter asset
+pub const DEFAULT_EVM_DECIMALS: u32 = 18;
+
+/// Shortest hashlock prefix a swap may be compared on, in bytes
+pub const MIN_HASHLOCK_LEN: u32 = 20;
+
+/// Maximum length of a swap memo in bytes
+pub const MAX_MEMO_LENGTH: u32 = 64;
+
+/// Version of the event schema, appended as the last topic of every event
+/// 
+/// Bump whenever an event's topics or payload change shape, so indexers
+/// can route events by version.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Fixed-point scale of normalized exchange rates (7 decimals)
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Ledgers closed per day at ~5 seconds per ledger
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Remaining instance TTL, in ledgers, below which a bump extends it
+pub const INSTANCE_TTL_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+
+/// Instance TTL, in ledgers, a bump extends to
+pub const INSTANCE_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Remaining persistent entry TTL, in ledgers, below which a write extends it
+pub const PERSISTENT_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Persistent entry TTL, in ledgers, a write extends to; outlasts the
+/// longest timelock plus the default abandonment period
+pub const PERSISTENT_TTL_EXTEND_TO: u32 = 90 * DAY_IN_LEDGERS;
+
 /// Swap status enumeration
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -20,12 +111,32 @@ pub enum SwapStatus {
     Failed,     // Swap failed for other reasons
 }
 
+/// Failure category recorded when a swap is marked failed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FailureCode {
+    EthLegFailed,       // Ethereum counterpart could not be completed
+    Timeout,            // Counterparty did not act in time
+    ResolverDefault,    // Resolver failed to fill the swap
+    Other,              // Any other failure, see the reason
+}
+
+/// Party the protocol fee is charged to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeePayer {
+    Sender,     // Fee locked on top of the amount at creation
+    Recipient,  // Fee deducted from the payout at claim
+}
+
 /// HTLC Swap structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Swap {
     /// Unique swap identifier
     pub id: String,
+    /// Position of the swap in creation order, starting at 1
+    pub index: u64,
     /// Address that locked the funds
     pub sender: Address,
     /// Address that can claim the funds
@@ -36,6 +147,8 @@ pub struct Swap {
     pub amount: i128,
     /// SHA-256 hash of the secret
     pub hashlock: BytesN<32>,
+    /// Number of leading hashlock bytes compared at claim (32 = full hash)
+    pub hashlock_len: u32,
     /// UNIX timestamp after which refund is possible
     pub timelock: u64,
     /// Current status of the swap
@@ -48,12 +161,452 @@ pub struct Swap {
     pub refunded_at: Option<u64>,
     /// Secret preimage (revealed after claim)
     pub preimage: Option<BytesN<32>>,
+    /// Salt revealed with the preimage for salted commitments
+    pub salt: Option<BytesN<32>>,
     /// Ethereum contract address for cross-chain coordination
     pub eth_contract: Address,
     /// Ethereum chain ID
     pub eth_chain_id: u64,
     /// Optional resolver address for 1inch Fusion+ integration
     pub resolver: Option<Address>,
+    /// Optional contract notified after a successful claim
+    pub claim_hook: Option<Address>,
+    /// Optional contract notified after a successful refund
+    pub refund_hook: Option<Address>,
+    /// Agreed amount of the EVM asset, in its smallest unit
+    pub counter_amount: Option<i128>,
+    /// EVM token address of the counter asset
+    pub counter_token: Option<BytesN<20>>,
+    /// Decimals of the EVM counter asset
+    pub counter_decimals: Option<u32>,
+    /// Decimals of the Stellar token
+    pub stellar_decimals: u32,
+    /// Decimals of the EVM asset
+    pub evm_decimals: u32,
+    /// Optional integrator reference, e.g. an invoice ID
+    pub memo: Option<String>,
+    /// Parent order shared by the partial fills of one Fusion+ order
+    pub order_group: Option<BytesN<32>>,
+    /// Token the swap is quoted in, if not the locked token (informational)
+    pub quote_token: Option<Address>,
+    /// Intended value of the swap in the quote token (informational)
+    pub quote_amount: Option<i128>,
+    /// Optional keeper allowed to trigger the refund on the sender's behalf
+    pub refund_authority: Option<Address>,
+    /// Address refunds are paid to (the sender unless set at creation)
+    pub refund_to: Address,
+    /// Latest timestamp by which the Ethereum leg must have finalized for a claim
+    pub eth_finality_deadline: Option<u64>,
+    /// Timestamp from which anyone may submit the claim (public claim window)
+    pub public_unlock: Option<u64>,
+    /// Latest claim time, after which the swap is refundable before the timelock
+    pub claim_deadline: Option<u64>,
+    /// Party the protocol fee is charged to
+    pub fee_payer: FeePayer,
+    /// Fee locked on top of the amount when the sender pays it
+    pub prepaid_fee: i128,
+    /// Smallest net payout the recipient accepts at claim
+    pub min_net_amount: Option<i128>,
+    /// Time the Ethereum lock is final; claims are rejected before it
+    pub finality_at: u64,
+    /// Part of the protocol fee set aside for the resolver at claim
+    pub resolver_reward: i128,
+}
+
+/// Hot part of a stored swap, read on every claim and refund
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapCore {
+    /// Unique swap identifier
+    pub id: String,
+    /// Address that locked the funds
+    pub sender: Address,
+    /// Address that can claim the funds
+    pub recipient: Address,
+    /// Stellar asset contract address
+    pub token: Address,
+    /// Amount of tokens locked
+    pub amount: i128,
+    /// SHA-256 hash of the secret
+    pub hashlock: BytesN<32>,
+    /// Number of leading hashlock bytes compared at claim (32 = full hash)
+    pub hashlock_len: u32,
+    /// UNIX timestamp after which refund is possible
+    pub timelock: u64,
+    /// Current status of the swap
+    pub status: SwapStatus,
+    /// Timestamp when swap was claimed (if applicable)
+    pub claimed_at: Option<u64>,
+    /// Timestamp when swap was refunded (if applicable)
+    pub refunded_at: Option<u64>,
+    /// Secret preimage (revealed after claim)
+    pub preimage: Option<BytesN<32>>,
+    /// Salt revealed with the preimage for salted commitments
+    pub salt: Option<BytesN<32>>,
+    /// Optional resolver address for 1inch Fusion+ integration
+    pub resolver: Option<Address>,
+    /// Optional contract notified after a successful claim
+    pub claim_hook: Option<Address>,
+    /// Optional contract notified after a successful refund
+    pub refund_hook: Option<Address>,
+    /// Optional keeper allowed to trigger the refund on the sender's behalf
+    pub refund_authority: Option<Address>,
+    /// Address refunds are paid to (the sender unless set at creation)
+    pub refund_to: Address,
+    /// Latest timestamp by which the Ethereum leg must have finalized for a claim
+    pub eth_finality_deadline: Option<u64>,
+    /// Timestamp from which anyone may submit the claim (public claim window)
+    pub public_unlock: Option<u64>,
+    /// Latest claim time, after which the swap is refundable before the timelock
+    pub claim_deadline: Option<u64>,
+    /// Party the protocol fee is charged to
+    pub fee_payer: FeePayer,
+    /// Fee locked on top of the amount when the sender pays it
+    pub prepaid_fee: i128,
+    /// Smallest net payout the recipient accepts at claim
+    pub min_net_amount: Option<i128>,
+    /// Time the Ethereum lock is final; claims are rejected before it
+    pub finality_at: u64,
+    /// Part of the protocol fee set aside for the resolver at claim
+    pub resolver_reward: i128,
+}
+
+/// Cold part of a stored swap, only read by views
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapMeta {
+    /// Position of the swap in creation order, starting at 1
+    pub index: u64,
+    /// Timestamp when swap was created
+    pub created_at: u64,
+    /// Ethereum contract address for cross-chain coordination
+    pub eth_contract: Address,
+    /// Ethereum chain ID
+    pub eth_chain_id: u64,
+    /// Agreed amount of the EVM asset, in its smallest unit
+    pub counter_amount: Option<i128>,
+    /// EVM token address of the counter asset
+    pub counter_token: Option<BytesN<20>>,
+    /// Decimals of the EVM counter asset
+    pub counter_decimals: Option<u32>,
+    /// Decimals of the Stellar token
+    pub stellar_decimals: u32,
+    /// Decimals of the EVM asset
+    pub evm_decimals: u32,
+    /// Optional integrator reference, e.g. an invoice ID
+    pub memo: Option<String>,
+    /// Parent order shared by the partial fills of one Fusion+ order
+    pub order_group: Option<BytesN<32>>,
+    /// Token the swap is quoted in, if not the locked token (informational)
+    pub quote_token: Option<Address>,
+    /// Intended value of the swap in the quote token (informational)
+    pub quote_amount: Option<i128>,
+}
+
+/// Compact view of a swap for list views
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapSummary {
+    /// Unique swap identifier
+    pub id: String,
+    /// Current status of the swap
+    pub status: SwapStatus,
+    /// Amount of tokens locked
+    pub amount: i128,
+    /// UNIX timestamp after which refund is possible
+    pub timelock: u64,
+    /// Address that can claim the funds
+    pub recipient: Address,
+}
+
+/// Fee breakdown of a prospective swap
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapQuote {
+    /// Protocol fee charged on the swap
+    pub fee: i128,
+    /// Total the sender transfers at creation
+    pub sender_total: i128,
+    /// Amount the recipient receives on claim
+    pub recipient_payout: i128,
+}
+
+/// Stored swap with values derived at the current ledger time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapView {
+    /// Stored swap
+    pub swap: Swap,
+    /// Amount the recipient would receive on claim
+    pub net_amount: i128,
+    /// Protocol fee the claim would collect
+    pub protocol_fee: i128,
+    /// Seconds left until the timelock (0 once it has passed)
+    pub seconds_to_timelock: u64,
+    /// Whether the recipient could claim the swap now
+    pub is_claimable: bool,
+    /// Whether the swap could be refunded now
+    pub is_refundable: bool,
+}
+
+/// Comparison of a candidate preimage against a swap's hashlock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimDiagnosis {
+    /// SHA-256 of the preimage, followed by the salt if one was given
+    pub computed_hash: BytesN<32>,
+    /// Hashlock stored on the swap
+    pub hashlock: BytesN<32>,
+    /// Number of leading bytes compared
+    pub hashlock_len: u32,
+    /// Whether the preimage would pass the claim's hashlock check
+    pub matches: bool,
+}
+
+/// Storage TTL thresholds and extensions, in ledgers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlPolicy {
+    /// Remaining instance TTL below which a bump extends it
+    pub instance_threshold: u32,
+    /// Instance TTL a bump extends to
+    pub instance_extend_to: u32,
+    /// Remaining persistent entry TTL below which a write extends it
+    pub persistent_threshold: u32,
+    /// Persistent entry TTL a write extends to
+    pub persistent_extend_to: u32,
+}
+
+/// Completion progress of an order group
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupProgress {
+    /// Number of swaps in the group
+    pub swaps: u32,
+    /// Number of swaps claimed
+    pub filled_swaps: u32,
+    /// Amount locked across all swaps in the group
+    pub total_amount: i128,
+    /// Amount of the claimed swaps
+    pub filled_amount: i128,
+}
+
+/// Outcome of cancelling one swap in a batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CancelOutcome {
+    Cancelled,      // Swap refunded to its refund address
+    NotFound,       // No swap with this ID
+    NotSender,      // Swap belongs to another sender
+    NotCancellable, // Swap is no longer pending or the counterpart side has started
+    OnCooldown,     // Swap was created less than the cancel cooldown ago
+}
+
+/// Ethereum escrow events a relayer can attest to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CounterpartEvent {
+    Funded,     // Counterpart escrow funded on Ethereum
+    Claimed,    // Counterpart escrow claimed, revealing the preimage
+    Refunded,   // Counterpart escrow refunded on Ethereum
+}
+
+/// Attestation payload signed by the Ethereum attester
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EthAttestationPayload {
+    /// HTLC contract the attestation is addressed to
+    pub contract: Address,
+    /// Swap the observation applies to
+    pub swap_id: String,
+    /// Observed counterpart event
+    pub event: CounterpartEvent,
+    /// Preimage revealed on Ethereum (required for `Claimed`)
+    pub preimage: Option<BytesN<32>>,
+    /// Ethereum transaction hash of the observed event
+    pub eth_tx_hash: BytesN<32>,
+}
+
+/// Verified attestation stored alongside a swap for audit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EthAttestation {
+    /// Observed counterpart event
+    pub event: CounterpartEvent,
+    /// Preimage revealed on Ethereum, if any
+    pub preimage: Option<BytesN<32>>,
+    /// Ethereum transaction hash of the observed event
+    pub eth_tx_hash: BytesN<32>,
+    /// Ethereum address of the attester that signed the payload
+    pub attester: BytesN<20>,
+    /// Timestamp when the attestation was submitted
+    pub attested_at: u64,
+}
+
+/// Optional terms of a new swap
+/// 
+/// `SwapOptions::default()` leaves every option unset, pulls funds with a
+/// direct transfer and charges the fee to the recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapOptions {
+    /// 1inch Fusion+ resolver assigned to the swap
+    pub resolver: Option<Address>,
+    /// Pull funds with `transfer_from` against an allowance granted to the
+    /// contract instead of a direct sender-authorized `transfer`
+    pub use_allowance: bool,
+    /// Contract notified via `on_htlc_claimed` after claim
+    pub claim_hook: Option<Address>,
+    /// Agreed amount of the EVM asset, recorded for audit
+    pub counter_amount: Option<i128>,
+    /// EVM token address of the counter asset
+    pub counter_token: Option<BytesN<20>>,
+    /// Decimals of the EVM counter asset
+    pub counter_decimals: Option<u32>,
+    /// Integrator reference of at most `MAX_MEMO_LENGTH` bytes
+    pub memo: Option<String>,
+    /// Keeper allowed to trigger the refund
+    pub refund_authority: Option<Address>,
+    /// Latest claim time, at or before the timelock, by which the Ethereum
+    /// leg must have finalized
+    pub eth_finality_deadline: Option<u64>,
+    /// Number of leading hashlock bytes compared at claim, for truncated
+    /// Ethereum commitments (defaults to 32)
+    pub hashlock_len: Option<u32>,
+    /// Time, before the timelock, from which any caller may submit the
+    /// claim; funds still go to the recipient
+    pub public_unlock: Option<u64>,
+    /// Latest claim time, at or before the timelock, after which the sender
+    /// may refund early
+    pub claim_deadline: Option<u64>,
+    /// Address refunds are paid to instead of the sender, e.g. the user an
+    /// exchange funds the swap for
+    pub refund_to: Option<Address>,
+    /// `Sender` locks the protocol fee on top of the amount at creation,
+    /// `Recipient` has it deducted from the payout at claim
+    pub fee_payer: FeePayer,
+    /// Parent order shared by the swaps filling parts of one Fusion+ order
+    /// (at most `MAX_GROUP_SWAPS` per group)
+    pub order_group: Option<BytesN<32>>,
+    /// Smallest payout, net of fees, the recipient accepts; claims paying
+    /// less fail with `PayoutBelowMinimum`
+    pub min_net_amount: Option<i128>,
+    /// Token a resolver values the swap in, when it differs from the swap
+    /// token; recorded and emitted only, nothing is converted
+    pub quote_token: Option<Address>,
+    /// Intended value of the swap in `quote_token`
+    pub quote_amount: Option<i128>,
+    /// Contract notified via `on_swap_refunded` after refund
+    pub refund_hook: Option<Address>,
+}
+
+impl Default for SwapOptions {
+    fn default() -> Self {
+        SwapOptions {
+            resolver: None,
+            use_allowance: false,
+            claim_hook: None,
+            counter_amount: None,
+            counter_token: None,
+            counter_decimals: None,
+            memo: None,
+            refund_authority: None,
+            eth_finality_deadline: None,
+            hashlock_len: None,
+            public_unlock: None,
+            claim_deadline: None,
+            refund_to: None,
+            fee_payer: FeePayer::Recipient,
+            order_group: None,
+            min_net_amount: None,
+            quote_token: None,
+            quote_amount: None,
+            refund_hook: None,
+        }
+    }
+}
+
+/// Terms of a new swap before it is stored
+pub(crate) struct NewSwap {
+    pub sender: Address,
+    pub recipient: Address,
+    pub hashlock: BytesN<32>,
+    pub timelock: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub eth_contract: Address,
+    pub eth_chain_id: u64,
+    pub options: SwapOptions,
+}
+
+/// Order terms signed off-chain by a maker
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderTerms {
+    /// HTLC contract the order may be filled on
+    pub contract: Address,
+    /// Address whose funds are locked
+    pub maker: Address,
+    /// Ed25519 public key that signed the order
+    pub maker_public_key: BytesN<32>,
+    /// Address that can claim the swap
+    pub recipient: Address,
+    /// Stellar asset contract address
+    pub token: Address,
+    /// Amount of tokens to lock
+    pub amount: i128,
+    /// SHA-256 hash of the secret
+    pub hashlock: BytesN<32>,
+    /// UNIX timestamp after which refund is possible
+    pub timelock: u64,
+    /// UNIX timestamp after which the order can no longer be filled
+    pub expiry: u64,
+    /// Single-use order nonce
+    pub nonce: u64,
+    /// Ethereum contract address for cross-chain coordination
+    pub eth_contract: Address,
+    /// Ethereum chain ID
+    pub eth_chain_id: u64,
+}
+
+/// Maker order with an ed25519 signature over the XDR of its terms
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedOrder {
+    /// Signed order terms
+    pub terms: OrderTerms,
+    /// Ed25519 signature over `terms.to_xdr()`
+    pub signature: BytesN<64>,
+}
+
+/// Swap terms shared by the swaps of a repeated corridor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapTemplate {
+    /// Stellar asset contract address
+    pub token: Address,
+    /// Ethereum chain ID
+    pub eth_chain_id: u64,
+    /// Ethereum contract address for cross-chain coordination
+    pub eth_contract: Address,
+    /// Optional resolver assigned to every swap
+    pub resolver: Option<Address>,
+    /// Seconds from creation to each swap's timelock
+    pub timelock_offset: u64,
+    /// Party the protocol fee is charged to
+    pub fee_payer: FeePayer,
+    /// Whether senders other than the owner may use the template
+    pub is_public: bool,
+}
+
+/// Stored swap template and its owner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemplateInfo {
+    /// Address that created the template
+    pub owner: Address,
+    /// Shared swap terms
+    pub template: SwapTemplate,
 }
 
 /// Resolver information for 1inch Fusion+ integration
@@ -66,12 +619,46 @@ pub struct ResolverInfo {
     pub collateral_token: Address,
     /// Minimum collateral amount
     pub min_collateral: i128,
+    /// Collateral currently deposited with the contract
+    pub deposited_collateral: i128,
+    /// Number of assigned swaps that are not yet claimed or refunded
+    pub open_swaps: u32,
+    /// Sum of the amounts of those swaps
+    pub open_value: i128,
+    /// Largest swap amount the resolver can be assigned (0 = unlimited)
+    pub max_swap_amount: i128,
     /// Whether resolver is active
     pub is_active: bool,
     /// Total number of swaps resolved
     pub total_resolved: u64,
+    /// Sum of claimed amounts of swaps resolved
+    pub total_volume: i128,
     /// Timestamp when resolver was registered
     pub created_at: u64,
+    /// Timestamp of the resolver's latest heartbeat
+    pub last_active: u64,
+}
+
+/// Share of the protocol fee paid to one recipient
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSplit {
+    /// Address receiving this share
+    pub recipient: Address,
+    /// Share of each fee in basis points of the fee
+    pub share_bps: u32,
+}
+
+/// One resolver in a batch registration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolverRegistration {
+    /// Resolver address
+    pub resolver: Address,
+    /// Token used for collateral
+    pub collateral_token: Address,
+    /// Minimum collateral amount required
+    pub min_collateral: i128,
 }
 
 /// Contract statistics structure
@@ -82,10 +669,77 @@ pub struct ContractStats {
     pub total_swaps_created: u64,
     /// Total number of swaps completed
     pub total_swaps_completed: u64,
+    /// Total number of swaps refunded
+    pub total_swaps_refunded: u64,
+    /// Total number of swaps marked as failed
+    pub total_swaps_failed: u64,
+    /// Number of swaps still pending or active
+    pub active_swaps: u64,
     /// Current protocol fee in basis points
     pub protocol_fee_bps: u32,
-    /// Contract administrator
-    pub admin: Address,
-    /// Protocol fee recipient
-    pub fee_recipient: Address,
+    /// Contract administrator, `None` once renounced
+    pub admin: Option<Address>,
+    /// Whether the admin has been renounced and configuration is frozen
+    pub admin_renounced: bool,
+    /// Primary protocol fee recipient (first fee split), `None` before initialization
+    pub fee_recipient: Option<Address>,
+}
+
+/// Protocol limits applied to new swaps and configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Limits {
+    /// Shortest timelock from creation, in seconds
+    pub min_timelock_duration: u64,
+    /// Longest timelock from creation, in seconds
+    pub max_timelock_duration: u64,
+    /// Highest protocol fee the admin may set, in basis points
+    pub max_protocol_fee_bps: u32,
+    /// Smallest amount a swap may lock
+    pub min_swap_amount: i128,
+    /// Most open swaps a user may have (0 = unlimited)
+    pub max_open_swaps_per_user: u32,
+    /// Time before the timelock when claims stop being accepted
+    pub claim_cutoff_buffer: u64,
+    /// Time after the timelock before anyone may refund a swap
+    pub abandonment_period: u64,
+}
+
+/// Number of swaps currently in each status
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusCounts {
+    pub pending: u64,
+    pub active: u64,
+    pub claimed: u64,
+    pub refunded: u64,
+    pub failed: u64,
+}
+
+/// Progress of a statistics recount spanning several calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatsRecount {
+    /// Index of the next swap to count
+    pub next_index: u64,
+    /// Status counts of the swaps counted so far
+    pub counts: StatusCounts,
+}
+
+/// Result of checking the contract's internal accounting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthReport {
+    /// Whether every check below holds
+    pub healthy: bool,
+    /// Status counts add up to the swaps created
+    pub status_counts_match: bool,
+    /// Open swaps equal those created minus the claimed, refunded and failed ones
+    pub open_count_matches: bool,
+    /// Completed swaps don't exceed those created and no locked balance is negative
+    pub counters_non_negative: bool,
+    /// Each token's locked balance covers the principal of its open swaps
+    pub locked_covers_open_swaps: bool,
+    /// The contract holds at least the locked balance of each token
+    pub solvent: bool,
 }
\ No newline at end of file